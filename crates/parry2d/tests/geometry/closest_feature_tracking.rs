@@ -0,0 +1,67 @@
+use na::{Isometry2, Vector2};
+use parry2d::query::{self, ClosestFeatureTracker};
+use parry2d::shape::{Ball, ConvexPolygon, Cuboid, FeatureId, Shape};
+
+fn check_tracked_distance(
+    tracker: &mut ClosestFeatureTracker,
+    pos12: &Isometry2<f32>,
+    shape1: &dyn Shape,
+    shape2: &dyn Shape,
+) {
+    let tracked = tracker
+        .closest_points(pos12, shape1, shape2)
+        .unwrap()
+        .unwrap();
+    let expected = query::details::distance_support_map_support_map(
+        pos12,
+        shape1.as_support_map().unwrap(),
+        shape2.as_support_map().unwrap(),
+    );
+
+    assert!(tracked.distance <= expected + 1.0e-4);
+    assert_relative_eq!(tracked.distance, expected, epsilon = 5.0e-3);
+    assert!(shape1.distance_to_local_point(&tracked.point1, true) <= 1.0e-4);
+    assert!(shape2.distance_to_local_point(&tracked.point2, true) <= 1.0e-4);
+    assert_ne!(tracked.feature1, FeatureId::Unknown);
+    assert_ne!(tracked.feature2, FeatureId::Unknown);
+}
+
+#[test]
+fn track_closest_features_between_cuboid_and_polygon() {
+    let cuboid = Cuboid::new(Vector2::new(1.0, 0.5));
+    let poly = ConvexPolygon::from_convex_polyline(Ball::new(0.7).to_polyline(12)).unwrap();
+    let mut tracker = ClosestFeatureTracker::new();
+
+    for i in 0..200 {
+        let t = i as f32 * 0.05;
+        let pos12 = Isometry2::new(Vector2::new(3.0 * t.cos(), 2.0 * t.sin()), 0.7 * t);
+        check_tracked_distance(&mut tracker, &pos12, &cuboid, &poly);
+        check_tracked_distance(&mut ClosestFeatureTracker::new(), &pos12, &poly, &cuboid);
+    }
+}
+
+#[test]
+fn track_closest_features_between_cuboids() {
+    let cuboid = Cuboid::new(Vector2::repeat(1.0));
+    let mut tracker = ClosestFeatureTracker::new();
+
+    let pos12 = Isometry2::translation(3.0, 0.2);
+    let tracked = tracker
+        .closest_points(&pos12, &cuboid, &cuboid)
+        .unwrap()
+        .unwrap();
+    assert_relative_eq!(tracked.distance, 1.0, epsilon = 1.0e-5);
+    // The right face of the first cuboid, or any of its vertices facing the second cuboid.
+    assert!(matches!(
+        tracked.feature1,
+        FeatureId::Face(0) | FeatureId::Vertex(0) | FeatureId::Vertex(0b10)
+    ));
+    assert_relative_eq!(tracked.point1.x, 1.0, epsilon = 1.0e-5);
+    assert_relative_eq!(tracked.point2.x, -1.0, epsilon = 1.0e-5);
+
+    let overlapping = Isometry2::translation(1.5, 0.2);
+    assert!(tracker
+        .closest_points(&overlapping, &cuboid, &cuboid)
+        .unwrap()
+        .is_none());
+}
@@ -0,0 +1,177 @@
+use na::Point2;
+use parry2d::math::Real;
+use parry2d::query::PointQuery;
+use parry2d::shape::{Compound, Polyline, TriMesh};
+use parry2d::transformation::{
+    constrained_delaunay_triangulation, ConstrainedTriangulationError,
+    ConstrainedTriangulationParameters,
+};
+
+fn square(center: Point2<Real>, half_extent: Real) -> Vec<Point2<Real>> {
+    vec![
+        center + na::Vector2::new(-half_extent, -half_extent),
+        center + na::Vector2::new(half_extent, -half_extent),
+        center + na::Vector2::new(half_extent, half_extent),
+        center + na::Vector2::new(-half_extent, half_extent),
+    ]
+}
+
+/// A polyline made of closed loops.
+fn loops(loops: &[Vec<Point2<Real>>]) -> Polyline {
+    let mut vertices = Vec::new();
+    let mut indices = Vec::new();
+
+    for pts in loops {
+        let base = vertices.len() as u32;
+        let n = pts.len() as u32;
+        vertices.extend_from_slice(pts);
+        indices.extend((0..n).map(|i| [base + i, base + (i + 1) % n]));
+    }
+
+    Polyline::new(vertices, Some(indices))
+}
+
+fn triangle_areas(mesh: &TriMesh) -> impl Iterator<Item = Real> + '_ {
+    mesh.triangles().map(|tri| {
+        let (a, b, c) = (tri.a, tri.b, tri.c);
+        (b - a).perp(&(c - a)) / 2.0
+    })
+}
+
+fn covers(mesh: &TriMesh, pt: Point2<Real>) -> bool {
+    mesh.triangles().any(|tri| tri.contains_local_point(&pt))
+}
+
+#[test]
+fn triangulate_polygon_with_holes() {
+    let outline = loops(&[
+        square(Point2::origin(), 2.0),
+        square(Point2::new(-1.0, -1.0), 0.5),
+        square(Point2::new(1.0, 1.0), 0.5),
+    ]);
+    let mesh = constrained_delaunay_triangulation(
+        outline.vertices(),
+        outline.indices(),
+        &ConstrainedTriangulationParameters::default(),
+    )
+    .unwrap();
+
+    assert_eq!(mesh.vertices().len(), 12);
+    assert!(triangle_areas(&mesh).all(|area| area > 0.0));
+    assert_relative_eq!(triangle_areas(&mesh).sum::<Real>(), 14.0, epsilon = 1.0e-5);
+    assert!(!covers(&mesh, Point2::new(-1.0, -1.0)));
+    assert!(!covers(&mesh, Point2::new(1.0, 1.0)));
+    assert!(covers(&mesh, Point2::new(1.0, -1.0)));
+
+    // Without removing the outside, the holes are triangulated too.
+    let filled = constrained_delaunay_triangulation(
+        outline.vertices(),
+        outline.indices(),
+        &ConstrainedTriangulationParameters {
+            remove_outside: false,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert_relative_eq!(
+        triangle_areas(&filled).sum::<Real>(),
+        16.0,
+        epsilon = 1.0e-5
+    );
+
+    // The triangulation can be decomposed into convex parts covering the same area.
+    let compound = Compound::decompose_trimesh(&mesh).unwrap();
+    let compound_area: Real = compound
+        .shapes()
+        .iter()
+        .map(|(_, shape)| shape.mass_properties(1.0).mass())
+        .sum();
+    assert_relative_eq!(compound_area, 14.0, epsilon = 1.0e-4);
+}
+
+#[test]
+fn triangulate_nested_and_crossing_constraints() {
+    // An island inside of the hole of a polygon is kept.
+    let nested = loops(&[
+        square(Point2::origin(), 3.0),
+        square(Point2::origin(), 2.0),
+        square(Point2::origin(), 1.0),
+    ]);
+    let mesh = constrained_delaunay_triangulation(
+        nested.vertices(),
+        nested.indices(),
+        &ConstrainedTriangulationParameters::default(),
+    )
+    .unwrap();
+    assert_relative_eq!(
+        triangle_areas(&mesh).sum::<Real>(),
+        36.0 - 16.0 + 4.0,
+        epsilon = 1.0e-4
+    );
+
+    // Two crossing loops are split at their intersections.
+    let crossing = loops(&[
+        square(Point2::origin(), 1.0),
+        square(Point2::new(1.0, 1.0), 1.0),
+    ]);
+    let mesh = constrained_delaunay_triangulation(
+        crossing.vertices(),
+        crossing.indices(),
+        &ConstrainedTriangulationParameters::default(),
+    )
+    .unwrap();
+    assert_eq!(mesh.vertices().len(), 10);
+    assert_relative_eq!(triangle_areas(&mesh).sum::<Real>(), 6.0, epsilon = 1.0e-5);
+    assert!(!covers(&mesh, Point2::new(0.5, 0.5)));
+}
+
+#[test]
+fn triangulate_with_refinement() {
+    let outline = loops(&[square(Point2::origin(), 2.0), square(Point2::origin(), 1.0)]);
+    let params = ConstrainedTriangulationParameters {
+        min_angle: 25.0_f32.to_radians(),
+        max_area: 0.1,
+        max_added_vertices: Some(1000),
+        ..Default::default()
+    };
+    let mesh =
+        constrained_delaunay_triangulation(outline.vertices(), outline.indices(), &params).unwrap();
+
+    assert_relative_eq!(triangle_areas(&mesh).sum::<Real>(), 12.0, epsilon = 1.0e-4);
+    assert!(triangle_areas(&mesh).all(|area| area > 0.0 && area <= 0.1 + 1.0e-5));
+
+    for tri in mesh.triangles() {
+        let (a, b, c) = (tri.a, tri.b, tri.c);
+
+        for (p, q, r) in [(a, b, c), (b, c, a), (c, a, b)] {
+            let angle = (q - p).angle(&(r - p));
+            assert!(angle >= params.min_angle - 1.0e-3);
+        }
+    }
+}
+
+#[test]
+fn triangulate_invalid_input() {
+    let vertices = square(Point2::origin(), 1.0);
+    assert_eq!(
+        constrained_delaunay_triangulation(
+            &vertices,
+            &[[0, 1], [1, 4]],
+            &ConstrainedTriangulationParameters::default()
+        )
+        .err(),
+        Some(ConstrainedTriangulationError::InvalidEdge(1))
+    );
+
+    let mut vertices = vertices;
+    vertices[2].x = Real::NAN;
+    assert_eq!(
+        constrained_delaunay_triangulation(
+            &vertices,
+            &[],
+            &ConstrainedTriangulationParameters::default()
+        )
+        .err(),
+        Some(ConstrainedTriangulationError::InvalidVertex(2))
+    );
+}
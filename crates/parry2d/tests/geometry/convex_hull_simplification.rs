@@ -0,0 +1,73 @@
+use na::Point2;
+use parry2d::math::Real;
+use parry2d::shape::ConvexPolygon;
+use parry2d::transformation::{
+    simplify_convex_hull, ConvexHullSimplificationBound, ConvexHullSimplificationParameters,
+};
+
+fn area(polygon: &ConvexPolygon) -> Real {
+    let pts = polygon.points();
+    (0..pts.len())
+        .map(|i| pts[i].coords.perp(&pts[(i + 1) % pts.len()].coords))
+        .sum::<Real>()
+        / 2.0
+}
+
+/// The largest distance from a point to the inside of the polygon.
+fn max_distance_outside(polygon: &ConvexPolygon, points: &[Point2<Real>]) -> Real {
+    let mut max_dist = -Real::MAX;
+
+    for (normal, vertex) in polygon.normals().iter().zip(polygon.points()) {
+        for pt in points {
+            max_dist = max_dist.max(normal.dot(&(pt - vertex)));
+        }
+    }
+
+    max_dist
+}
+
+#[test]
+fn simplify_circle_hull() {
+    let points: Vec<_> = (0..64)
+        .map(|i| {
+            let angle = i as Real * core::f32::consts::PI * 2.0 / 64.0;
+            Point2::new(angle.cos(), angle.sin())
+        })
+        .collect();
+    let original = ConvexPolygon::from_convex_hull(&points).unwrap();
+
+    let outside = ConvexHullSimplificationParameters {
+        max_vertices: 8,
+        ..Default::default()
+    };
+    let simplified = simplify_convex_hull(&points, &outside).unwrap();
+    assert!(simplified.points().len() <= 8);
+    assert!(max_distance_outside(&simplified, &points) < 1.0e-4);
+    assert!(area(&simplified) >= area(&original));
+
+    let inside = ConvexHullSimplificationParameters {
+        bound: ConvexHullSimplificationBound::Inside,
+        ..outside
+    };
+    let simplified = simplify_convex_hull(&points, &inside).unwrap();
+    assert_eq!(simplified.points().len(), 8);
+    assert!(max_distance_outside(&original, simplified.points()) < 1.0e-4);
+    assert!(area(&simplified) <= area(&original));
+    // Close to the area of the regular octagon.
+    assert!(area(&simplified) > 2.6);
+
+    // A triangle is the smallest possible result.
+    let triangle = ConvexHullSimplificationParameters {
+        max_vertices: 3,
+        ..Default::default()
+    };
+    let simplified = simplify_convex_hull(&points, &triangle).unwrap();
+    assert_eq!(simplified.points().len(), 3);
+    assert!(max_distance_outside(&simplified, &points) < 1.0e-4);
+
+    let too_small = ConvexHullSimplificationParameters {
+        max_faces: 2,
+        ..Default::default()
+    };
+    assert!(simplify_convex_hull(&points, &too_small).is_none());
+}
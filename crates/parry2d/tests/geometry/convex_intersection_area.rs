@@ -0,0 +1,67 @@
+use na::{Isometry2, Point2, Vector2};
+use parry2d::math::Real;
+use parry2d::query::{self, IntersectionVolumeError};
+use parry2d::shape::{Ball, Cuboid, HalfSpace, Polyline, Triangle};
+
+#[test]
+fn intersection_area_of_rotated_squares() {
+    // Two identical squares rotated by 45 degrees overlap on a regular octagon.
+    let square = Cuboid::new(Vector2::repeat(1.0));
+    let pos12 = Isometry2::rotation(core::f32::consts::FRAC_PI_4);
+
+    let mprops = query::intersection_volume(&pos12, &square, &square)
+        .unwrap()
+        .unwrap();
+    assert_relative_eq!(
+        mprops.mass(),
+        8.0 * (Real::sqrt(2.0) - 1.0),
+        epsilon = 1.0e-4
+    );
+    assert_relative_eq!(mprops.local_com, Point2::origin(), epsilon = 1.0e-4);
+
+    let far = Isometry2::translation(3.0, 0.0);
+    assert!(query::intersection_volume(&far, &square, &square)
+        .unwrap()
+        .is_none());
+}
+
+#[test]
+fn intersection_area_with_triangle_and_halfspace() {
+    let square = Cuboid::new(Vector2::repeat(1.0));
+    // A clockwise triangle covering the top-right quarter of the square.
+    let triangle = Triangle::new(
+        Point2::new(0.0, 0.0),
+        Point2::new(0.0, 4.0),
+        Point2::new(4.0, 0.0),
+    );
+    let mprops = query::intersection_volume(&Isometry2::identity(), &square, &triangle)
+        .unwrap()
+        .unwrap();
+    assert_relative_eq!(mprops.mass(), 1.0, epsilon = 1.0e-4);
+    assert_relative_eq!(mprops.local_com, Point2::new(0.5, 0.5), epsilon = 1.0e-4);
+
+    let halfspace = HalfSpace::new(Vector2::y_axis());
+    let pos12 = Isometry2::translation(0.0, 0.5);
+    let mprops = query::intersection_volume(&pos12, &square, &halfspace)
+        .unwrap()
+        .unwrap();
+    assert_relative_eq!(mprops.mass(), 3.0, epsilon = 1.0e-4);
+    assert_relative_eq!(mprops.local_com, Point2::new(0.0, -0.25), epsilon = 1.0e-4);
+
+    // Curved shapes are approximated by polygons.
+    let mprops = query::intersection_volume(&pos12, &Ball::new(1.0), &halfspace)
+        .unwrap()
+        .unwrap();
+    let segment_area = Real::acos(0.5) - 0.5 * Real::sqrt(0.75);
+    assert_relative_eq!(
+        mprops.mass(),
+        core::f32::consts::PI - segment_area,
+        epsilon = 1.0e-2
+    );
+
+    let polyline = Polyline::new(square.to_polyline(), None);
+    assert_eq!(
+        query::intersection_volume(&pos12, &polyline, &square),
+        Err(IntersectionVolumeError::Unsupported)
+    );
+}
@@ -0,0 +1,96 @@
+use na::{DVector, Isometry2, Point2, Vector2};
+use parry2d::query::{ContactManifold, DefaultQueryDispatcher, PersistentQueryDispatcher};
+use parry2d::shape::{HeightField, Shape, Voxels};
+
+fn flat_heightfield(ncells: usize, size: f32) -> HeightField {
+    HeightField::new(DVector::zeros(ncells + 1), Vector2::new(size, 1.0))
+}
+
+fn compute_manifolds(
+    pos12: &Isometry2<f32>,
+    shape1: &dyn Shape,
+    shape2: &dyn Shape,
+    manifolds: &mut Vec<ContactManifold<(), ()>>,
+    workspace: &mut Option<parry2d::query::ContactManifoldsWorkspace>,
+) {
+    DefaultQueryDispatcher
+        .contact_manifolds(pos12, shape1, shape2, 0.1, manifolds, workspace)
+        .unwrap();
+}
+
+#[test]
+fn heightfield_heightfield_contact_manifolds() {
+    let heightfield1 = flat_heightfield(20, 20.0);
+    let heightfield2 = flat_heightfield(2, 2.0);
+    let mut manifolds = vec![];
+    let mut workspace = None;
+
+    let pos12 = Isometry2::translation(3.0, 0.05);
+    compute_manifolds(
+        &pos12,
+        &heightfield1,
+        &heightfield2,
+        &mut manifolds,
+        &mut workspace,
+    );
+
+    let num_points: usize = manifolds.iter().map(|m| m.points.len()).sum();
+    assert!(num_points > 0);
+
+    for manifold in &manifolds {
+        // Only the segments below the second heightfield are visited.
+        let seg1 = heightfield1
+            .segment_at(manifold.subshape1 as usize)
+            .unwrap();
+        assert!(seg1.a.x <= 4.1 && seg1.b.x >= 1.9);
+
+        for pt in &manifold.points {
+            assert_relative_eq!(pt.dist, 0.05, epsilon = 1.0e-4);
+            assert_relative_eq!(manifold.local_n1.y.abs(), 1.0, epsilon = 1.0e-4);
+        }
+    }
+
+    // The manifolds are kept for the same pairs of segments after a small motion.
+    let pairs: Vec<_> = manifolds
+        .iter()
+        .map(|m| (m.subshape1, m.subshape2))
+        .collect();
+    let pos12 = Isometry2::translation(3.0, 0.04);
+    compute_manifolds(
+        &pos12,
+        &heightfield1,
+        &heightfield2,
+        &mut manifolds,
+        &mut workspace,
+    );
+    let new_pairs: Vec<_> = manifolds
+        .iter()
+        .map(|m| (m.subshape1, m.subshape2))
+        .collect();
+    assert_eq!(pairs, new_pairs);
+}
+
+#[test]
+fn heightfield_voxels_contact_manifolds() {
+    let heightfield = flat_heightfield(10, 10.0);
+    let voxels = Voxels::new(
+        Vector2::repeat(1.0),
+        &[Point2::new(0, 0), Point2::new(1, 0), Point2::new(0, 1)],
+    );
+
+    for dist in [0.02, -0.02] {
+        let pos12 = Isometry2::translation(-1.3, dist);
+        let mut manifolds = vec![];
+        compute_manifolds(&pos12, &heightfield, &voxels, &mut manifolds, &mut None);
+
+        let num_points: usize = manifolds.iter().map(|m| m.points.len()).sum();
+        assert!(num_points > 0);
+
+        for manifold in &manifolds {
+            for pt in &manifold.points {
+                assert_relative_eq!(manifold.local_n1, Vector2::y(), epsilon = 1.0e-4);
+                assert_relative_eq!(pt.dist, dist, epsilon = 1.0e-4);
+            }
+        }
+    }
+}
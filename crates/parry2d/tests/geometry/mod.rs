@@ -1,7 +1,14 @@
 mod aabb_scale;
 mod ball_ball_toi;
 mod ball_cuboid_contact;
+mod closest_feature_tracking;
+mod constrained_delaunay;
+mod convex_hull_simplification;
+mod convex_intersection_area;
 mod epa2;
 mod epa_convergence;
+mod heightfield_contact_manifolds;
+mod polygon_boolean;
+mod polyline_offset;
 mod ray_cast;
 mod time_of_impact2;
@@ -0,0 +1,231 @@
+use na::{Point2, Vector2};
+use parry2d::math::Real;
+use parry2d::transformation::{
+    polygon_boolean, polygon_boolean_with_tolerances, ConstrainedTriangulationParameters,
+    MultiPolygon, PolygonBooleanOp, PolygonIntersectionTolerances,
+};
+
+fn rect(min: [Real; 2], max: [Real; 2]) -> Vec<Point2<Real>> {
+    vec![
+        Point2::new(min[0], min[1]),
+        Point2::new(max[0], min[1]),
+        Point2::new(max[0], max[1]),
+        Point2::new(min[0], max[1]),
+    ]
+}
+
+fn circle(center: Point2<Real>, radius: Real, n: usize) -> Vec<Point2<Real>> {
+    (0..n)
+        .map(|i| {
+            let angle = i as Real * core::f32::consts::PI * 2.0 / n as Real;
+            center + Vector2::new(angle.cos(), angle.sin()) * radius
+        })
+        .collect()
+}
+
+fn signed_area(ring: &[Point2<Real>]) -> Real {
+    MultiPolygon::new(vec![ring.to_vec()]).area()
+}
+
+/// Checks the result is consistent with its outline and triangulation.
+fn check_result(poly: &MultiPolygon) {
+    let polyline = poly.to_polyline();
+    assert_eq!(
+        polyline.vertices().len(),
+        poly.rings().iter().map(|ring| ring.len()).sum::<usize>()
+    );
+
+    if poly.is_empty() {
+        return;
+    }
+
+    let mesh = poly
+        .to_trimesh(&ConstrainedTriangulationParameters::default())
+        .unwrap();
+    let mesh_area: Real = mesh
+        .triangles()
+        .map(|tri| (tri.b - tri.a).perp(&(tri.c - tri.a)) / 2.0)
+        .sum();
+    assert_relative_eq!(mesh_area, poly.area(), epsilon = 1.0e-3);
+}
+
+#[test]
+fn boolean_overlapping_squares() {
+    let a = MultiPolygon::new(vec![rect([0.0, 0.0], [2.0, 2.0])]);
+    // Clockwise, the orientation of the input doesn’t matter.
+    let mut ring = rect([1.0, 1.0], [3.0, 3.0]);
+    ring.reverse();
+    let b = MultiPolygon::new(vec![ring]);
+
+    for (op, area) in [
+        (PolygonBooleanOp::Union, 7.0),
+        (PolygonBooleanOp::Intersection, 1.0),
+        (PolygonBooleanOp::Difference, 3.0),
+        (PolygonBooleanOp::Xor, 6.0),
+    ] {
+        let result = polygon_boolean(op, &a, &b);
+        assert_relative_eq!(result.area(), area, epsilon = 1.0e-5);
+        // All the outer boundaries are counterclockwise.
+        assert!(result.rings().iter().all(|ring| signed_area(ring) > 0.0));
+        check_result(&result);
+    }
+
+    let union = polygon_boolean(PolygonBooleanOp::Union, &a, &b);
+    assert_eq!(union.rings().len(), 1);
+    assert_eq!(union.rings()[0].len(), 8);
+    assert!(union.contains_point(&Point2::new(2.5, 2.5)));
+    assert!(!union.contains_point(&Point2::new(2.5, 0.5)));
+
+    let xor = polygon_boolean(PolygonBooleanOp::Xor, &a, &b);
+    assert_eq!(xor.rings().len(), 2);
+    assert!(!xor.contains_point(&Point2::new(1.5, 1.5)));
+}
+
+#[test]
+fn boolean_coincident_edges() {
+    let a = MultiPolygon::new(vec![rect([0.0, 0.0], [1.0, 1.0])]);
+    let b = MultiPolygon::new(vec![rect([1.0, 0.0], [2.0, 1.0])]);
+
+    // The shared edge disappears, along with its collinear vertices.
+    let union = polygon_boolean(PolygonBooleanOp::Union, &a, &b);
+    assert_eq!(union.rings().len(), 1);
+    assert_eq!(union.rings()[0].len(), 4);
+    assert_relative_eq!(union.area(), 2.0, epsilon = 1.0e-5);
+
+    assert!(polygon_boolean(PolygonBooleanOp::Intersection, &a, &b).is_empty());
+
+    // Partially overlapping collinear edges.
+    let c = MultiPolygon::new(vec![rect([0.5, 1.0], [1.5, 2.0])]);
+    let union = polygon_boolean(PolygonBooleanOp::Union, &union, &c);
+    assert_eq!(union.rings().len(), 1);
+    assert_eq!(union.rings()[0].len(), 8);
+    assert_relative_eq!(union.area(), 3.0, epsilon = 1.0e-5);
+
+    // A polygon combined with itself.
+    for (op, area) in [
+        (PolygonBooleanOp::Union, 1.0),
+        (PolygonBooleanOp::Intersection, 1.0),
+        (PolygonBooleanOp::Difference, 0.0),
+        (PolygonBooleanOp::Xor, 0.0),
+    ] {
+        let result = polygon_boolean(op, &a, &a);
+        assert_relative_eq!(result.area(), area, epsilon = 1.0e-5);
+        assert_eq!(result.is_empty(), area == 0.0);
+    }
+}
+
+#[test]
+fn boolean_polygons_with_holes() {
+    // A square with a square hole.
+    let frame = MultiPolygon::new(vec![
+        rect([0.0, 0.0], [4.0, 4.0]),
+        rect([1.0, 1.0], [3.0, 3.0]),
+    ]);
+    assert!(!frame.contains_point(&Point2::new(2.0, 2.0)));
+
+    // Cutting a bar through the frame splits it in two, with no hole left.
+    let bar = MultiPolygon::new(vec![rect([1.5, -1.0], [2.5, 5.0])]);
+    let cut = polygon_boolean(PolygonBooleanOp::Difference, &frame, &bar);
+    assert_eq!(cut.rings().len(), 2);
+    assert_relative_eq!(cut.area(), 12.0 - 2.0, epsilon = 1.0e-5);
+    check_result(&cut);
+
+    // Filling the hole and more.
+    let plug = MultiPolygon::new(vec![rect([0.5, 0.5], [3.5, 3.5])]);
+    let filled = polygon_boolean(PolygonBooleanOp::Union, &frame, &plug);
+    assert_eq!(filled.rings().len(), 1);
+    assert_relative_eq!(filled.area(), 16.0, epsilon = 1.0e-5);
+
+    // The intersection with the plug is a thinner frame, the hole being clockwise.
+    let thin = polygon_boolean(PolygonBooleanOp::Intersection, &frame, &plug);
+    assert_eq!(thin.rings().len(), 2);
+    assert_relative_eq!(thin.area(), 9.0 - 4.0, epsilon = 1.0e-5);
+    assert_eq!(
+        thin.rings()
+            .iter()
+            .filter(|ring| signed_area(ring) < 0.0)
+            .count(),
+        1
+    );
+    check_result(&thin);
+
+    // Polygons touching at a single vertex are kept as separate rings.
+    let a = MultiPolygon::new(vec![rect([0.0, 0.0], [1.0, 1.0])]);
+    let b = MultiPolygon::new(vec![rect([1.0, 1.0], [2.0, 2.0])]);
+    let union = polygon_boolean(PolygonBooleanOp::Union, &a, &b);
+    assert_eq!(union.rings().len(), 2);
+    assert!(union.rings().iter().all(|ring| ring.len() == 4));
+}
+
+#[test]
+fn boolean_destructible_terrain() {
+    let mut terrain = MultiPolygon::new(vec![rect([0.0, 0.0], [10.0, 4.0])]);
+    let mut rng = oorandom::Rand32::new(42);
+    let mut area = terrain.area();
+
+    for _ in 0..10 {
+        let center = Point2::new(rng.rand_float() * 10.0, rng.rand_float() * 4.0);
+        let crater = MultiPolygon::new(vec![circle(center, 0.5 + rng.rand_float(), 16)]);
+        terrain = polygon_boolean(PolygonBooleanOp::Difference, &terrain, &crater);
+
+        assert!(terrain.area() <= area + 1.0e-4);
+        assert!(!terrain.contains_point(&center));
+        area = terrain.area();
+        check_result(&terrain);
+    }
+
+    assert!(area > 0.0);
+}
+
+#[test]
+fn boolean_is_scale_independent() {
+    let areas = |scale: Real| {
+        let a = MultiPolygon::new(vec![circle(Point2::new(0.0, 0.0), scale, 64)]);
+        let b = MultiPolygon::new(vec![circle(Point2::new(0.7, 0.3) * scale, scale, 64)]);
+
+        [
+            PolygonBooleanOp::Union,
+            PolygonBooleanOp::Difference,
+            PolygonBooleanOp::Intersection,
+            PolygonBooleanOp::Xor,
+        ]
+        .map(|op| {
+            let result = polygon_boolean(op, &a, &b);
+            assert_eq!(
+                result.rings().len(),
+                if op == PolygonBooleanOp::Xor { 2 } else { 1 }
+            );
+            result.area() / (scale * scale)
+        })
+    };
+
+    let expected = areas(1.0);
+
+    for scale in [1.0e-2, 1.0e4] {
+        for (area, expected) in areas(scale).iter().zip(expected) {
+            assert_relative_eq!(*area, expected, max_relative = 1.0e-3);
+        }
+    }
+}
+
+#[test]
+fn boolean_dense_polygons() {
+    let n = 5000;
+    let a = MultiPolygon::new(vec![circle(Point2::new(0.0, 0.0), 1.0, n)]);
+    let b = MultiPolygon::new(vec![circle(Point2::new(1.0, 0.2), 1.0, n)]);
+
+    // The edges are short relative to the size of the polygons, so the collinearity epsilon
+    // must be smaller than the default one for their vertices not to be considered collinear.
+    let tolerances = PolygonIntersectionTolerances {
+        collinearity_epsilon: 1.0e-10,
+    };
+    let union = polygon_boolean_with_tolerances(PolygonBooleanOp::Union, &a, &b, tolerances);
+    let inter = polygon_boolean_with_tolerances(PolygonBooleanOp::Intersection, &a, &b, tolerances);
+    assert_eq!(union.rings().len(), 1);
+    assert_eq!(inter.rings().len(), 1);
+    assert_relative_eq!(
+        union.area() + inter.area(),
+        a.area() + b.area(),
+        max_relative = 1.0e-4
+    );
+}
@@ -0,0 +1,156 @@
+use na::Point2;
+use parry2d::math::Real;
+use parry2d::query::PointQuery;
+use parry2d::shape::Polyline;
+use parry2d::transformation::{offset_polyline, OffsetJoin};
+
+fn closed_polyline(loops: &[&[[Real; 2]]]) -> Polyline {
+    let mut vertices = vec![];
+    let mut indices = vec![];
+
+    for pts in loops {
+        let base = vertices.len() as u32;
+        let len = pts.len() as u32;
+        vertices.extend(pts.iter().map(|pt| Point2::from(*pt)));
+        indices.extend((0..len).map(|i| [base + i, base + (i + 1) % len]));
+    }
+
+    Polyline::new(vertices, Some(indices))
+}
+
+/// The signed area of each connected component of the polyline.
+fn areas(polyline: &Polyline) -> Vec<Real> {
+    polyline
+        .extract_connected_components()
+        .iter()
+        .map(|component| {
+            let pts = component.vertices();
+            let n = pts.len();
+            (0..n)
+                .map(|i| pts[i].coords.perp(&pts[(i + 1) % n].coords))
+                .sum::<Real>()
+                / 2.0
+        })
+        .collect()
+}
+
+const SQUARE: [[Real; 2]; 4] = [[-1.0, -1.0], [1.0, -1.0], [1.0, 1.0], [-1.0, 1.0]];
+
+#[test]
+fn offset_square_joins() {
+    let square = closed_polyline(&[&SQUARE]);
+    let expected = [
+        (OffsetJoin::Miter { limit: 2.0 }, 9.0),
+        // Beyond the miter limit, the corners are beveled.
+        (OffsetJoin::Miter { limit: 1.2 }, 8.5),
+        (OffsetJoin::Bevel, 8.5),
+        (
+            OffsetJoin::Round { subdivisions: 64 },
+            8.0 + core::f32::consts::PI * 0.25,
+        ),
+    ];
+
+    for (join, expected_area) in expected {
+        let offset = offset_polyline(&square, 0.5, join).unwrap();
+        let areas = areas(&offset);
+        assert_eq!(areas.len(), 1);
+        assert_relative_eq!(areas[0], expected_area, epsilon = 1.0e-3);
+
+        for pt in offset.vertices() {
+            assert!(square.distance_to_local_point(pt, false) >= 0.5 - 1.0e-4);
+        }
+    }
+
+    // Shrinking keeps sharp corners.
+    let offset = offset_polyline(&square, -0.5, OffsetJoin::Bevel).unwrap();
+    assert_eq!(areas(&offset), vec![1.0]);
+
+    // Shrinking by more than half the width removes the polygon.
+    assert!(offset_polyline(&square, -1.1, OffsetJoin::default()).is_none());
+}
+
+#[test]
+fn offset_splits_and_merges_polygons() {
+    // Two squares connected by a thin bridge.
+    let dumbbell = closed_polyline(&[&[
+        [0.0, 0.0],
+        [2.0, 0.0],
+        [2.0, 0.8],
+        [4.0, 0.8],
+        [4.0, 0.0],
+        [6.0, 0.0],
+        [6.0, 2.0],
+        [4.0, 2.0],
+        [4.0, 1.2],
+        [2.0, 1.2],
+        [2.0, 2.0],
+        [0.0, 2.0],
+    ]]);
+
+    // The bridge vanishes when shrinking.
+    let offset = offset_polyline(&dumbbell, -0.3, OffsetJoin::default()).unwrap();
+    let areas_shrunk = areas(&offset);
+    assert_eq!(areas_shrunk.len(), 2);
+    for area in areas_shrunk {
+        assert_relative_eq!(area, 1.4 * 1.4, epsilon = 1.0e-4);
+    }
+
+    // Growing a C shape closes its mouth, creating a hole.
+    let c_shape = closed_polyline(&[&[
+        [0.0, 0.0],
+        [3.0, 0.0],
+        [3.0, 3.0],
+        [1.6, 3.0],
+        [1.6, 2.0],
+        [2.0, 2.0],
+        [2.0, 1.0],
+        [1.0, 1.0],
+        [1.0, 2.0],
+        [1.4, 2.0],
+        [1.4, 3.0],
+        [0.0, 3.0],
+    ]]);
+    let offset = offset_polyline(&c_shape, 0.25, OffsetJoin::default()).unwrap();
+    let mut areas_grown = areas(&offset);
+    areas_grown.sort_by(|a, b| a.total_cmp(b));
+    assert_eq!(areas_grown.len(), 2);
+    // The hole, oriented clockwise.
+    assert_relative_eq!(areas_grown[0], -0.5 * 0.5, epsilon = 1.0e-4);
+    assert_relative_eq!(areas_grown[1], 3.5 * 3.5, epsilon = 1.0e-4);
+
+    // Holes in the input shrink when growing the polygon.
+    let hollow_square = closed_polyline(&[
+        &[[-2.0, -2.0], [2.0, -2.0], [2.0, 2.0], [-2.0, 2.0]],
+        &[[-1.0, -1.0], [-1.0, 1.0], [1.0, 1.0], [1.0, -1.0]],
+    ]);
+    let offset = offset_polyline(&hollow_square, 0.25, OffsetJoin::default()).unwrap();
+    let mut areas_hollow = areas(&offset);
+    areas_hollow.sort_by(|a, b| a.total_cmp(b));
+    assert_relative_eq!(areas_hollow[0], -1.5 * 1.5, epsilon = 1.0e-4);
+    assert_relative_eq!(areas_hollow[1], 4.5 * 4.5, epsilon = 1.0e-4);
+}
+
+#[test]
+fn offset_dense_outline() {
+    // A circle with 10k vertices.
+    let n = 10_000;
+    let circle: Vec<[Real; 2]> = (0..n)
+        .map(|i| {
+            let angle = i as Real / n as Real * core::f32::consts::TAU;
+            [10.0 * angle.cos(), 10.0 * angle.sin()]
+        })
+        .collect();
+    let circle = closed_polyline(&[&circle]);
+    let pi = core::f32::consts::PI;
+
+    for (distance, join) in [
+        (0.5, OffsetJoin::Round { subdivisions: 8 }),
+        (-0.5, OffsetJoin::default()),
+    ] {
+        let offset = offset_polyline(&circle, distance, join).unwrap();
+        let areas = areas(&offset);
+        assert_eq!(areas.len(), 1);
+        let radius = 10.0 + distance;
+        assert_relative_eq!(areas[0], pi * radius * radius, max_relative = 1.0e-3);
+    }
+}
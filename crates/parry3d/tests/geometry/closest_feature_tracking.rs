@@ -0,0 +1,109 @@
+use na::{Isometry3, Vector3};
+use parry3d::query::{self, ClosestFeatureTracker};
+use parry3d::shape::{Ball, ConvexPolyhedron, Cuboid, FeatureId, Shape};
+
+fn check_tracked_distance(
+    tracker: &mut ClosestFeatureTracker,
+    pos12: &Isometry3<f32>,
+    shape1: &dyn Shape,
+    shape2: &dyn Shape,
+) {
+    let tracked = tracker
+        .closest_points(pos12, shape1, shape2)
+        .unwrap()
+        .unwrap();
+    // NOTE: compare with GJK since the dedicated cuboid-cuboid distance is approximate. GJK
+    //       only gives an upper bound within its tolerance, so the tracked distance may be
+    //       slightly smaller.
+    let expected = query::details::distance_support_map_support_map(
+        pos12,
+        shape1.as_support_map().unwrap(),
+        shape2.as_support_map().unwrap(),
+    );
+    assert!(tracked.distance <= expected + 1.0e-4);
+    assert_relative_eq!(tracked.distance, expected, epsilon = 5.0e-3);
+    assert_relative_eq!(
+        na::distance(&tracked.point1, &(pos12 * tracked.point2)),
+        tracked.distance,
+        epsilon = 1.0e-5
+    );
+
+    // Both points must lie on their shape.
+    assert!(shape1.distance_to_local_point(&tracked.point1, true) <= 1.0e-4);
+    assert!(shape2.distance_to_local_point(&tracked.point2, true) <= 1.0e-4);
+    assert_ne!(tracked.feature1, FeatureId::Unknown);
+    assert_ne!(tracked.feature2, FeatureId::Unknown);
+}
+
+#[test]
+fn track_closest_features_between_moving_cuboids() {
+    let cuboid1 = Cuboid::new(Vector3::new(1.0, 0.5, 2.0));
+    let cuboid2 = Cuboid::new(Vector3::new(0.3, 0.8, 0.6));
+    let mut tracker = ClosestFeatureTracker::new();
+
+    for i in 0..200 {
+        let t = i as f32 * 0.05;
+        let pos12 = Isometry3::new(
+            Vector3::new(4.0 * t.cos(), 3.0 * t.sin(), 0.5 * (2.0 * t).sin()),
+            Vector3::new(0.3 * t, 0.7 * t, -0.2 * t),
+        );
+        check_tracked_distance(&mut tracker, &pos12, &cuboid1, &cuboid2);
+    }
+}
+
+#[test]
+fn track_closest_features_between_cuboid_and_polyhedron() {
+    let cuboid = Cuboid::new(Vector3::new(1.0, 1.5, 0.5));
+    let (vertices, indices) = Ball::new(0.8).to_trimesh(10, 10);
+    let poly = ConvexPolyhedron::from_convex_mesh(vertices, &indices).unwrap();
+    let mut tracker = ClosestFeatureTracker::new();
+
+    for i in 0..200 {
+        let t = i as f32 * 0.05;
+        let pos12 = Isometry3::new(
+            Vector3::new(3.5 * t.cos(), 0.5 * t.sin(), 3.5 * t.sin()),
+            Vector3::new(0.4 * t, -0.3 * t, 0.5 * t),
+        );
+        check_tracked_distance(&mut tracker, &pos12, &cuboid, &poly);
+        check_tracked_distance(&mut ClosestFeatureTracker::new(), &pos12, &poly, &cuboid);
+    }
+}
+
+#[test]
+fn track_closest_features_reports_face_pairs() {
+    let cuboid = Cuboid::new(Vector3::repeat(1.0));
+    let mut tracker = ClosestFeatureTracker::new();
+    let pos12 = Isometry3::translation(0.2, 0.1, 3.0);
+
+    let tracked = tracker
+        .closest_points(&pos12, &cuboid, &cuboid)
+        .unwrap()
+        .unwrap();
+    assert_relative_eq!(tracked.distance, 1.0, epsilon = 1.0e-5);
+    // Any feature of the bottom face of the second cuboid is a valid closest feature.
+    assert_eq!(tracked.feature1, FeatureId::Face(2));
+    assert_ne!(tracked.feature2, FeatureId::Unknown);
+    assert_relative_eq!(tracked.point1.z, 1.0, epsilon = 1.0e-5);
+    assert_relative_eq!(tracked.point2.z, -1.0, epsilon = 1.0e-5);
+}
+
+#[test]
+fn track_closest_features_of_intersecting_shapes() {
+    let cuboid = Cuboid::new(Vector3::repeat(1.0));
+    let mut tracker = ClosestFeatureTracker::new();
+
+    let far = Isometry3::translation(3.0, 0.0, 0.0);
+    assert!(tracker
+        .closest_points(&far, &cuboid, &cuboid)
+        .unwrap()
+        .is_some());
+
+    let close = Isometry3::translation(1.5, 0.0, 0.0);
+    assert!(tracker
+        .closest_points(&close, &cuboid, &cuboid)
+        .unwrap()
+        .is_none());
+
+    let ball = Ball::new(1.0);
+    assert!(tracker.closest_points(&far, &cuboid, &ball).is_err());
+}
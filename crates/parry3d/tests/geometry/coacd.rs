@@ -0,0 +1,99 @@
+use na::Point3;
+use parry3d::math::{Isometry, Real};
+use parry3d::query::PointQuery;
+use parry3d::shape::{Compound, ConvexPolyhedron, Shape};
+use parry3d::transformation::coacd::{self, CoACDParameters};
+
+/// An L-shaped prism of height 1, with a volume of 3.
+fn l_shape() -> (Vec<Point3<Real>>, Vec<[u32; 3]>) {
+    let polygon = [
+        [0.0, 0.0],
+        [2.0, 0.0],
+        [2.0, 1.0],
+        [1.0, 1.0],
+        [1.0, 2.0],
+        [0.0, 2.0],
+    ];
+    let n = polygon.len() as u32;
+    let mut vertices: Vec<_> = polygon
+        .iter()
+        .map(|p| Point3::new(p[0], p[1], 0.0))
+        .collect();
+    vertices.extend(polygon.iter().map(|p| Point3::new(p[0], p[1], 1.0)));
+
+    let mut indices = vec![];
+    for i in 1..n - 1 {
+        indices.push([0, i + 1, i]);
+        indices.push([n, n + i, n + i + 1]);
+    }
+    for i in 0..n {
+        let j = (i + 1) % n;
+        indices.push([i, j, n + j]);
+        indices.push([i, n + j, n + i]);
+    }
+
+    (vertices, indices)
+}
+
+fn convex_parts(compound: &Compound) -> Vec<&ConvexPolyhedron> {
+    compound
+        .shapes()
+        .iter()
+        .map(|(_, shape)| shape.as_convex_polyhedron().unwrap())
+        .collect()
+}
+
+fn volume(convex: &ConvexPolyhedron) -> Real {
+    convex.mass_properties(1.0).mass()
+}
+
+#[test]
+fn coacd_l_shape() {
+    let (vertices, indices) = l_shape();
+    let compound = coacd::decompose(&vertices, &indices, &CoACDParameters::default()).unwrap();
+    let hulls = convex_parts(&compound);
+
+    assert_eq!(hulls.len(), 2);
+    let total_volume: Real = hulls.iter().map(|hull| volume(hull)).sum();
+    assert_relative_eq!(total_volume, 3.0, epsilon = 0.05);
+
+    // The hulls cover the input, without leaking outside of its bounding box.
+    for pt in &vertices {
+        let dist = compound.distance_to_point(&Isometry::identity(), pt, true);
+        assert!(dist < 1.0e-4);
+    }
+    assert!(hulls
+        .iter()
+        .flat_map(|hull| hull.points())
+        .all(|pt| pt.iter().all(|x| (-1.0e-4..=2.0 + 1.0e-4).contains(x))));
+
+    // Limiting the number of hulls merges them.
+    let params = CoACDParameters {
+        max_convex_hulls: 1,
+        ..Default::default()
+    };
+    let compound = coacd::decompose(&vertices, &indices, &params).unwrap();
+    let hulls = convex_parts(&compound);
+    assert_eq!(hulls.len(), 1);
+    assert_relative_eq!(volume(hulls[0]), 3.5, epsilon = 1.0e-4);
+}
+
+#[test]
+fn coacd_convex_input() {
+    let (vertices, indices) = parry3d::shape::Cuboid::new(na::Vector3::repeat(1.0)).to_trimesh();
+    let compound = coacd::decompose(&vertices, &indices, &CoACDParameters::default()).unwrap();
+    let hulls = convex_parts(&compound);
+    assert_eq!(hulls.len(), 1);
+    assert_relative_eq!(volume(hulls[0]), 8.0, epsilon = 1.0e-4);
+}
+
+#[test]
+#[should_panic]
+fn coacd_invalid_concavity() {
+    let (vertices, indices) = l_shape();
+    let params = CoACDParameters {
+        concavity: Real::NAN,
+        ..CoACDParameters::default()
+    };
+    let _ = coacd::decompose(&vertices, &indices, &params);
+}
@@ -0,0 +1,45 @@
+use na::{Isometry3, Vector3};
+use parry3d::query;
+use parry3d::shape::{Ball, Compound, Cuboid, SharedShape};
+
+#[test]
+fn compound_ball_contact_with_asymmetric_pose() {
+    let part_pos = Isometry3::translation(1.0, 0.0, 0.0);
+    let part = Cuboid::new(Vector3::new(0.5, 1.0, 0.25));
+    let compound = Compound::new(vec![(part_pos, SharedShape::new(part))]);
+    let ball = Ball::new(0.5);
+
+    let pos1 = Isometry3::new(Vector3::new(0.2, -0.3, 0.1), Vector3::new(0.0, 0.0, 0.4));
+    let pos2 = pos1 * Isometry3::new(Vector3::new(1.8, 0.4, 0.0), Vector3::new(0.3, 0.0, 0.0));
+
+    let compound_contact = query::contact(&pos1, &compound, &pos2, &ball, 0.1)
+        .unwrap()
+        .unwrap();
+    let part_contact = query::contact(&(pos1 * part_pos), &part, &pos2, &ball, 0.1)
+        .unwrap()
+        .unwrap();
+
+    assert_relative_eq!(compound_contact.dist, part_contact.dist, epsilon = 1.0e-5);
+    assert_relative_eq!(
+        compound_contact.point1,
+        part_contact.point1,
+        epsilon = 1.0e-5
+    );
+    assert_relative_eq!(
+        compound_contact.point2,
+        part_contact.point2,
+        epsilon = 1.0e-5
+    );
+    assert_relative_eq!(
+        *compound_contact.normal1,
+        *part_contact.normal1,
+        epsilon = 1.0e-5
+    );
+
+    // The flipped query places the compound at the inverse of the same relative pose.
+    let flipped = query::contact(&pos2, &ball, &pos1, &compound, 0.1)
+        .unwrap()
+        .unwrap();
+    assert_relative_eq!(flipped.dist, part_contact.dist, epsilon = 1.0e-5);
+    assert_relative_eq!(flipped.point1, part_contact.point2, epsilon = 1.0e-5);
+}
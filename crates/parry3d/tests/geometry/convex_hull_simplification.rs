@@ -0,0 +1,156 @@
+use na::Point3;
+use parry3d::math::Real;
+use parry3d::shape::{Ball, Cuboid, SharedShape};
+use parry3d::transformation::vhacd::{VHACDParameters, VHACD};
+use parry3d::transformation::{
+    self, simplify_convex_hull, ConvexHullSimplificationBound, ConvexHullSimplificationParameters,
+};
+
+/// The volume of the convex hull of the points.
+fn volume(points: &[Point3<Real>]) -> Real {
+    let (vertices, indices) = transformation::convex_hull(points);
+    indices
+        .iter()
+        .map(|idx| {
+            let [a, b, c] = idx.map(|i| vertices[i as usize].coords);
+            a.dot(&b.cross(&c)) / 6.0
+        })
+        .sum()
+}
+
+/// The largest distance from a point to the inside of the convex hull of `hull_points`.
+fn max_distance_outside(hull_points: &[Point3<Real>], points: &[Point3<Real>]) -> Real {
+    let (vertices, indices) = transformation::convex_hull(hull_points);
+    let mut max_dist = -Real::MAX;
+
+    for idx in indices {
+        let [a, b, c] = idx.map(|i| vertices[i as usize]);
+        let normal = (b - a).cross(&(c - a)).normalize();
+
+        for pt in points {
+            max_dist = max_dist.max(normal.dot(&(pt - a)));
+        }
+    }
+
+    max_dist
+}
+
+#[test]
+fn simplify_sphere_hull() {
+    let (points, _) = Ball::new(1.0).to_trimesh(16, 16);
+    let original = volume(&points);
+    assert!(points.len() > 200);
+
+    let outside = ConvexHullSimplificationParameters {
+        max_vertices: 32,
+        ..Default::default()
+    };
+    let simplified = simplify_convex_hull(&points, &outside).unwrap();
+    assert!(simplified.points().len() <= 32);
+    // The simplified hull contains the original one.
+    assert!(max_distance_outside(simplified.points(), &points) < 1.0e-4);
+    assert!(volume(simplified.points()) >= original);
+    assert!(volume(simplified.points()) < original * 1.5);
+
+    let inside = ConvexHullSimplificationParameters {
+        bound: ConvexHullSimplificationBound::Inside,
+        ..outside
+    };
+    let simplified = simplify_convex_hull(&points, &inside).unwrap();
+    assert!(simplified.points().len() <= 32);
+    // The simplified hull is contained in the original one.
+    assert!(max_distance_outside(&points, simplified.points()) < 1.0e-4);
+    assert!(volume(simplified.points()) <= original);
+    assert!(volume(simplified.points()) > original * 0.75);
+
+    // Limit the number of faces instead.
+    let faces = ConvexHullSimplificationParameters {
+        max_vertices: u32::MAX,
+        max_faces: 10,
+        ..Default::default()
+    };
+    let simplified = simplify_convex_hull(&points, &faces).unwrap();
+    assert!(simplified.faces().len() <= 10);
+    assert!(max_distance_outside(simplified.points(), &points) < 1.0e-4);
+}
+
+#[test]
+fn simplify_dense_hull_inside() {
+    let (points, _) = Ball::new(1.0).to_trimesh(48, 48);
+    assert!(points.len() > 2000);
+
+    let inside = ConvexHullSimplificationParameters {
+        max_vertices: 64,
+        bound: ConvexHullSimplificationBound::Inside,
+        ..Default::default()
+    };
+    let simplified = simplify_convex_hull(&points, &inside).unwrap();
+    assert!(simplified.points().len() <= 64);
+    assert!(simplified.points().len() > 32);
+    assert!(max_distance_outside(&points, simplified.points()) < 1.0e-4);
+    assert!(volume(simplified.points()) > volume(&points) * 0.85);
+}
+
+#[test]
+fn simplify_convex_hull_budget() {
+    let (points, _) = Cuboid::new(na::Vector3::new(1.0, 2.0, 3.0)).to_trimesh();
+
+    // The hull already fits in the budget.
+    let simplified = simplify_convex_hull(&points, &Default::default()).unwrap();
+    assert_eq!(simplified.points().len(), 8);
+    assert_eq!(simplified.faces().len(), 6);
+
+    // A box can be exactly represented with its six faces.
+    let params = ConvexHullSimplificationParameters {
+        max_vertices: 8,
+        max_faces: 6,
+        ..Default::default()
+    };
+    let simplified = simplify_convex_hull(&points, &params).unwrap();
+    assert_relative_eq!(volume(simplified.points()), 48.0, epsilon = 1.0e-3);
+
+    // A tetrahedron is the smallest possible result.
+    let params = ConvexHullSimplificationParameters {
+        max_vertices: 4,
+        ..Default::default()
+    };
+    let simplified = simplify_convex_hull(&points, &params).unwrap();
+    assert_eq!(simplified.points().len(), 4);
+    assert!(max_distance_outside(simplified.points(), &points) < 1.0e-4);
+
+    let params = ConvexHullSimplificationParameters {
+        max_vertices: 3,
+        ..Default::default()
+    };
+    assert!(simplify_convex_hull(&points, &params).is_none());
+}
+
+#[test]
+fn vhacd_simplified_convex_hulls() {
+    let (vertices, indices) = Ball::new(1.0).to_trimesh(8, 8);
+    let params = VHACDParameters {
+        resolution: 16,
+        max_convex_hulls: 2,
+        convex_hull_simplification: Some(ConvexHullSimplificationParameters {
+            max_vertices: 8,
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    let decomposition = VHACD::decompose(&params, &vertices, &indices, true);
+
+    for (hull_vertices, _) in decomposition.compute_exact_convex_hulls(&vertices, &indices) {
+        assert!(hull_vertices.len() <= 8);
+    }
+
+    for (hull_vertices, _) in decomposition.compute_convex_hulls(1) {
+        assert!(hull_vertices.len() <= 8);
+    }
+
+    // The parameters alone enable the simplification of shape decompositions.
+    let compound = SharedShape::convex_decomposition_with_params(&vertices, &indices, &params);
+    for (_, part) in compound.as_compound().unwrap().shapes() {
+        assert!(part.as_convex_polyhedron().unwrap().points().len() <= 8);
+    }
+}
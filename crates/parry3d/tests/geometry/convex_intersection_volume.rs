@@ -0,0 +1,201 @@
+use na::{Isometry3, Point3, Vector3};
+use parry3d::math::Real;
+use parry3d::query::{self, IntersectionVolumeError};
+use parry3d::shape::{
+    Ball, Cone, ConvexPolyhedron, Cuboid, Cylinder, HalfSpace, RoundShape, TriMesh, Triangle,
+};
+use parry3d::transformation;
+
+fn cuboid_polyhedron(cuboid: &Cuboid) -> ConvexPolyhedron {
+    let (vertices, indices) = cuboid.to_trimesh();
+    ConvexPolyhedron::from_convex_mesh(vertices, &indices).unwrap()
+}
+
+#[test]
+fn intersection_volume_of_aligned_cuboids() {
+    let cuboid = Cuboid::new(Vector3::repeat(1.0));
+    let pos12 = Isometry3::translation(1.5, 0.5, 0.0);
+
+    let mprops = query::intersection_volume(&pos12, &cuboid, &cuboid)
+        .unwrap()
+        .unwrap();
+    assert_relative_eq!(mprops.mass(), 0.5 * 1.5 * 2.0, epsilon = 1.0e-4);
+    assert_relative_eq!(
+        mprops.local_com,
+        Point3::new(0.75, 0.25, 0.0),
+        epsilon = 1.0e-4
+    );
+
+    let far = Isometry3::translation(3.0, 0.0, 0.0);
+    assert!(query::intersection_volume(&far, &cuboid, &cuboid)
+        .unwrap()
+        .is_none());
+}
+
+#[test]
+fn intersection_volume_of_rotated_cuboids_matches_polyhedra() {
+    let cuboid1 = Cuboid::new(Vector3::new(1.0, 2.0, 0.5));
+    let cuboid2 = Cuboid::new(Vector3::new(0.7, 0.3, 1.5));
+    let pos12 = Isometry3::new(Vector3::new(0.4, -0.2, 0.1), Vector3::new(0.3, 0.5, -0.2));
+
+    let mprops = query::intersection_volume(&pos12, &cuboid1, &cuboid2)
+        .unwrap()
+        .unwrap();
+
+    let poly1 = cuboid_polyhedron(&cuboid1);
+    let poly2 = cuboid_polyhedron(&cuboid2);
+    let expected = query::intersection_volume(&pos12, &poly1, &poly2)
+        .unwrap()
+        .unwrap();
+    assert_relative_eq!(mprops.mass(), expected.mass(), epsilon = 1.0e-4);
+    assert_relative_eq!(mprops.local_com, expected.local_com, epsilon = 1.0e-4);
+
+    // The overlap can’t be larger than any of the two cuboids.
+    assert!(mprops.mass() > 0.0);
+    assert!(mprops.mass() <= 0.7 * 0.3 * 1.5 * 8.0);
+
+    // Two identical cubes rotated by 45 degrees overlap on a regular octagonal prism.
+    let cube = Cuboid::new(Vector3::repeat(1.0));
+    let rot = Isometry3::rotation(Vector3::z() * core::f32::consts::FRAC_PI_4);
+    let octagon = query::intersection_volume(&rot, &cube, &cube)
+        .unwrap()
+        .unwrap();
+    assert_relative_eq!(
+        octagon.mass(),
+        8.0 * (Real::sqrt(2.0) - 1.0) * 2.0,
+        epsilon = 1.0e-4
+    );
+    assert_relative_eq!(octagon.local_com, Point3::origin(), epsilon = 1.0e-4);
+
+    // A cuboid fully inside of the other one.
+    let small = Cuboid::new(Vector3::repeat(0.1));
+    let inside = query::intersection_volume(&pos12, &cuboid1, &small)
+        .unwrap()
+        .unwrap();
+    assert_relative_eq!(inside.mass(), 0.008, epsilon = 1.0e-5);
+    assert_relative_eq!(
+        inside.local_com,
+        Point3::from(pos12.translation.vector),
+        epsilon = 1.0e-4
+    );
+}
+
+#[test]
+fn intersection_volume_with_halfspace() {
+    let cuboid = Cuboid::new(Vector3::repeat(1.0));
+    let halfspace = HalfSpace::new(Vector3::y_axis());
+    let pos12 = Isometry3::translation(0.0, 0.5, 0.0);
+
+    let mprops = query::intersection_volume(&pos12, &cuboid, &halfspace)
+        .unwrap()
+        .unwrap();
+    assert_relative_eq!(mprops.mass(), 6.0, epsilon = 1.0e-4);
+    assert_relative_eq!(
+        mprops.local_com,
+        Point3::new(0.0, -0.25, 0.0),
+        epsilon = 1.0e-4
+    );
+
+    // Same query with the shapes swapped: the result is in the local-space of the half-space.
+    let swapped = query::intersection_volume(&pos12.inverse(), &halfspace, &cuboid)
+        .unwrap()
+        .unwrap();
+    assert_relative_eq!(swapped.mass(), 6.0, epsilon = 1.0e-4);
+    assert_relative_eq!(
+        swapped.local_com,
+        Point3::new(0.0, -0.75, 0.0),
+        epsilon = 1.0e-4
+    );
+
+    let poly = transformation::convex_polyhedron_halfspace_intersection(
+        &pos12,
+        &cuboid_polyhedron(&cuboid),
+        &halfspace,
+    )
+    .unwrap()
+    .unwrap();
+    assert_eq!(poly.points().len(), 8);
+}
+
+#[test]
+fn intersection_volume_of_curved_shapes() {
+    let ball = Ball::new(1.0);
+    let cuboid = Cuboid::new(Vector3::repeat(1.0));
+    let halfspace = HalfSpace::new(Vector3::y_axis());
+
+    // A ball centered on a face of a cube: half of it is inside.
+    let pos12 = Isometry3::translation(0.0, 1.0, 0.0);
+    let mprops = query::intersection_volume(&pos12, &cuboid, &ball)
+        .unwrap()
+        .unwrap();
+    let half_ball = 2.0 / 3.0 * core::f32::consts::PI;
+    assert!(mprops.mass() <= half_ball);
+    assert_relative_eq!(mprops.mass(), half_ball, max_relative = 3.0e-2);
+    assert_relative_eq!(mprops.local_com.y, 1.0 - 3.0 / 8.0, epsilon = 1.0e-2);
+
+    // Round and flat shapes.
+    let round_cuboid = RoundShape {
+        inner_shape: Cuboid::new(Vector3::repeat(0.5)),
+        border_radius: 0.5,
+    };
+    let mprops = query::intersection_volume(&Isometry3::identity(), &round_cuboid, &halfspace)
+        .unwrap()
+        .unwrap();
+    assert_relative_eq!(
+        mprops.mass(),
+        round_cuboid_volume(0.5, 0.5) / 2.0,
+        max_relative = 3.0e-2
+    );
+
+    let cylinder = Cylinder::new(1.0, 0.5);
+    let cone = Cone::new(1.0, 0.5);
+    let mprops = query::intersection_volume(&Isometry3::identity(), &cylinder, &cone)
+        .unwrap()
+        .unwrap();
+    let cone_volume = core::f32::consts::PI * 0.25 * 2.0 / 3.0;
+    assert_relative_eq!(mprops.mass(), cone_volume, epsilon = 1.0e-2);
+
+    let triangle = Triangle::new(
+        Point3::origin(),
+        Point3::new(1.0, 0.0, 0.0),
+        Point3::new(0.0, 1.0, 0.0),
+    );
+    assert_eq!(
+        query::intersection_volume(&Isometry3::identity(), &cuboid, &triangle),
+        Ok(None)
+    );
+}
+
+/// The volume of a cuboid with the given half-extent, dilated by the given radius.
+fn round_cuboid_volume(half_extent: Real, radius: Real) -> Real {
+    let side = half_extent * 2.0;
+    side * side * side
+        + 6.0 * side * side * radius
+        + 3.0 * side * core::f32::consts::PI * radius * radius
+        + 4.0 / 3.0 * core::f32::consts::PI * radius * radius * radius
+}
+
+#[test]
+fn intersection_volume_of_unsupported_shapes() {
+    let cuboid = Cuboid::new(Vector3::repeat(1.0));
+    let halfspace = HalfSpace::new(Vector3::y_axis());
+    let (vertices, indices) = cuboid.to_trimesh();
+    let mesh = TriMesh::new(vertices, indices).unwrap();
+    let pos12 = Isometry3::identity();
+
+    assert_eq!(
+        query::intersection_volume(&pos12, &mesh, &cuboid),
+        Err(IntersectionVolumeError::Unsupported)
+    );
+    assert_eq!(
+        query::intersection_volume(&pos12, &halfspace, &halfspace),
+        Err(IntersectionVolumeError::Unsupported)
+    );
+
+    // A failed convex hull isn’t reported as an empty intersection.
+    let invalid = Ball::new(Real::NAN);
+    assert!(matches!(
+        query::intersection_volume(&pos12, &cuboid, &invalid),
+        Err(IntersectionVolumeError::ConvexHullError(_))
+    ));
+}
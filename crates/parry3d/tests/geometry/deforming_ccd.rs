@@ -0,0 +1,137 @@
+use na::{Point3, Vector3};
+use parry3d::math::Real;
+use parry3d::query::details::{
+    cast_deforming_point_triangle, cast_deforming_segment_segment, cast_deforming_trimesh_trimesh,
+};
+use parry3d::shape::{Cuboid, Segment, TriMesh, Triangle};
+
+const TOLERANCE: Real = 1.0e-4;
+
+fn unit_triangle(z: Real) -> Triangle {
+    Triangle::new(
+        Point3::new(0.0, 0.0, z),
+        Point3::new(1.0, 0.0, z),
+        Point3::new(0.0, 1.0, z),
+    )
+}
+
+#[test]
+fn deforming_point_triangle() {
+    // A point crossing a static triangle.
+    let triangle = unit_triangle(0.0);
+    let toi = cast_deforming_point_triangle(
+        &Point3::new(0.2, 0.2, 1.0),
+        &Point3::new(0.2, 0.2, -1.0),
+        &triangle,
+        &triangle,
+        TOLERANCE,
+    )
+    .unwrap();
+    assert!((toi - 0.5).abs() < 1.0e-4, "{toi}");
+
+    // A point crossing the triangle’s plane outside of the triangle.
+    let toi = cast_deforming_point_triangle(
+        &Point3::new(2.0, 2.0, 1.0),
+        &Point3::new(2.0, 2.0, -1.0),
+        &triangle,
+        &triangle,
+        TOLERANCE,
+    );
+    assert!(toi.is_none());
+
+    // A static point hit by a deforming triangle: only its first vertex moves, sweeping
+    // through the point at a quarter of the motion.
+    let end = Triangle::new(Point3::new(0.0, 0.0, 4.0), triangle.b, triangle.c);
+    let toi = cast_deforming_point_triangle(
+        &Point3::new(0.0, 0.0, 1.0),
+        &Point3::new(0.0, 0.0, 1.0),
+        &triangle,
+        &end,
+        TOLERANCE,
+    )
+    .unwrap();
+    assert!((toi - 0.25).abs() < 1.0e-4, "{toi}");
+}
+
+#[test]
+fn deforming_fast_primitives() {
+    // Over the precision of the root isolation, these primitives move much further than the
+    // tolerance: the impact time must be refined before checking the hit.
+    let triangle = unit_triangle(0.0);
+    let (z_start, z_end) = (1.0e4, -1.2345678e4);
+    let expected = z_start / (z_start - z_end);
+    let toi = cast_deforming_point_triangle(
+        &Point3::new(0.2, 0.3, z_start),
+        &Point3::new(0.2, 0.3, z_end),
+        &triangle,
+        &triangle,
+        TOLERANCE,
+    )
+    .unwrap();
+    assert!((toi - expected).abs() < 1.0e-6, "{toi}");
+
+    let static_segment = Segment::new(Point3::new(0.0, -1.0, 0.0), Point3::new(0.0, 1.0, 0.0));
+    let start = Segment::new(
+        Point3::new(-1.0, 0.3, z_start),
+        Point3::new(1.0, 0.3, z_start),
+    );
+    let end = Segment::new(Point3::new(-1.0, 0.3, z_end), Point3::new(1.0, 0.3, z_end));
+    let toi =
+        cast_deforming_segment_segment(&start, &end, &static_segment, &static_segment, TOLERANCE)
+            .unwrap();
+    assert!((toi - expected).abs() < 1.0e-6, "{toi}");
+}
+
+#[test]
+fn deforming_segment_segment() {
+    let static_segment = Segment::new(Point3::new(0.0, -1.0, 0.0), Point3::new(0.0, 1.0, 0.0));
+    let start = Segment::new(Point3::new(-1.0, 0.0, 1.0), Point3::new(1.0, 0.0, 1.0));
+    let end = Segment::new(Point3::new(-1.0, 0.0, -1.0), Point3::new(1.0, 0.0, -1.0));
+    let toi =
+        cast_deforming_segment_segment(&start, &end, &static_segment, &static_segment, TOLERANCE)
+            .unwrap();
+    assert!((toi - 0.5).abs() < 1.0e-4, "{toi}");
+
+    // The moving segment passes next to the static one.
+    let start = Segment::new(Point3::new(0.5, 0.0, 1.0), Point3::new(2.0, 0.0, 1.0));
+    let end = Segment::new(Point3::new(0.5, 0.0, -1.0), Point3::new(2.0, 0.0, -1.0));
+    let toi =
+        cast_deforming_segment_segment(&start, &end, &static_segment, &static_segment, TOLERANCE);
+    assert!(toi.is_none());
+}
+
+#[test]
+fn deforming_trimesh_trimesh() {
+    let (vertices, indices) = Cuboid::new(Vector3::repeat(1.0)).to_trimesh();
+    let mesh1 = TriMesh::new(vertices.clone(), indices.clone()).unwrap();
+    let mesh2_vertices = vertices
+        .iter()
+        .map(|pt| pt + Vector3::new(5.0, 0.1, 0.2))
+        .collect();
+    let mesh2 = TriMesh::new(mesh2_vertices, indices).unwrap();
+
+    // The second cuboid moves 4 units toward the first one: they touch after 3 units.
+    let end_vertices2: Vec<_> = mesh2
+        .vertices()
+        .iter()
+        .map(|pt| pt - Vector3::x() * 4.0)
+        .collect();
+    let hits =
+        cast_deforming_trimesh_trimesh(&mesh1, mesh1.vertices(), &mesh2, &end_vertices2, TOLERANCE);
+    assert!(!hits.is_empty());
+    let first_toi = hits
+        .iter()
+        .map(|hit| hit.time_of_impact)
+        .fold(Real::MAX, Real::min);
+    assert!((first_toi - 0.75).abs() < 1.0e-3, "{first_toi}");
+
+    // Moving away from each other doesn’t generate any hit.
+    let end_vertices2: Vec<_> = mesh2
+        .vertices()
+        .iter()
+        .map(|pt| pt + Vector3::x() * 4.0)
+        .collect();
+    let hits =
+        cast_deforming_trimesh_trimesh(&mesh1, mesh1.vertices(), &mesh2, &end_vertices2, TOLERANCE);
+    assert!(hits.is_empty());
+}
@@ -0,0 +1,138 @@
+use core::cell::Cell;
+use na::{Isometry3, Point3, Unit, Vector3};
+use parry3d::math::Real;
+use parry3d::query::gjk::{GJKResult, GjkCache, VoronoiSimplex};
+use parry3d::query::{self, ContactManifold, DefaultQueryDispatcher, PersistentQueryDispatcher};
+use parry3d::shape::{Cone, Cuboid, Cylinder, SupportMap};
+
+/// A support map counting the number of support points computed, i.e., the GJK iterations.
+struct CountingSupportMap<S> {
+    shape: S,
+    calls: Cell<usize>,
+}
+
+impl<S> CountingSupportMap<S> {
+    fn new(shape: S) -> Self {
+        Self {
+            shape,
+            calls: Cell::new(0),
+        }
+    }
+}
+
+impl<S: SupportMap> SupportMap for CountingSupportMap<S> {
+    fn local_support_point(&self, dir: &Vector3<Real>) -> Point3<Real> {
+        self.calls.set(self.calls.get() + 1);
+        self.shape.local_support_point(dir)
+    }
+
+    fn local_support_point_toward(&self, dir: &Unit<Vector3<Real>>) -> Point3<Real> {
+        self.calls.set(self.calls.get() + 1);
+        self.shape.local_support_point_toward(dir)
+    }
+}
+
+/// A trajectory going from separation to penetration and back, with small steps.
+fn trajectory() -> impl Iterator<Item = Isometry3<Real>> {
+    (0..200).map(|i| {
+        let t = i as Real / 100.0;
+        Isometry3::new(
+            Vector3::new(3.0 - 2.5 * t * (2.0 - t), 0.2 * t, -0.1 * t),
+            Vector3::new(0.3 * t, 0.2 * t, 0.1),
+        )
+    })
+}
+
+#[test]
+fn gjk_cache_matches_cold_start() {
+    let cyl = Cylinder::new(0.8, 0.5);
+    let cuboid = Cuboid::new(Vector3::new(0.4, 0.6, 0.5));
+    let mut distance_cache = GjkCache::new();
+    let mut contact_cache = GjkCache::new();
+
+    for pos12 in trajectory() {
+        let cold = query::details::distance_support_map_support_map(&pos12, &cyl, &cuboid);
+        let warm = query::details::distance_support_map_support_map_with_cache(
+            &pos12,
+            &cyl,
+            &cuboid,
+            &mut distance_cache,
+        );
+        assert!((cold - warm).abs() < 1.0e-3);
+        assert!(!distance_cache.is_empty());
+
+        let cold = query::details::contact_support_map_support_map_with_params(
+            &pos12,
+            &cyl,
+            &cuboid,
+            0.1,
+            &mut VoronoiSimplex::new(),
+            None,
+        );
+        let warm = query::details::contact_support_map_support_map_with_cache(
+            &pos12,
+            &cyl,
+            &cuboid,
+            0.1,
+            &mut contact_cache,
+        );
+
+        match (cold, warm) {
+            (GJKResult::ClosestPoints(p1, p2, n), GJKResult::ClosestPoints(q1, q2, m)) => {
+                let cold_dist = (p2 - p1).dot(&n);
+                let warm_dist = (q2 - q1).dot(&m);
+                assert!((cold_dist - warm_dist).abs() < 1.0e-3);
+                assert_eq!(contact_cache.separating_axis(), Some(m));
+            }
+            (GJKResult::NoIntersection(_), GJKResult::NoIntersection(_)) => {}
+            (cold, warm) => panic!("Mismatch: {:?} vs. {:?}", cold, warm),
+        }
+    }
+}
+
+#[test]
+fn gjk_cache_reduces_iterations() {
+    let cyl = CountingSupportMap::new(Cylinder::new(0.8, 0.5));
+    let cuboid = Cuboid::new(Vector3::new(0.4, 0.6, 0.5));
+    let mut cache = GjkCache::new();
+    let mut cold_calls = 0;
+    let mut warm_calls = 0;
+
+    for pos12 in trajectory() {
+        cyl.calls.set(0);
+        let _ = query::details::distance_support_map_support_map(&pos12, &cyl, &cuboid);
+        cold_calls += cyl.calls.get();
+
+        cyl.calls.set(0);
+        let _ = query::details::distance_support_map_support_map_with_cache(
+            &pos12, &cyl, &cuboid, &mut cache,
+        );
+        warm_calls += cyl.calls.get();
+    }
+
+    assert!(warm_calls * 4 < cold_calls);
+}
+
+#[test]
+fn contact_manifold_stores_gjk_cache() {
+    let dispatcher = DefaultQueryDispatcher;
+    let cone = Cone::new(0.7, 0.6);
+    let cuboid = Cuboid::new(Vector3::new(0.4, 0.6, 0.5));
+    let mut manifolds: Vec<ContactManifold<(), ()>> = vec![];
+    let mut workspace = None;
+
+    for pos12 in trajectory() {
+        dispatcher
+            .contact_manifolds(&pos12, &cone, &cuboid, 0.1, &mut manifolds, &mut workspace)
+            .unwrap();
+
+        let contact = query::contact(&Isometry3::identity(), &cone, &pos12, &cuboid, 0.1).unwrap();
+        let deepest = manifolds[0].find_deepest_contact();
+        assert_eq!(contact.is_some(), deepest.is_some());
+
+        if let (Some(contact), Some(deepest)) = (contact, deepest) {
+            assert!(!manifolds[0].gjk_cache().is_empty());
+            assert!((contact.dist - deepest.dist).abs() < 1.0e-2);
+        }
+    }
+}
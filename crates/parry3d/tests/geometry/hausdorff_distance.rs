@@ -0,0 +1,82 @@
+use na::{Isometry3, Point3, Vector3};
+use parry3d::math::Real;
+use parry3d::query::details::{
+    hausdorff_distance_trimesh_shape, hausdorff_distance_trimesh_trimesh,
+};
+use parry3d::shape::{Ball, Cuboid, TriMesh};
+
+fn cuboid_mesh(half_extent: Real) -> TriMesh {
+    let (vertices, indices) = Cuboid::new(Vector3::repeat(half_extent)).to_trimesh();
+    TriMesh::new(vertices, indices).unwrap()
+}
+
+#[test]
+fn hausdorff_distance_between_nested_cuboids() {
+    let small = cuboid_mesh(1.0);
+    let large = cuboid_mesh(1.1);
+    let pos = Isometry3::new(Vector3::new(1.0, 2.0, 3.0), Vector3::new(0.1, 0.2, 0.3));
+
+    // Every point of the small cuboid is at a distance 0.1 from the large cuboid’s surface.
+    let one_sided = hausdorff_distance_trimesh_shape(&pos, &small, &pos, &large, 0.1);
+    assert!((one_sided.max - 0.1).abs() < 1.0e-4);
+    assert!((one_sided.mean - 0.1).abs() < 1.0e-4);
+    assert!((one_sided.rms - 0.1).abs() < 1.0e-4);
+
+    // The corners of the large cuboid are further away from the small one.
+    let symmetric = hausdorff_distance_trimesh_trimesh(&pos, &small, &pos, &large, 0.1);
+    let expected = 0.1 * Real::sqrt(3.0);
+    assert!((symmetric.max - expected).abs() < 1.0e-4);
+    assert!(symmetric.mean > 0.1 && symmetric.mean < symmetric.rms);
+    assert!(symmetric.rms < symmetric.max);
+
+    // The worst point pair is given in world-space, with the first point on the small cuboid.
+    let local_worst1 = pos.inverse_transform_point(&symmetric.worst_point1);
+    let local_worst2 = pos.inverse_transform_point(&symmetric.worst_point2);
+    assert!((local_worst1.coords.abs() - Vector3::repeat(1.0)).norm() < 1.0e-4);
+    assert!((local_worst2.coords.abs() - Vector3::repeat(1.1)).norm() < 1.0e-4);
+}
+
+#[test]
+fn hausdorff_distance_trimesh_ball() {
+    let mesh = cuboid_mesh(1.0);
+    let ball = Ball::new(1.0);
+    let result = hausdorff_distance_trimesh_shape(
+        &Isometry3::identity(),
+        &mesh,
+        &Isometry3::identity(),
+        &ball,
+        0.05,
+    );
+
+    // The corners are the furthest points from the sphere.
+    let expected = Real::sqrt(3.0) - 1.0;
+    assert!((result.max - expected).abs() < 1.0e-4);
+    assert!((result.worst_point1.coords.abs() - Vector3::repeat(1.0)).norm() < 1.0e-4);
+    assert!(((result.worst_point2 - Point3::origin()).norm() - 1.0).abs() < 1.0e-4);
+    // The centers of the faces touch the sphere.
+    assert!(result.mean < result.max);
+}
+
+#[test]
+fn hausdorff_distance_tiny_sample_spacing() {
+    // The subdivision of each triangle is bounded, so this completes quickly.
+    let vertices = vec![
+        Point3::new(1.0, 0.0, 0.0),
+        Point3::new(0.0, 1.0, 0.0),
+        Point3::new(0.0, 0.0, 1.0),
+    ];
+    let mesh = TriMesh::new(vertices, vec![[0, 1, 2]]).unwrap();
+    let ball = Ball::new(1.0);
+    let pos = Isometry3::identity();
+    let result = hausdorff_distance_trimesh_shape(&pos, &mesh, &pos, &ball, 1.0e-30);
+    // The barycenter of the triangle is the furthest point from the sphere.
+    assert!((result.max - (1.0 - 1.0 / Real::sqrt(3.0))).abs() < 1.0e-2);
+}
+
+#[test]
+#[should_panic]
+fn hausdorff_distance_invalid_sample_spacing() {
+    let mesh = cuboid_mesh(1.0);
+    let pos = Isometry3::identity();
+    let _ = hausdorff_distance_trimesh_trimesh(&pos, &mesh, &pos, &mesh, 0.0);
+}
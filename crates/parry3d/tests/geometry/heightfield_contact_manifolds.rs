@@ -0,0 +1,147 @@
+use na::{DMatrix, Isometry3, Point3, Vector3};
+use parry3d::bounding_volume::BoundingVolume;
+use parry3d::query::{ContactManifold, DefaultQueryDispatcher, PersistentQueryDispatcher};
+use parry3d::shape::{HeightField, Shape, Voxels};
+
+fn flat_heightfield(ncells: usize, size: f32) -> HeightField {
+    HeightField::new(
+        DMatrix::zeros(ncells + 1, ncells + 1),
+        Vector3::new(size, 1.0, size),
+    )
+}
+
+fn compute_manifolds(
+    pos12: &Isometry3<f32>,
+    shape1: &dyn Shape,
+    shape2: &dyn Shape,
+    manifolds: &mut Vec<ContactManifold<(), ()>>,
+    workspace: &mut Option<parry3d::query::ContactManifoldsWorkspace>,
+) {
+    DefaultQueryDispatcher
+        .contact_manifolds(pos12, shape1, shape2, 0.1, manifolds, workspace)
+        .unwrap();
+}
+
+#[test]
+fn heightfield_heightfield_contact_manifolds() {
+    let heightfield1 = flat_heightfield(20, 20.0);
+    let heightfield2 = flat_heightfield(2, 2.0);
+    let mut manifolds = vec![];
+    let mut workspace = None;
+
+    let pos12 = Isometry3::translation(3.0, 0.05, -4.0);
+    compute_manifolds(
+        &pos12,
+        &heightfield1,
+        &heightfield2,
+        &mut manifolds,
+        &mut workspace,
+    );
+
+    let num_points: usize = manifolds.iter().map(|m| m.points.len()).sum();
+    assert!(num_points > 0);
+
+    for manifold in &manifolds {
+        // Only the triangles below the second heightfield are visited.
+        let tri1 = heightfield1.triangle_at_id(manifold.subshape1).unwrap();
+        let tri2 = heightfield2.triangle_at_id(manifold.subshape2).unwrap();
+        assert!(tri1
+            .local_aabb()
+            .intersects(&tri2.aabb(&pos12).loosened(0.1)));
+
+        for pt in &manifold.points {
+            assert_relative_eq!(pt.dist, 0.05, epsilon = 1.0e-4);
+            assert_relative_eq!(manifold.local_n1.y.abs(), 1.0, epsilon = 1.0e-4);
+        }
+    }
+
+    // The manifolds are kept for the same pairs of triangles after a small motion.
+    let pairs: Vec<_> = manifolds
+        .iter()
+        .map(|m| (m.subshape1, m.subshape2))
+        .collect();
+    let pos12 = Isometry3::translation(3.0, 0.04, -4.0);
+    compute_manifolds(
+        &pos12,
+        &heightfield1,
+        &heightfield2,
+        &mut manifolds,
+        &mut workspace,
+    );
+    let new_pairs: Vec<_> = manifolds
+        .iter()
+        .map(|m| (m.subshape1, m.subshape2))
+        .collect();
+    assert_eq!(pairs, new_pairs);
+
+    // The manifolds are removed when the heightfields no longer overlap.
+    let pos12 = Isometry3::translation(30.0, 0.05, -4.0);
+    compute_manifolds(
+        &pos12,
+        &heightfield1,
+        &heightfield2,
+        &mut manifolds,
+        &mut workspace,
+    );
+    assert!(manifolds.is_empty());
+}
+
+#[test]
+fn heightfield_voxels_contact_manifolds() {
+    let heightfield = flat_heightfield(10, 10.0);
+    let voxels = Voxels::new(
+        Vector3::repeat(1.0),
+        &[
+            Point3::new(0, 0, 0),
+            Point3::new(1, 0, 0),
+            Point3::new(0, 0, 1),
+            Point3::new(1, 0, 1),
+            Point3::new(0, 1, 0),
+        ],
+    );
+
+    for dist in [0.02, -0.02] {
+        // Both the voxels-heightfield and heightfield-voxels orders must give the same contacts.
+        let pos_hf_vox = Isometry3::translation(-1.3, dist, 0.7);
+
+        let mut manifolds_hf_vox = vec![];
+        compute_manifolds(
+            &pos_hf_vox,
+            &heightfield,
+            &voxels,
+            &mut manifolds_hf_vox,
+            &mut None,
+        );
+        let mut manifolds_vox_hf = vec![];
+        compute_manifolds(
+            &pos_hf_vox.inverse(),
+            &voxels,
+            &heightfield,
+            &mut manifolds_vox_hf,
+            &mut None,
+        );
+
+        let num_points: usize = manifolds_hf_vox.iter().map(|m| m.points.len()).sum();
+        let num_flipped_points: usize = manifolds_vox_hf.iter().map(|m| m.points.len()).sum();
+        assert!(num_points > 0);
+        assert_eq!(num_points, num_flipped_points);
+
+        for manifold in &manifolds_hf_vox {
+            for pt in &manifold.points {
+                // Contacts against the internal faces of the voxels must have been filtered out.
+                assert_relative_eq!(manifold.local_n1, Vector3::y(), epsilon = 1.0e-4);
+                assert_relative_eq!(pt.dist, dist, epsilon = 1.0e-4);
+                // The contacts are only on the voxels of the bottom layer.
+                let local_p2 = manifold.subshape_pos2.unwrap() * pt.local_p2;
+                assert_relative_eq!(local_p2.y, 0.0, epsilon = 1.0e-4);
+            }
+        }
+
+        for manifold in &manifolds_vox_hf {
+            for pt in &manifold.points {
+                assert_relative_eq!(manifold.local_n1, -Vector3::y(), epsilon = 1.0e-4);
+                assert_relative_eq!(pt.dist, dist, epsilon = 1.0e-4);
+            }
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use na::Point3;
+use parry3d::math::Real;
+use parry3d::transformation::{self, IncrementalConvexHull};
+use std::collections::HashSet;
+
+fn random_points(n: usize) -> Vec<Point3<Real>> {
+    let mut rng = oorandom::Rand32::new(42);
+    (0..n)
+        .map(|_| {
+            Point3::new(
+                rng.rand_float() * 2.0 - 1.0,
+                rng.rand_float() * 2.0 - 1.0,
+                rng.rand_float() * 2.0 - 1.0,
+            )
+        })
+        .collect()
+}
+
+fn volume((vertices, indices): &(Vec<Point3<Real>>, Vec<[u32; 3]>)) -> Real {
+    indices
+        .iter()
+        .map(|idx| {
+            let [a, b, c] = idx.map(|i| vertices[i as usize].coords);
+            a.dot(&b.cross(&c)) / 6.0
+        })
+        .sum()
+}
+
+#[test]
+fn incremental_convex_hull_matches_batch_hull() {
+    let points = random_points(200);
+    let mut hull = IncrementalConvexHull::new();
+    let mut facets = HashSet::new();
+
+    for pt in &points {
+        let update = hull.add_point(*pt).unwrap();
+
+        for id in &update.destroyed {
+            assert!(facets.remove(id));
+            assert!(hull.facet(*id).is_none());
+        }
+
+        for id in &update.created {
+            assert!(facets.insert(*id));
+        }
+    }
+
+    // The reported changes account for all the facets of the hull.
+    let hull_facets: HashSet<_> = hull.facets().map(|(id, _)| id).collect();
+    assert_eq!(facets, hull_facets);
+
+    let incremental = hull.vertices_and_indices();
+    transformation::check_convex_hull(&incremental.0, &incremental.1).unwrap();
+    assert!(hull.to_convex_polyhedron().is_some());
+
+    let expected = transformation::convex_hull(&points);
+    assert_relative_eq!(volume(&incremental), volume(&expected), epsilon = 1.0e-4);
+    assert_eq!(incremental.0.len(), expected.0.len());
+
+    // Inserting all the points at once gives the same hull.
+    let batch = IncrementalConvexHull::from_points(&points).unwrap();
+    assert_relative_eq!(
+        volume(&batch.vertices_and_indices()),
+        volume(&expected),
+        epsilon = 1.0e-4
+    );
+}
+
+#[test]
+fn incremental_convex_hull_updates() {
+    let mut hull = IncrementalConvexHull::new();
+
+    // Coplanar points only form a flat hull.
+    let update = hull
+        .add_points(&[
+            Point3::origin(),
+            Point3::new(1.0, 0.0, 0.0),
+            Point3::new(0.0, 1.0, 0.0),
+        ])
+        .unwrap();
+    assert!(update.is_empty());
+    assert!(hull.is_degenerate());
+    assert!(hull.to_convex_polyhedron().is_none());
+    assert_eq!(hull.vertices_and_indices().0.len(), 3);
+
+    // A point off the plane creates a tetrahedron.
+    let update = hull.add_point(Point3::new(0.0, 0.0, 1.0)).unwrap();
+    assert!(!hull.is_degenerate());
+    assert_eq!(update.created.len(), 4);
+    assert!(update.destroyed.is_empty());
+
+    // A point inside of the hull doesn’t change it.
+    let update = hull.add_point(Point3::new(0.1, 0.1, 0.1)).unwrap();
+    assert!(update.is_empty());
+
+    // A point in front of the slanted face replaces it by three facets.
+    let slanted = hull
+        .facets()
+        .find(|(_, idx)| {
+            let mut idx = *idx;
+            idx.sort();
+            idx == [1, 2, 3]
+        })
+        .unwrap()
+        .0;
+    let update = hull.add_point(Point3::new(1.0, 1.0, 1.0)).unwrap();
+    assert_eq!(update.destroyed, vec![slanted]);
+    assert_eq!(update.created.len(), 3);
+
+    for id in update.created {
+        assert!(hull.facet(id).unwrap().contains(&5));
+    }
+
+    assert!(hull.to_convex_polyhedron().is_some());
+    assert_relative_eq!(volume(&hull.vertices_and_indices()), 0.5, epsilon = 1.0e-5);
+    assert_eq!(hull.points().len(), 6);
+}
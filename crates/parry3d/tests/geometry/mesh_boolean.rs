@@ -0,0 +1,238 @@
+use na::{Isometry3, Point3, Translation3, UnitQuaternion, Vector3};
+use parry3d::mass_properties::MassProperties;
+use parry3d::query::{self, PointQuery};
+use parry3d::shape::{Cuboid, TriMesh, TriMeshFlags};
+use parry3d::transformation::{
+    intersect_meshes_with_tolerances, mesh_boolean, MeshBooleanOp, MeshIntersectionTolerances,
+    TriangleProvenance,
+};
+
+fn cuboid_mesh(half_extents: Vector3<f32>) -> TriMesh {
+    let (vertices, indices) = Cuboid::new(half_extents).to_trimesh();
+    TriMesh::with_flags(
+        vertices,
+        indices,
+        TriMeshFlags::ORIENTED | TriMeshFlags::HALF_EDGE_TOPOLOGY,
+    )
+    .unwrap()
+}
+
+fn volume(mesh: &TriMesh) -> f32 {
+    MassProperties::from_trimesh(1.0, mesh.vertices(), mesh.indices()).mass()
+}
+
+#[test]
+fn mesh_boolean_volumes() {
+    let half_extents1 = Vector3::new(1.0, 1.0, 1.0);
+    let half_extents2 = Vector3::new(0.8, 1.2, 0.6);
+    let mesh1 = cuboid_mesh(half_extents1);
+    let mesh2 = cuboid_mesh(half_extents2);
+    let pos1 = Isometry3::translation(0.1, 0.2, 0.3);
+    let pos2 = Isometry3::new(Vector3::new(0.9, 0.6, 0.5), Vector3::new(0.1, 0.2, 0.3));
+
+    let volume1 = 8.0 * half_extents1.product();
+    let volume2 = 8.0 * half_extents2.product();
+    let inter = query::intersection_volume(
+        &pos1.inv_mul(&pos2),
+        &Cuboid::new(half_extents1),
+        &Cuboid::new(half_extents2),
+    )
+    .unwrap()
+    .unwrap()
+    .mass();
+
+    let expected = [
+        (MeshBooleanOp::Intersection, inter),
+        (MeshBooleanOp::Union, volume1 + volume2 - inter),
+        (MeshBooleanOp::Difference, volume1 - inter),
+        (MeshBooleanOp::Xor, volume1 + volume2 - 2.0 * inter),
+    ];
+
+    for (op, expected_volume) in expected {
+        let result = mesh_boolean(op, &pos1, &mesh1, &pos2, &mesh2)
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.provenance.len(), result.mesh.indices().len());
+        assert!(result.mesh.topology().is_some());
+        assert!(result.mesh.pseudo_normals_if_oriented().is_some());
+        assert_relative_eq!(volume(&result.mesh), expected_volume, epsilon = 1.0e-3);
+    }
+}
+
+#[test]
+fn mesh_boolean_provenance() {
+    let mesh1 = cuboid_mesh(Vector3::repeat(1.0));
+    let mesh2 = cuboid_mesh(Vector3::repeat(0.7));
+    let pos1 = Isometry3::identity();
+    let pos2 = Isometry3::new(Vector3::new(0.8, 0.5, 0.4), Vector3::new(0.2, -0.1, 0.3));
+
+    for op in [
+        MeshBooleanOp::Intersection,
+        MeshBooleanOp::Union,
+        MeshBooleanOp::Difference,
+    ] {
+        let result = mesh_boolean(op, &pos1, &mesh1, &pos2, &mesh2)
+            .unwrap()
+            .unwrap();
+        let mut from_mesh1 = false;
+        let mut from_mesh2 = false;
+
+        for (tri, provenance) in result.mesh.triangles().zip(&result.provenance) {
+            let (source, pos, reversed) = match *provenance {
+                TriangleProvenance::Mesh1(tid) => {
+                    from_mesh1 = true;
+                    (mesh1.triangle(tid), pos1, false)
+                }
+                TriangleProvenance::Mesh2(tid) => {
+                    from_mesh2 = true;
+                    (mesh2.triangle(tid), pos2, op == MeshBooleanOp::Difference)
+                }
+            };
+
+            // The output triangle lies on its source triangle, with the expected orientation.
+            let source = source.transformed(&pos);
+            let source_normal = source.normal().unwrap();
+            let normal = tri.normal().unwrap();
+            let sign = if reversed { -1.0 } else { 1.0 };
+            assert_relative_eq!(*normal, *source_normal * sign, epsilon = 1.0e-4);
+            assert_relative_eq!(
+                (tri.center() - source.a).dot(&source_normal),
+                0.0,
+                epsilon = 1.0e-4
+            );
+        }
+
+        assert!(from_mesh1 && from_mesh2);
+    }
+}
+
+#[test]
+fn mesh_boolean_chaining() {
+    let mesh = cuboid_mesh(Vector3::repeat(1.0));
+    let pos1 = Isometry3::identity();
+    let pos2 = Isometry3::new(Vector3::new(1.0, 0.3, 0.2), Vector3::new(0.0, 0.3, 0.0));
+    let pos3 = Isometry3::new(Vector3::new(0.4, 1.1, -0.3), Vector3::new(0.2, 0.0, 0.1));
+
+    let union = mesh_boolean(MeshBooleanOp::Union, &pos1, &mesh, &pos2, &mesh)
+        .unwrap()
+        .unwrap();
+    let carved = mesh_boolean(
+        MeshBooleanOp::Difference,
+        &Isometry3::identity(),
+        &union.mesh,
+        &pos3,
+        &mesh,
+    )
+    .unwrap()
+    .unwrap();
+
+    assert!(volume(&carved.mesh) < volume(&union.mesh));
+
+    // Provenance refers to the triangles of the union, which refer to the original meshes.
+    for provenance in &carved.provenance {
+        if let TriangleProvenance::Mesh1(tid) = provenance {
+            assert!((*tid as usize) < union.provenance.len());
+        }
+    }
+
+    // Disjoint meshes.
+    let far = Isometry3::translation(10.0, 0.0, 0.0);
+    let union = mesh_boolean(MeshBooleanOp::Union, &pos1, &mesh, &far, &mesh)
+        .unwrap()
+        .unwrap();
+    assert_relative_eq!(volume(&union.mesh), 16.0, epsilon = 1.0e-4);
+    assert!(
+        mesh_boolean(MeshBooleanOp::Intersection, &pos1, &mesh, &far, &mesh)
+            .unwrap()
+            .is_none()
+    );
+}
+
+fn aabb_overlap(a: [(f32, f32); 3], b: [(f32, f32); 3]) -> [(f32, f32); 3] {
+    core::array::from_fn(|i| (a[i].0.max(b[i].0), a[i].1.min(b[i].1)))
+}
+
+fn aabb_volume(a: [(f32, f32); 3]) -> f32 {
+    a.iter().map(|(min, max)| (max - min).max(0.0)).product()
+}
+
+#[test]
+fn mesh_boolean_xor_closed_shells() {
+    let mesh = cuboid_mesh(Vector3::repeat(1.0));
+    let pos1 = Isometry3::identity();
+    let pos2 = Isometry3::translation(1.0, 0.3, 0.2);
+    let xor = mesh_boolean(MeshBooleanOp::Xor, &pos1, &mesh, &pos2, &mesh)
+        .unwrap()
+        .unwrap();
+
+    // Both differences are closed shells sharing no edge: every half-edge has a twin.
+    let topology = xor.mesh.topology().unwrap();
+    assert!(topology.half_edges.iter().all(|e| e.twin != u32::MAX));
+    assert_eq!(
+        xor.mesh
+            .connected_components()
+            .unwrap()
+            .num_connected_components(),
+        2
+    );
+
+    // Point containment is consistent with the symmetric difference.
+    assert!(xor.mesh.contains_local_point(&Point3::new(-0.5, 0.0, 0.0)));
+    assert!(xor.mesh.contains_local_point(&Point3::new(1.5, 0.3, 0.2)));
+    assert!(!xor.mesh.contains_local_point(&Point3::new(0.5, 0.2, 0.1)));
+    assert!(!xor.mesh.contains_local_point(&Point3::new(3.0, 0.0, 0.0)));
+
+    // The result can be chained with another boolean.
+    let pos3 = Isometry3::translation(0.5, -0.5, 0.15);
+    let mesh3 = cuboid_mesh(Vector3::repeat(0.9));
+    let chained = mesh_boolean(
+        MeshBooleanOp::Intersection,
+        &Isometry3::identity(),
+        &xor.mesh,
+        &pos3,
+        &mesh3,
+    )
+    .unwrap()
+    .unwrap();
+
+    let a = [(-1.0, 1.0), (-1.0, 1.0), (-1.0, 1.0)];
+    let b = [(0.0, 2.0), (-0.7, 1.3), (-0.8, 1.2)];
+    let c = [(-0.4, 1.4), (-1.4, 0.4), (-0.75, 1.05)];
+    let expected = aabb_volume(aabb_overlap(a, c)) + aabb_volume(aabb_overlap(b, c))
+        - 2.0 * aabb_volume(aabb_overlap(aabb_overlap(a, b), c));
+    assert_relative_eq!(volume(&chained.mesh), expected, epsilon = 1.0e-3);
+}
+
+#[test]
+fn intersect_meshes_sub_epsilon_segments() {
+    // A corner of the second cube pokes through the top face of the first one by less than the
+    // insertion epsilon, so both endpoints of each intersection segment are merged.
+    let mesh1 = cuboid_mesh(Vector3::repeat(1.0));
+    let mesh2 = cuboid_mesh(Vector3::repeat(0.5));
+    let depth = 1.0e-3;
+    let rotation =
+        UnitQuaternion::rotation_between(&Vector3::repeat(-1.0), &-Vector3::z()).unwrap();
+    let pos2 = Isometry3::from_parts(
+        Translation3::new(0.0, 0.0, 1.0 + 0.5 * 3.0f32.sqrt() - depth),
+        rotation,
+    );
+    let tolerances = MeshIntersectionTolerances {
+        global_insertion_epsilon: 1.0e-3,
+        ..Default::default()
+    };
+
+    let result = intersect_meshes_with_tolerances(
+        &Isometry3::identity(),
+        &mesh1,
+        false,
+        &pos2,
+        &mesh2,
+        false,
+        tolerances,
+    )
+    .unwrap();
+
+    if let Some(result) = result {
+        assert!(volume(&result) < 1.0e-6);
+    }
+}
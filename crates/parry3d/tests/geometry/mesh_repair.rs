@@ -0,0 +1,197 @@
+use na::Point3;
+use parry3d::math::Real;
+use parry3d::shape::{Ball, TriMesh, TriMeshFlags};
+use parry3d::transformation::{repair_trimesh, MeshRepairParameters};
+
+fn cube() -> (Vec<Point3<Real>>, Vec<[u32; 3]>) {
+    let vertices = vec![
+        Point3::new(-1.0, -1.0, -1.0),
+        Point3::new(1.0, -1.0, -1.0),
+        Point3::new(1.0, 1.0, -1.0),
+        Point3::new(-1.0, 1.0, -1.0),
+        Point3::new(-1.0, -1.0, 1.0),
+        Point3::new(1.0, -1.0, 1.0),
+        Point3::new(1.0, 1.0, 1.0),
+        Point3::new(-1.0, 1.0, 1.0),
+    ];
+    let indices = vec![
+        [0, 2, 1],
+        [0, 3, 2],
+        [4, 5, 6],
+        [4, 6, 7],
+        [0, 1, 5],
+        [0, 5, 4],
+        [2, 3, 7],
+        [2, 7, 6],
+        [1, 2, 6],
+        [1, 6, 5],
+        [0, 4, 7],
+        [0, 7, 3],
+    ];
+    (vertices, indices)
+}
+
+fn signed_volume(vertices: &[Point3<Real>], indices: &[[u32; 3]]) -> Real {
+    indices
+        .iter()
+        .map(|idx| {
+            let [a, b, c] = idx.map(|i| vertices[i as usize].coords);
+            a.dot(&b.cross(&c)) / 6.0
+        })
+        .sum()
+}
+
+/// Checks that the mesh is closed and oriented, with the given signed volume.
+fn assert_closed_and_oriented(vertices: Vec<Point3<Real>>, indices: Vec<[u32; 3]>, volume: Real) {
+    assert_relative_eq!(signed_volume(&vertices, &indices), volume, epsilon = 1.0e-3);
+    let mesh = TriMesh::with_flags(
+        vertices,
+        indices,
+        TriMeshFlags::HALF_EDGE_TOPOLOGY | TriMeshFlags::ORIENTED,
+    )
+    .unwrap();
+    assert!(mesh.pseudo_normals_if_oriented().is_some());
+    assert!(mesh
+        .topology()
+        .unwrap()
+        .half_edges
+        .iter()
+        .all(|half_edge| half_edge.twin != u32::MAX));
+}
+
+#[test]
+fn repair_triangle_soup_with_flipped_triangles_and_hole() {
+    let (vertices, mut indices) = cube();
+
+    // Flip the whole mesh, except for two triangles.
+    for idx in &mut indices[2..] {
+        idx.swap(0, 1);
+    }
+
+    // Remove a triangle, and duplicate another one.
+    let _ = indices.remove(5);
+    indices.push(indices[0]);
+
+    // Unweld all the vertices.
+    let soup_vertices: Vec<_> = indices
+        .iter()
+        .flat_map(|idx| idx.map(|i| vertices[i as usize]))
+        .collect();
+    let soup_indices: Vec<_> = (0..indices.len() as u32)
+        .map(|i| [i * 3, i * 3 + 1, i * 3 + 2])
+        .collect();
+
+    let mut mesh = TriMesh::new(soup_vertices.clone(), soup_indices.clone()).unwrap();
+    assert!(mesh
+        .set_flags(TriMeshFlags::MERGE_DUPLICATE_VERTICES | TriMeshFlags::HALF_EDGE_TOPOLOGY)
+        .is_err());
+
+    let (vertices, indices, report) = repair_trimesh(
+        &soup_vertices,
+        &soup_indices,
+        &MeshRepairParameters::default(),
+    );
+
+    assert!(report.has_changes());
+    assert_eq!(vertices.len(), 8);
+    assert_eq!(report.removed_vertices, soup_vertices.len() - 8);
+    assert_eq!(report.removed_triangles, 1);
+    assert_eq!(report.split_t_junctions, 0);
+    assert_eq!(report.flipped_triangles, 9);
+    assert_eq!(report.filled_holes, 1);
+    assert_eq!(report.added_triangles, 1);
+    assert_eq!(report.unfilled_holes, 0);
+    assert_eq!(indices.len(), 12);
+    assert_closed_and_oriented(vertices, indices, 8.0);
+}
+
+#[test]
+fn repair_t_junction() {
+    let (mut vertices, mut indices) = cube();
+
+    // Split the triangle [0, 2, 1] at the midpoint of its edge [1, 0], without splitting the
+    // adjacent triangle [0, 1, 5].
+    vertices.push(na::center(&vertices[0], &vertices[1]));
+    indices[0] = [0, 2, 8];
+    indices.push([8, 2, 1]);
+
+    let (vertices, indices, report) =
+        repair_trimesh(&vertices, &indices, &MeshRepairParameters::default());
+
+    assert_eq!(report.split_t_junctions, 1);
+    assert_eq!(report.flipped_triangles, 0);
+    assert_eq!(report.filled_holes, 0);
+    assert_eq!(indices.len(), 14);
+    assert_closed_and_oriented(vertices, indices, 8.0);
+}
+
+#[test]
+fn repair_fills_large_holes() {
+    let (vertices, indices) = Ball::new(1.0).to_trimesh(20, 20);
+    let volume = signed_volume(&vertices, &indices);
+
+    // Remove a band of triangles around the equator, splitting the sphere into two components,
+    // and a single triangle from the southern hemisphere.
+    let mut open_indices: Vec<_> = indices
+        .iter()
+        .copied()
+        .filter(|idx| !idx.iter().all(|i| vertices[*i as usize].y.abs() < 0.2))
+        .collect();
+    let removed = open_indices
+        .iter()
+        .position(|idx| idx.iter().all(|i| vertices[*i as usize].y < -0.5))
+        .unwrap();
+    let _ = open_indices.remove(removed);
+
+    let params = MeshRepairParameters {
+        max_hole_edges: 10,
+        ..Default::default()
+    };
+    let (_, _, report) = repair_trimesh(&vertices, &open_indices, &params);
+    assert_eq!(report.filled_holes, 1);
+    assert_eq!(report.unfilled_holes, 2);
+
+    let (new_vertices, new_indices, report) =
+        repair_trimesh(&vertices, &open_indices, &MeshRepairParameters::default());
+    assert_eq!(report.filled_holes, 3);
+    assert_eq!(report.unfilled_holes, 0);
+    assert_eq!(report.flipped_triangles, 0);
+    assert_eq!(
+        new_indices.len(),
+        open_indices.len() + report.added_triangles
+    );
+
+    let mesh = TriMesh::with_flags(
+        new_vertices.clone(),
+        new_indices.clone(),
+        TriMeshFlags::CONNECTED_COMPONENTS,
+    )
+    .unwrap();
+    assert_eq!(
+        mesh.connected_components()
+            .unwrap()
+            .num_connected_components(),
+        2
+    );
+    assert!(signed_volume(&new_vertices, &new_indices) < volume);
+    assert_closed_and_oriented(
+        new_vertices.clone(),
+        new_indices.clone(),
+        signed_volume(&new_vertices, &new_indices).abs(),
+    );
+
+    // Flipping one of the components is fixed.
+    let mut flipped_indices = open_indices.clone();
+    for idx in &mut flipped_indices {
+        if vertices[idx[0] as usize].y > 0.0 {
+            idx.swap(1, 2);
+        }
+    }
+    let (_, _, report) = repair_trimesh(
+        &vertices,
+        &flipped_indices,
+        &MeshRepairParameters::default(),
+    );
+    assert!(report.flipped_triangles > 0);
+    assert_eq!(report.filled_holes, 3);
+}
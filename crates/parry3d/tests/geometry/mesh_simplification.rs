@@ -0,0 +1,185 @@
+use na::Point3;
+use parry3d::mass_properties::MassProperties;
+use parry3d::math::Real;
+use parry3d::query::PointQuery;
+use parry3d::shape::{Ball, TriMesh, TriMeshFlags};
+use parry3d::transformation::{
+    simplify_trimesh, MeshSimplificationBound, MeshSimplificationParameters,
+};
+use std::collections::HashMap;
+
+/// A cube with each face subdivided into `n * n` quads.
+fn subdivided_cube(n: u32) -> (Vec<Point3<Real>>, Vec<[u32; 3]>) {
+    let mut vertices = vec![];
+    let mut vertex_ids = HashMap::new();
+    let mut indices = vec![];
+    let mut vertex = |coords: [u32; 3]| -> u32 {
+        *vertex_ids.entry(coords).or_insert_with(|| {
+            vertices.push(Point3::from(
+                coords.map(|c| c as Real * 2.0 / n as Real - 1.0),
+            ));
+            vertices.len() as u32 - 1
+        })
+    };
+
+    for axis in 0..3 {
+        for side in [0, n] {
+            for u in 0..n {
+                for v in 0..n {
+                    let mut corners = [[u, v], [u + 1, v], [u + 1, v + 1], [u, v + 1]].map(|uv| {
+                        let mut coords = [0; 3];
+                        coords[axis] = side;
+                        coords[(axis + 1) % 3] = uv[0];
+                        coords[(axis + 2) % 3] = uv[1];
+                        vertex(coords)
+                    });
+
+                    if side == 0 {
+                        corners.reverse();
+                    }
+
+                    indices.push([corners[0], corners[1], corners[2]]);
+                    indices.push([corners[0], corners[2], corners[3]]);
+                }
+            }
+        }
+    }
+
+    (vertices, indices)
+}
+
+fn volume(vertices: &[Point3<Real>], indices: &[[u32; 3]]) -> Real {
+    MassProperties::from_trimesh(1.0, vertices, indices).mass()
+}
+
+/// Checks that the mesh is a closed manifold with the topology of a sphere.
+fn assert_closed_sphere_topology(vertices: Vec<Point3<Real>>, indices: Vec<[u32; 3]>) -> TriMesh {
+    let num_faces = indices.len() as i64;
+    let num_vertices = vertices.len() as i64;
+    let mesh = TriMesh::with_flags(
+        vertices,
+        indices,
+        TriMeshFlags::HALF_EDGE_TOPOLOGY | TriMeshFlags::ORIENTED,
+    )
+    .unwrap();
+    assert!(mesh
+        .topology()
+        .unwrap()
+        .half_edges
+        .iter()
+        .all(|half_edge| half_edge.twin != u32::MAX));
+    // Euler characteristic of a sphere, with 3 * F / 2 edges.
+    assert_eq!(num_vertices - num_faces * 3 / 2 + num_faces, 2);
+    mesh
+}
+
+#[test]
+fn simplify_subdivided_cube() {
+    let (vertices, indices) = subdivided_cube(8);
+    let params = MeshSimplificationParameters {
+        max_error: 1.0e-3,
+        ..Default::default()
+    };
+    let (vertices, indices) = simplify_trimesh(&vertices, &indices, &params);
+
+    // The flat faces collapse to the 12 triangles of a cube, without moving its corners.
+    assert_eq!(indices.len(), 12);
+    assert_eq!(vertices.len(), 8);
+    for pt in &vertices {
+        assert_relative_eq!(pt.coords.abs(), na::Vector3::repeat(1.0), epsilon = 1.0e-4);
+    }
+    assert_relative_eq!(volume(&vertices, &indices), 8.0, epsilon = 1.0e-3);
+    let _ = assert_closed_sphere_topology(vertices, indices);
+}
+
+#[test]
+fn simplify_sphere_to_target_triangle_count() {
+    let (vertices, indices) = Ball::new(1.0).to_trimesh(40, 40);
+    let params = MeshSimplificationParameters {
+        target_triangle_count: 300,
+        ..Default::default()
+    };
+    let (simplified_vertices, simplified_indices) = simplify_trimesh(&vertices, &indices, &params);
+
+    assert!(simplified_indices.len() <= 300);
+    assert!(simplified_indices.len() >= 298);
+    assert_relative_eq!(
+        volume(&simplified_vertices, &simplified_indices),
+        volume(&vertices, &indices),
+        max_relative = 0.05
+    );
+    let _ = assert_closed_sphere_topology(simplified_vertices, simplified_indices);
+}
+
+#[test]
+fn simplify_sphere_with_max_error() {
+    let (vertices, indices) = Ball::new(1.0).to_trimesh(40, 40);
+    let mut num_triangles = indices.len();
+
+    for max_error in [1.0e-3, 1.0e-2, 1.0e-1] {
+        let params = MeshSimplificationParameters {
+            max_error,
+            ..Default::default()
+        };
+        let (_, simplified_indices) = simplify_trimesh(&vertices, &indices, &params);
+        assert!(simplified_indices.len() < num_triangles);
+        num_triangles = simplified_indices.len();
+    }
+}
+
+#[test]
+fn simplify_open_grid_preserves_boundary() {
+    // A single face of the subdivided cube, which is a flat open square.
+    let (vertices, indices) = subdivided_cube(8);
+    let indices: Vec<_> = indices
+        .into_iter()
+        .filter(|idx| idx.iter().all(|i| vertices[*i as usize].z == 1.0))
+        .collect();
+    let params = MeshSimplificationParameters {
+        max_error: 1.0e-3,
+        ..Default::default()
+    };
+    let (vertices, indices) = simplify_trimesh(&vertices, &indices, &params);
+
+    assert_eq!(indices.len(), 2);
+    let area: Real = indices
+        .iter()
+        .map(|idx| {
+            let [a, b, c] = idx.map(|i| vertices[i as usize]);
+            (b - a).cross(&(c - a)).norm() / 2.0
+        })
+        .sum();
+    assert_relative_eq!(area, 4.0, epsilon = 1.0e-4);
+}
+
+#[test]
+fn simplify_sphere_with_bounds() {
+    let (vertices, indices) = Ball::new(1.0).to_trimesh(30, 30);
+    let original = TriMesh::new(vertices.clone(), indices.clone()).unwrap();
+
+    for bound in [
+        MeshSimplificationBound::Inside,
+        MeshSimplificationBound::Outside,
+    ] {
+        let params = MeshSimplificationParameters {
+            target_triangle_count: 100,
+            bound,
+            ..Default::default()
+        };
+        let (simplified_vertices, simplified_indices) =
+            simplify_trimesh(&vertices, &indices, &params);
+        assert!(simplified_indices.len() < indices.len() / 4);
+        let simplified = assert_closed_sphere_topology(simplified_vertices, simplified_indices);
+
+        let (inner, outer) = if bound == MeshSimplificationBound::Inside {
+            (&simplified, &original)
+        } else {
+            (&original, &simplified)
+        };
+
+        for pt in inner.vertices() {
+            let dist = outer.distance_to_local_point(pt, false);
+            assert!(outer.winding_number(pt) > 0.5 || dist < 1.0e-4);
+        }
+    }
+}
@@ -1,12 +1,33 @@
 mod aabb_scale;
 mod ball_ball_toi;
 mod ball_triangle_toi;
+mod closest_feature_tracking;
+mod coacd;
+mod compound_contact;
 mod convex_hull;
+mod convex_hull_simplification;
+mod convex_intersection_volume;
 mod cuboid_ray_cast;
 mod cylinder_cuboid_contact;
+mod deforming_ccd;
 mod epa3;
+mod gjk_warm_start;
+mod hausdorff_distance;
+mod heightfield_contact_manifolds;
+mod incremental_convex_hull;
+mod mesh_boolean;
+mod mesh_repair;
+mod mesh_simplification;
+mod query_stats;
 mod still_objects_toi;
+mod submerged_volume;
 mod time_of_impact3;
 mod trimesh_connected_components;
 mod trimesh_intersection;
+mod trimesh_offset;
+mod trimesh_ray_packet;
+mod trimesh_triangle_pairs_intersection;
+mod trimesh_trimesh_penetration;
 mod trimesh_trimesh_toi;
+mod trimesh_winding_number;
+mod vhacd_progress;
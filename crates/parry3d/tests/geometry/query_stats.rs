@@ -0,0 +1,95 @@
+#![cfg(feature = "stats")]
+
+use na::{Isometry3, Vector3};
+use parry3d::query::{self, stats, ContactManifold, PersistentQueryDispatcher};
+use parry3d::shape::{Ball, Cuboid, ShapeType, TriMesh};
+
+#[test]
+fn stats_count_queries_and_gjk_iterations() {
+    let cuboid = Cuboid::new(Vector3::repeat(1.0));
+    let ball = Ball::new(0.5);
+    let pos12 = Isometry3::new(Vector3::new(3.0, 0.5, 0.2), Vector3::new(0.1, 0.2, 0.3));
+
+    let (_, stats) = stats::collect(|| {
+        for _ in 0..10 {
+            let _ = query::distance(&Isometry3::identity(), &cuboid, &pos12, &ball).unwrap();
+        }
+        let _ = query::contact(&Isometry3::identity(), &cuboid, &pos12, &cuboid, 0.0).unwrap();
+    });
+
+    assert_eq!(stats.distances, 10);
+    assert_eq!(stats.contacts, 1);
+    assert_eq!(stats.intersection_tests, 0);
+    assert!(stats.gjk_iterations.runs > 0);
+    assert!(stats.gjk_iterations.iterations > 0);
+
+    let buckets: usize = stats.gjk_iterations.buckets.iter().sum();
+    assert_eq!(buckets, stats.gjk_iterations.runs);
+}
+
+#[test]
+fn stats_count_bvh_visits_and_manifold_generators() {
+    let (vertices, indices) = Cuboid::new(Vector3::repeat(1.0)).to_trimesh();
+    let trimesh = TriMesh::new(vertices, indices).unwrap();
+    let ball = Ball::new(0.5);
+    let pos12 = Isometry3::translation(0.0, 1.2, 0.0);
+
+    let (_, stats) = stats::collect(|| {
+        let mut manifolds: Vec<ContactManifold<(), ()>> = vec![];
+        let mut workspace = None;
+        query::DefaultQueryDispatcher
+            .contact_manifolds(&pos12, &trimesh, &ball, 0.1, &mut manifolds, &mut workspace)
+            .unwrap();
+    });
+
+    assert_eq!(stats.contact_manifolds, 1);
+    assert_eq!(
+        stats.contact_manifold_generators(ShapeType::TriMesh, ShapeType::Ball),
+        1
+    );
+    assert!(stats.contact_manifold_generators(ShapeType::Triangle, ShapeType::Ball) > 0);
+    assert!(stats.total_contact_manifold_generators() > 1);
+    assert!(stats.bvh_nodes_visited > 0);
+    assert!(stats.bvh_leaves_visited > 0);
+}
+
+#[test]
+fn stats_are_scoped_to_the_current_thread() {
+    let ball = Ball::new(0.5);
+    let pos12 = Isometry3::translation(2.0, 0.0, 0.0);
+    let distance = || query::distance(&Isometry3::identity(), &ball, &pos12, &ball).unwrap();
+
+    stats::reset();
+    let (_, outer) = stats::collect(|| {
+        let _ = distance();
+        let (_, inner) = stats::collect(|| {
+            let _ = distance();
+            // Queries running on other threads are not recorded by this thread.
+            std::thread::scope(|s| {
+                let _ = s.spawn(|| {
+                    for _ in 0..5 {
+                        let _ = distance();
+                    }
+                });
+            });
+        });
+        assert_eq!(inner.distances, 1);
+    });
+
+    // The inner statistics are added to the outer ones.
+    assert_eq!(outer.distances, 2);
+    assert_eq!(stats::snapshot().distances, 2);
+
+    stats::reset();
+    assert_eq!(stats::snapshot(), stats::QueryStats::default());
+}
+
+#[test]
+fn stats_histogram_buckets() {
+    assert_eq!(stats::IterationHistogram::bucket(0), 0);
+    assert_eq!(stats::IterationHistogram::bucket(1), 1);
+    assert_eq!(stats::IterationHistogram::bucket(3), 2);
+    assert_eq!(stats::IterationHistogram::bucket(4), 3);
+    assert_eq!(stats::IterationHistogram::bucket(63), 6);
+    assert_eq!(stats::IterationHistogram::bucket(100), 7);
+}
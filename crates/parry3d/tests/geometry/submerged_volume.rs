@@ -0,0 +1,186 @@
+use core::f32::consts::PI;
+use na::{Isometry3, Point3, Vector3};
+use parry3d::math::Real;
+use parry3d::query::details::submerged_volume_convex_polyhedron;
+use parry3d::query::{self, SubmergedVolume};
+use parry3d::shape::{
+    Ball, Capsule, Compound, ConvexPolyhedron, Cuboid, Cylinder, HalfSpace, SharedShape, TriMesh,
+    TriMeshFlags,
+};
+
+fn assert_submerged_eq(actual: SubmergedVolume, expected: SubmergedVolume, epsilon: Real) {
+    assert_relative_eq!(actual.volume, expected.volume, max_relative = epsilon);
+    assert_relative_eq!(actual.centroid, expected.centroid, epsilon = epsilon * 10.0);
+    assert_relative_eq!(
+        actual.waterline_area,
+        expected.waterline_area,
+        max_relative = epsilon
+    );
+}
+
+fn tilted_water(depth: Real) -> Isometry3<Real> {
+    // The water surface is tilted and located at the given depth along its normal.
+    let rot = Isometry3::rotation(Vector3::new(0.4, -0.2, 0.3));
+    rot * Isometry3::translation(0.0, depth, 0.0)
+}
+
+#[test]
+fn submerged_ball() {
+    let ball = Ball::new(2.0);
+    let water = HalfSpace::new(Vector3::y_axis());
+
+    let half = query::submerged_volume(&Isometry3::identity(), &ball, &water).unwrap();
+    assert_relative_eq!(half.volume, 2.0 / 3.0 * PI * 8.0, epsilon = 1.0e-4);
+    assert_relative_eq!(
+        half.centroid,
+        Point3::new(0.0, -0.75, 0.0),
+        epsilon = 1.0e-4
+    );
+    assert_relative_eq!(half.waterline_area, PI * 4.0, epsilon = 1.0e-4);
+
+    let full =
+        query::submerged_volume(&Isometry3::translation(0.0, 3.0, 0.0), &ball, &water).unwrap();
+    assert_relative_eq!(full.volume, 4.0 / 3.0 * PI * 8.0, epsilon = 1.0e-3);
+    assert_relative_eq!(full.centroid, Point3::origin(), epsilon = 1.0e-4);
+    assert_eq!(full.waterline_area, 0.0);
+
+    let emerged =
+        query::submerged_volume(&Isometry3::translation(0.0, -3.0, 0.0), &ball, &water).unwrap();
+    assert_eq!(emerged, SubmergedVolume::zero());
+}
+
+#[test]
+fn submerged_cuboid_and_convex_polyhedron() {
+    let cuboid = Cuboid::new(Vector3::new(1.0, 0.5, 2.0));
+    let water = HalfSpace::new(Vector3::y_axis());
+
+    let submerged =
+        query::submerged_volume(&Isometry3::translation(0.0, 0.25, 0.0), &cuboid, &water).unwrap();
+    assert_relative_eq!(submerged.volume, 6.0, epsilon = 1.0e-4);
+    assert_relative_eq!(
+        submerged.centroid,
+        Point3::new(0.0, -0.125, 0.0),
+        epsilon = 1.0e-4
+    );
+    assert_relative_eq!(submerged.waterline_area, 8.0, epsilon = 1.0e-4);
+
+    let (vertices, indices) = cuboid.to_trimesh();
+    let poly = ConvexPolyhedron::from_convex_mesh(vertices.clone(), &indices).unwrap();
+    let mesh = TriMesh::with_flags(vertices, indices, TriMeshFlags::ORIENTED).unwrap();
+
+    for depth in [-0.3, 0.1, 0.7] {
+        let pos12 = tilted_water(depth);
+        let expected = query::submerged_volume(&pos12, &cuboid, &water).unwrap();
+        let from_poly = submerged_volume_convex_polyhedron(&pos12, &poly, &water);
+        let from_mesh = query::submerged_volume(&pos12, &mesh, &water).unwrap();
+        assert_submerged_eq(from_poly, expected, 1.0e-4);
+        assert_submerged_eq(from_mesh, expected, 1.0e-4);
+    }
+
+    // The triangle mesh must be oriented.
+    let (vertices, indices) = cuboid.to_trimesh();
+    let unoriented = TriMesh::new(vertices, indices).unwrap();
+    assert!(query::submerged_volume(&Isometry3::identity(), &unoriented, &water).is_err());
+}
+
+#[test]
+fn submerged_cylinder_and_capsule_match_their_tessellations() {
+    let water = HalfSpace::new(Vector3::y_axis());
+
+    let cylinder = Cylinder::new(1.5, 0.7);
+    let (vertices, indices) = cylinder.to_trimesh(256);
+    let cylinder_mesh = TriMesh::with_flags(vertices, indices, TriMeshFlags::ORIENTED).unwrap();
+
+    let capsule = Capsule::new(
+        Point3::new(0.2, -1.0, 0.1),
+        Point3::new(-0.3, 0.8, 0.4),
+        0.6,
+    );
+    let (vertices, indices) = capsule.to_trimesh(256, 128);
+    let capsule_mesh = TriMesh::with_flags(vertices, indices, TriMeshFlags::ORIENTED).unwrap();
+
+    for depth in [-1.0, -0.2, 0.0, 0.5, 1.2] {
+        let pos12 = tilted_water(depth);
+
+        let actual = query::submerged_volume(&pos12, &cylinder, &water).unwrap();
+        let expected = query::submerged_volume(&pos12, &cylinder_mesh, &water).unwrap();
+        assert_submerged_eq(actual, expected, 1.0e-3);
+
+        let actual = query::submerged_volume(&pos12, &capsule, &water).unwrap();
+        let expected = query::submerged_volume(&pos12, &capsule_mesh, &water).unwrap();
+        assert_submerged_eq(actual, expected, 1.0e-3);
+    }
+
+    // Water surface perpendicular to the cylinder’s axis.
+    let submerged =
+        query::submerged_volume(&Isometry3::translation(0.0, 0.5, 0.0), &cylinder, &water).unwrap();
+    assert_relative_eq!(submerged.volume, PI * 0.49 * 2.0, epsilon = 1.0e-4);
+    assert_relative_eq!(
+        submerged.centroid,
+        Point3::new(0.0, -0.5, 0.0),
+        epsilon = 1.0e-4
+    );
+    assert_relative_eq!(submerged.waterline_area, PI * 0.49, epsilon = 1.0e-4);
+}
+
+#[test]
+fn submerged_capsule_is_exact() {
+    let water = HalfSpace::new(Vector3::y_axis());
+    let capsule = Capsule::new_y(1.0, 0.5);
+    let total = PI * 0.25 * 2.0 + 4.0 / 3.0 * PI * 0.125;
+
+    // Any water surface through the center splits the capsule in two symmetric halves.
+    for k in 0..16 {
+        let angle = k as Real * PI / 16.0;
+        let rot = Isometry3::rotation(Vector3::new(angle, 0.3 * angle, 0.0));
+        let submerged = query::submerged_volume(&rot, &capsule, &water).unwrap();
+        let emerged = query::submerged_volume(
+            &(rot * Isometry3::rotation(Vector3::x() * PI)),
+            &capsule,
+            &water,
+        )
+        .unwrap();
+
+        assert_relative_eq!(submerged.volume, total / 2.0, max_relative = 1.0e-5);
+        assert_relative_eq!(emerged.volume, total / 2.0, max_relative = 1.0e-5);
+        assert_relative_eq!(submerged.centroid, -emerged.centroid, epsilon = 1.0e-5);
+        assert_relative_eq!(
+            submerged.waterline_area,
+            emerged.waterline_area,
+            max_relative = 1.0e-5
+        );
+    }
+
+    // A water surface crossing only the top hemisphere leaves a spherical cap above it.
+    let pos12 = Isometry3::translation(0.0, 1.25, 0.0);
+    let submerged = query::submerged_volume(&pos12, &capsule, &water).unwrap();
+    let cap = PI * 0.0625 * (1.5 - 0.25) / 3.0;
+    assert_relative_eq!(submerged.volume, total - cap, max_relative = 1.0e-5);
+    assert_relative_eq!(submerged.waterline_area, PI * 0.1875, max_relative = 1.0e-5);
+}
+
+#[test]
+fn submerged_compound() {
+    let cuboid = Cuboid::new(Vector3::repeat(0.5));
+    let compound = Compound::new(vec![
+        (
+            Isometry3::translation(-1.0, 0.0, 0.0),
+            SharedShape::new(cuboid),
+        ),
+        (
+            Isometry3::translation(1.0, 1.0, 0.0),
+            SharedShape::new(cuboid),
+        ),
+    ]);
+    let water = HalfSpace::new(Vector3::y_axis());
+
+    let submerged =
+        query::submerged_volume(&Isometry3::translation(0.0, 0.75, 0.0), &compound, &water)
+            .unwrap();
+    assert_relative_eq!(submerged.volume, 1.25, epsilon = 1.0e-4);
+    // The centroid is the volume-weighted average of the submerged parts’ centroids.
+    let expected =
+        (Point3::new(-1.0, 0.0, 0.0) * 1.0 + Vector3::new(1.0, 0.625, 0.0) * 0.25) / 1.25;
+    assert_relative_eq!(submerged.centroid, expected, epsilon = 1.0e-4);
+    assert_relative_eq!(submerged.waterline_area, 1.0, epsilon = 1.0e-4);
+}
@@ -0,0 +1,86 @@
+use na::{Point3, Vector3};
+use parry3d::math::Real;
+use parry3d::query::PointQuery;
+use parry3d::shape::{Cuboid, TriMesh, TriMeshFlags};
+use parry3d::transformation::{offset_trimesh, solidify_trimesh};
+
+fn volume(mesh: &TriMesh) -> Real {
+    mesh.triangles()
+        .map(|tri| tri.a.coords.dot(&tri.b.coords.cross(&tri.c.coords)) / 6.0)
+        .sum()
+}
+
+fn assert_closed(mesh: &TriMesh) {
+    let mesh = TriMesh::with_flags(
+        mesh.vertices().to_vec(),
+        mesh.indices().to_vec(),
+        TriMeshFlags::HALF_EDGE_TOPOLOGY,
+    )
+    .unwrap();
+    assert!(mesh
+        .topology()
+        .unwrap()
+        .half_edges
+        .iter()
+        .all(|half_edge| half_edge.twin != u32::MAX));
+}
+
+#[test]
+fn offset_cube() {
+    let (vertices, indices) = Cuboid::new(Vector3::repeat(1.0)).to_trimesh();
+    let cube = TriMesh::new(vertices, indices).unwrap();
+
+    // Growing rounds the edges and corners.
+    let grown = offset_trimesh(&cube, 0.2, 20).unwrap();
+    let expected = 8.0
+        + 6.0 * 4.0 * 0.2
+        + 12.0 * 2.0 * core::f32::consts::PI * 0.04 / 4.0
+        + 4.0 / 3.0 * core::f32::consts::PI * 0.008;
+    assert_relative_eq!(volume(&grown), expected, epsilon = 0.1);
+    assert_closed(&grown);
+
+    for pt in grown.vertices() {
+        assert_relative_eq!(
+            cube.distance_to_local_point(pt, true),
+            0.2,
+            epsilon = 1.0e-3
+        );
+    }
+
+    // Shrinking keeps the edges sharp.
+    let shrunk = offset_trimesh(&cube, -0.2, 20).unwrap();
+    assert_relative_eq!(volume(&shrunk), 1.6 * 1.6 * 1.6, epsilon = 0.1);
+    assert_closed(&shrunk);
+
+    // Shrinking by more than half the width removes the cube.
+    assert!(offset_trimesh(&cube, -1.1, 16).is_none());
+}
+
+#[test]
+fn solidify_open_grid() {
+    // A 2x2 square in the plane z = 0, made of 2x2 cells, with normals pointing toward +z.
+    let vertices: Vec<_> = (0..9)
+        .map(|i| Point3::new((i % 3) as Real, (i / 3) as Real, 0.0))
+        .collect();
+    let mut indices = vec![];
+    for j in 0..2 {
+        for i in 0..2 {
+            let id = i + j * 3;
+            indices.push([id, id + 1, id + 4]);
+            indices.push([id, id + 4, id + 3]);
+        }
+    }
+    let grid = TriMesh::new(vertices, indices).unwrap();
+
+    let shell = solidify_trimesh(&grid, 0.1);
+    assert_eq!(shell.vertices().len(), 18);
+    assert_eq!(shell.indices().len(), 8 * 2 + 8 * 2);
+    assert_relative_eq!(volume(&shell), 0.4, epsilon = 1.0e-5);
+    assert_closed(&shell);
+    assert_relative_eq!(shell.local_aabb().mins.z, -0.1);
+
+    // A negative thickness extrudes along the normals.
+    let shell = solidify_trimesh(&grid, -0.1);
+    assert_relative_eq!(volume(&shell), 0.4, epsilon = 1.0e-5);
+    assert_relative_eq!(shell.local_aabb().maxs.z, 0.1);
+}
@@ -0,0 +1,53 @@
+use parry3d::math::{Isometry, Point, Real, Vector};
+use parry3d::query::{Ray, RayCast};
+use parry3d::shape::{Ball, TriMesh};
+
+#[test]
+fn trimesh_ray_packets_match_individual_ray_casts() {
+    let (vertices, indices) = Ball::new(1.0).to_trimesh(20, 20);
+    let mesh = TriMesh::new(vertices, indices).unwrap();
+    let mut rng = oorandom::Rand32::new(42);
+
+    // Use a ray count that isn’t a multiple of the packet width.
+    let rays: Vec<_> = (0..1001)
+        .map(|_| {
+            let origin = Point::new(
+                rng.rand_float() * 4.0 - 2.0,
+                rng.rand_float() * 4.0 - 2.0,
+                -3.0,
+            );
+            let target = Point::new(
+                rng.rand_float() * 3.0 - 1.5,
+                rng.rand_float() * 3.0 - 1.5,
+                rng.rand_float() * 2.0 - 1.0,
+            );
+            Ray::new(origin, target - origin)
+        })
+        .collect();
+
+    for max_toi in [0.5, 1.0, Real::MAX] {
+        let hits = mesh.cast_local_rays(&rays, max_toi);
+        assert_eq!(hits.len(), rays.len());
+
+        for (ray, hit) in rays.iter().zip(hits.iter()) {
+            let expected = mesh.cast_local_ray(ray, max_toi, true);
+            assert_eq!(expected, hit.map(|hit| hit.1));
+
+            if let Some((tri, toi)) = hit {
+                let expected_tri = mesh.triangle(*tri).cast_local_ray(ray, max_toi, true);
+                assert_eq!(expected_tri, Some(*toi));
+            }
+        }
+    }
+
+    // The world-space version must agree with the local-space one.
+    let pos = Isometry::new(Vector::new(1.0, 2.0, 3.0), Vector::new(0.1, 0.2, 0.3));
+    let world_rays: Vec<_> = rays.iter().map(|ray| ray.transform_by(&pos)).collect();
+    let world_hits = mesh.cast_rays(&pos, &world_rays, Real::MAX);
+    for (ray, hit) in world_rays.iter().zip(world_hits.iter()) {
+        assert_eq!(
+            mesh.cast_ray(&pos, ray, Real::MAX, true),
+            hit.map(|hit| hit.1)
+        );
+    }
+}
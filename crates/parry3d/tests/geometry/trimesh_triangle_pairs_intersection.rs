@@ -0,0 +1,94 @@
+use na::{Isometry3, Vector3};
+use parry3d::math::{Isometry, Real};
+use parry3d::query::PointQuery;
+use parry3d::shape::{Cuboid, Segment, TriMesh, Triangle};
+use parry3d::transformation::{
+    intersecting_triangle_pairs, self_intersecting_triangle_pairs, MeshIntersectionTolerances,
+};
+
+fn cuboid_mesh(half_extents: Vector3<Real>, pos: &Isometry<Real>) -> TriMesh {
+    let (vertices, indices) = Cuboid::new(half_extents).to_trimesh();
+    let vertices = vertices.into_iter().map(|pt| pos * pt).collect();
+    TriMesh::new(vertices, indices).unwrap()
+}
+
+fn assert_segment_on_triangle(segment: &Segment, triangle: &Triangle) {
+    for pt in [segment.a, segment.b] {
+        let dist = triangle.distance_to_local_point(&pt, true);
+        assert!(dist < 1.0e-4, "intersection point too far: {dist}");
+    }
+}
+
+#[test]
+fn two_meshes_triangle_pairs_intersection() {
+    let mesh1 = cuboid_mesh(Vector3::new(1.0, 1.0, 1.0), &Isometry::identity());
+    let mesh2 = cuboid_mesh(Vector3::new(0.5, 0.7, 0.6), &Isometry::identity());
+    let pos1 = Isometry3::new(Vector3::new(0.1, 2.0, -0.5), Vector3::new(0.1, 0.2, 0.3));
+    let pos2 = pos1 * Isometry3::new(Vector3::new(0.8, 0.3, 0.1), Vector3::new(0.4, -0.2, 0.5));
+    let pos12 = pos1.inv_mul(&pos2);
+
+    let pairs = intersecting_triangle_pairs(
+        &pos1,
+        &mesh1,
+        &pos2,
+        &mesh2,
+        MeshIntersectionTolerances::default(),
+    );
+    assert!(!pairs.is_empty());
+
+    for (fid1, fid2, segment) in &pairs {
+        let tri1 = mesh1.triangle(*fid1);
+        let tri2 = mesh2.triangle(*fid2).transformed(&pos12);
+        assert_segment_on_triangle(segment, &tri1);
+        assert_segment_on_triangle(segment, &tri2);
+    }
+
+    // Disjoint meshes don’t have any intersecting triangles.
+    let pos2 = pos1 * Isometry3::translation(3.0, 0.0, 0.0);
+    let pairs = intersecting_triangle_pairs(
+        &pos1,
+        &mesh1,
+        &pos2,
+        &mesh2,
+        MeshIntersectionTolerances::default(),
+    );
+    assert!(pairs.is_empty());
+}
+
+#[test]
+fn single_mesh_self_intersecting_triangle_pairs() {
+    // A closed mesh has no self-intersection, even though adjacent triangles touch each other.
+    let mesh = cuboid_mesh(Vector3::new(1.0, 1.0, 1.0), &Isometry::identity());
+    assert!(
+        self_intersecting_triangle_pairs(&mesh, MeshIntersectionTolerances::default()).is_empty()
+    );
+
+    // Merge two overlapping cuboids into a single mesh.
+    let part2 = cuboid_mesh(
+        Vector3::new(0.5, 0.7, 0.6),
+        &Isometry3::new(Vector3::new(0.8, 0.3, 0.1), Vector3::new(0.4, -0.2, 0.5)),
+    );
+    let num_triangles1 = mesh.indices().len() as u32;
+    let mut merged = mesh.clone();
+    merged.append(&part2);
+
+    let pairs = self_intersecting_triangle_pairs(&merged, MeshIntersectionTolerances::default());
+    assert!(!pairs.is_empty());
+
+    for (fid1, fid2, segment) in &pairs {
+        // Only triangles from different cuboids intersect.
+        assert!(*fid1 < num_triangles1 && *fid2 >= num_triangles1);
+        assert_segment_on_triangle(segment, &merged.triangle(*fid1));
+        assert_segment_on_triangle(segment, &merged.triangle(*fid2));
+    }
+
+    // The result matches the intersections between two separate meshes.
+    let separate_pairs = intersecting_triangle_pairs(
+        &Isometry::identity(),
+        &mesh,
+        &Isometry::identity(),
+        &part2,
+        MeshIntersectionTolerances::default(),
+    );
+    assert_eq!(pairs.len(), separate_pairs.len());
+}
@@ -0,0 +1,82 @@
+use na::{Isometry3, Vector3};
+use parry3d::math::Real;
+use parry3d::query::details::{contact_trimesh_trimesh, penetration_trimesh_trimesh};
+use parry3d::query::{self, DefaultQueryDispatcher, QueryDispatcher};
+use parry3d::shape::{Cuboid, TriMesh, TriMeshFlags};
+
+fn cuboid_mesh(half_extent: Real, flags: TriMeshFlags) -> TriMesh {
+    let (vertices, indices) = Cuboid::new(Vector3::repeat(half_extent)).to_trimesh();
+    TriMesh::with_flags(vertices, indices, flags).unwrap()
+}
+
+#[test]
+fn penetration_between_overlapping_solid_cuboids() {
+    let mesh1 = cuboid_mesh(1.0, TriMeshFlags::ORIENTED);
+    let mesh2 = cuboid_mesh(1.0, TriMeshFlags::ORIENTED);
+    let pos12 = Isometry3::translation(1.5, 0.2, 0.1);
+
+    let penetration = penetration_trimesh_trimesh(&pos12, &mesh1, &mesh2).unwrap();
+    assert!((penetration.depth - 0.5).abs() < 1.0e-4);
+    assert!((*penetration.normal1 - Vector3::x()).norm() < 1.0e-4);
+    assert!((*penetration.normal2 + Vector3::x()).norm() < 1.0e-4);
+    assert!(!penetration.contacts.is_empty());
+
+    let deepest = penetration.deepest_contact().unwrap();
+    assert!((deepest.dist + 0.5).abs() < 1.0e-4);
+
+    // The contact query on solid `TriMesh`es reports the same global penetration.
+    let contact = DefaultQueryDispatcher
+        .contact(&pos12, &mesh1, &mesh2, 0.0)
+        .unwrap()
+        .unwrap();
+    assert!((contact.dist + 0.5).abs() < 1.0e-4);
+    assert!((*contact.normal1 - Vector3::x()).norm() < 1.0e-4);
+}
+
+#[test]
+fn penetration_of_deeply_nested_solid_cuboids() {
+    // The small cuboid is fully inside of the large one: no triangles intersect, but the
+    // meshes still penetrate.
+    let mesh1 = cuboid_mesh(2.0, TriMeshFlags::ORIENTED);
+    let mesh2 = cuboid_mesh(0.5, TriMeshFlags::ORIENTED);
+    let pos12 = Isometry3::translation(0.0, 1.0, 0.0);
+
+    let contact =
+        contact_trimesh_trimesh(&DefaultQueryDispatcher, &pos12, &mesh1, &mesh2, 0.0).unwrap();
+    assert!((contact.dist + 1.5).abs() < 1.0e-4);
+    assert!((*contact.normal1 - Vector3::y()).norm() < 1.0e-4);
+
+    // The default contact query treats solid meshes as volumes too.
+    let contact = query::contact(&Isometry3::identity(), &mesh1, &pos12, &mesh2, 0.0)
+        .unwrap()
+        .unwrap();
+    assert!((contact.dist + 1.5).abs() < 1.0e-4);
+    assert!((*contact.normal1 - Vector3::y()).norm() < 1.0e-4);
+
+    // Non-solid meshes keep the contact between their triangles, which are separated.
+    let hollow1 = cuboid_mesh(2.0, TriMeshFlags::empty());
+    let contact = query::contact(&Isometry3::identity(), &hollow1, &pos12, &mesh2, 0.0).unwrap();
+    assert!(contact.is_none());
+
+    // Separated meshes fall back to the contact between triangles, within the prediction.
+    let pos12 = Isometry3::translation(0.0, 3.0, 0.0);
+    let contact =
+        contact_trimesh_trimesh(&DefaultQueryDispatcher, &pos12, &mesh1, &mesh2, 1.0).unwrap();
+    assert!((contact.dist - 0.5).abs() < 1.0e-4);
+    assert!(
+        contact_trimesh_trimesh(&DefaultQueryDispatcher, &pos12, &mesh1, &mesh2, 0.1).is_none()
+    );
+}
+
+#[test]
+fn penetration_requires_solid_meshes() {
+    let mesh1 = cuboid_mesh(1.0, TriMeshFlags::empty());
+    let mesh2 = cuboid_mesh(1.0, TriMeshFlags::ORIENTED);
+    let pos12 = Isometry3::translation(1.5, 0.0, 0.0);
+
+    assert!(penetration_trimesh_trimesh(&pos12, &mesh1, &mesh2).is_none());
+    assert!(
+        penetration_trimesh_trimesh(&Isometry3::translation(3.0, 0.0, 0.0), &mesh2, &mesh2)
+            .is_none()
+    );
+}
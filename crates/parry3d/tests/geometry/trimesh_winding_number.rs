@@ -0,0 +1,75 @@
+use na::{Point3, Vector3};
+use parry3d::math::Real;
+use parry3d::query::PointQuery;
+use parry3d::shape::{Ball, Cuboid, TriMesh, TriMeshFlags};
+
+fn test_points() -> impl Iterator<Item = Point3<Real>> {
+    (0..10).flat_map(|i| {
+        (0..10).flat_map(move |j| {
+            (0..10).map(move |k| {
+                Point3::new(i as Real, j as Real, k as Real) * 0.35 - Vector3::repeat(1.6)
+            })
+        })
+    })
+}
+
+#[test]
+fn trimesh_winding_number_approximation_matches_exact_value() {
+    let (vertices, indices) = Ball::new(1.0).to_trimesh(20, 20);
+    let exact = TriMesh::new(vertices.clone(), indices.clone()).unwrap();
+    let fast = TriMesh::with_flags(vertices, indices, TriMeshFlags::WINDING_NUMBERS).unwrap();
+
+    for pt in test_points() {
+        let exact_value = exact.winding_number(&pt);
+        let fast_value = fast.winding_number(&pt);
+        assert!((exact_value - fast_value).abs() < 2.0e-2);
+
+        // Avoid points too close to the (tessellated) sphere.
+        let dist = pt.coords.norm();
+        if dist < 0.9 {
+            assert!((fast_value - 1.0).abs() < 2.0e-2);
+            assert!(fast.contains_local_point(&pt));
+            assert!(fast.project_local_point(&pt, false).is_inside);
+        } else if dist > 1.1 {
+            assert!(fast_value.abs() < 2.0e-2);
+            assert!(!fast.contains_local_point(&pt));
+            assert!(!fast.project_local_point(&pt, false).is_inside);
+        }
+    }
+}
+
+#[test]
+fn trimesh_winding_number_is_robust_to_holes() {
+    let (vertices, mut indices) = Cuboid::new(Vector3::repeat(1.0)).to_trimesh();
+    // Remove one of the two triangles of a face.
+    indices.pop();
+    let mesh = TriMesh::with_flags(vertices, indices, TriMeshFlags::WINDING_NUMBERS).unwrap();
+
+    assert!(mesh.contains_local_point(&Point3::new(0.1, 0.2, 0.3)));
+    assert!(mesh.contains_local_point(&Point3::new(-0.5, -0.5, -0.5)));
+    assert!(!mesh.contains_local_point(&Point3::new(3.0, 0.0, 0.0)));
+    assert!(!mesh.contains_local_point(&Point3::new(0.0, -3.0, 0.0)));
+
+    // The winding number is fractional, but still meaningful, near the hole.
+    let center = mesh.winding_number(&Point3::origin());
+    assert!(center > 0.5 && center < 1.0);
+}
+
+#[test]
+fn trimesh_winding_number_follows_mesh_updates() {
+    let (vertices, indices) = Cuboid::new(Vector3::repeat(1.0)).to_trimesh();
+    let mut mesh = TriMesh::with_flags(vertices, indices, TriMeshFlags::WINDING_NUMBERS).unwrap();
+    let pt = Point3::new(1.5, 0.0, 0.0);
+    assert!(!mesh.contains_local_point(&pt));
+
+    mesh = mesh.scaled(&Vector3::new(2.0, 1.0, 1.0));
+    assert!((mesh.winding_number(&pt) - 1.0).abs() < 1.0e-3);
+    assert!(mesh.contains_local_point(&pt));
+
+    mesh.reverse();
+    assert!((mesh.winding_number(&pt) + 1.0).abs() < 1.0e-3);
+    assert!(!mesh.contains_local_point(&pt));
+
+    mesh.set_flags(TriMeshFlags::empty()).unwrap();
+    assert!((mesh.winding_number(&pt) + 1.0).abs() < 1.0e-3);
+}
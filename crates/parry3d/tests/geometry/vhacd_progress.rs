@@ -0,0 +1,121 @@
+use core::ops::ControlFlow;
+use na::Point3;
+use parry3d::math::Real;
+use parry3d::transformation::vhacd::{VHACDParameters, VHACDProgress, VHACDStage, VHACD};
+
+/// An L-shaped prism.
+fn l_shape() -> (Vec<Point3<Real>>, Vec<[u32; 3]>) {
+    let polygon = [
+        [0.0, 0.0],
+        [2.0, 0.0],
+        [2.0, 1.0],
+        [1.0, 1.0],
+        [1.0, 2.0],
+        [0.0, 2.0],
+    ];
+    let n = polygon.len() as u32;
+    let mut vertices: Vec<_> = polygon
+        .iter()
+        .map(|p| Point3::new(p[0], p[1], 0.0))
+        .collect();
+    vertices.extend(polygon.iter().map(|p| Point3::new(p[0], p[1], 1.0)));
+
+    let mut indices = vec![];
+    for i in 1..n - 1 {
+        indices.push([0, i + 1, i]);
+        indices.push([n, n + i, n + i + 1]);
+    }
+    for i in 0..n {
+        let j = (i + 1) % n;
+        indices.push([i, j, n + j]);
+        indices.push([i, n + j, n + i]);
+    }
+
+    (vertices, indices)
+}
+
+#[test]
+fn vhacd_reports_progress() {
+    let (vertices, indices) = l_shape();
+    let params = VHACDParameters {
+        resolution: 32,
+        max_convex_hulls: 4,
+        ..Default::default()
+    };
+
+    let mut reports: Vec<VHACDProgress> = vec![];
+    let decomposition =
+        VHACD::decompose_with_progress(&params, &vertices, &indices, true, |progress| {
+            reports.push(*progress);
+            ControlFlow::Continue(())
+        })
+        .unwrap();
+
+    assert_eq!(reports[0].stage, VHACDStage::Voxelization);
+    assert!(reports.len() > 2);
+    assert!(reports[1..]
+        .iter()
+        .all(|report| report.stage == VHACDStage::Decomposition
+            && report.iteration < report.max_iterations
+            && report.processed_parts <= report.iteration_parts));
+    assert!(reports.windows(2).all(|w| w[0].iteration <= w[1].iteration));
+
+    let last = reports.last().unwrap();
+    assert_eq!(last.processed_parts, last.iteration_parts);
+    assert_eq!(last.num_parts, decomposition.voxel_parts().len());
+
+    // Reporting the progress doesn’t affect the result.
+    let reference = VHACD::decompose(&params, &vertices, &indices, true);
+    assert_eq!(
+        reference.compute_exact_convex_hulls(&vertices, &indices),
+        decomposition.compute_exact_convex_hulls(&vertices, &indices)
+    );
+}
+
+#[test]
+fn vhacd_cancellation() {
+    let (vertices, indices) = l_shape();
+    let params = VHACDParameters {
+        resolution: 32,
+        ..Default::default()
+    };
+
+    let mut num_reports = 0;
+    let decomposition =
+        VHACD::decompose_with_progress(&params, &vertices, &indices, true, |progress| {
+            num_reports += 1;
+            if progress.stage == VHACDStage::Decomposition {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        });
+
+    assert!(decomposition.is_none());
+    assert_eq!(num_reports, 2);
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn vhacd_is_independent_from_the_number_of_threads() {
+    let (vertices, indices) = l_shape();
+    let params = VHACDParameters {
+        resolution: 32,
+        max_convex_hulls: 8,
+        ..Default::default()
+    };
+    let decompose = |num_threads| {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .build()
+            .unwrap()
+            .install(|| {
+                VHACD::decompose(&params, &vertices, &indices, true)
+                    .compute_exact_convex_hulls(&vertices, &indices)
+            })
+    };
+
+    let reference = decompose(1);
+    assert!(reference.len() > 1);
+    assert_eq!(decompose(4), reference);
+}
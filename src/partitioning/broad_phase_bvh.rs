@@ -0,0 +1,237 @@
+use crate::bounding_volume::{Aabb, BoundingVolume};
+use crate::math::Real;
+use crate::partitioning::{Bvh, BvhWorkspace};
+use crate::utils::hashmap::HashMap;
+use crate::utils::SortedPair;
+use alloc::vec::Vec;
+
+/// An event emitted by the [`BroadPhaseBvh`] when a pair of proxies starts or stops overlapping.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum BroadPhasePairEvent {
+    /// The loosened AABBs of the two proxies started overlapping.
+    AddPair(SortedPair<u32>),
+    /// The loosened AABBs of the two proxies stopped overlapping, or at least one of them
+    /// was removed.
+    DeletePair(SortedPair<u32>),
+}
+
+/// A broad-phase that reports incrementally the pairs of proxies with overlapping AABBs.
+///
+/// Each proxy is identified by a `u32` index and stored as a leaf of a [`Bvh`]. Its AABB is
+/// loosened by a margin so that small motions don’t require any change to the tree. At each
+/// [`BroadPhaseBvh::update`], only the pairs involving at least one proxy that moved outside of
+/// its loosened AABB are checked, and the pairs that started or stopped overlapping since the
+/// last update are reported as [`BroadPhasePairEvent`]s.
+///
+/// If the `enhanced-determinism` feature is enabled, the events are always reported in the same
+/// order for the same sequence of operations.
+#[derive(Clone, Default)]
+pub struct BroadPhaseBvh {
+    tree: Bvh,
+    workspace: BvhWorkspace,
+    // Maps each overlapping pair to the index of the last update it was reported by the
+    // tree traversal.
+    pairs: HashMap<SortedPair<u32>, u32>,
+    frame_index: u32,
+    margin: Real,
+    optimize_incrementally: bool,
+}
+
+impl BroadPhaseBvh {
+    /// Creates an empty broad-phase.
+    ///
+    /// The AABB of each proxy is loosened by `margin`. A larger margin reduces the number of
+    /// tree updates when proxies move, at the cost of reporting pairs that are further apart. If
+    /// `margin` is zero, every pair is re-checked at each update.
+    ///
+    /// If `optimize_incrementally` is `true`, [`Bvh::optimize_incremental`] is run at each
+    /// update to keep the tree well-shaped while proxies move.
+    pub fn new(margin: Real, optimize_incrementally: bool) -> Self {
+        Self {
+            margin,
+            optimize_incrementally,
+            ..Default::default()
+        }
+    }
+
+    /// The BVH containing the loosened AABBs of all the proxies.
+    pub fn tree(&self) -> &Bvh {
+        &self.tree
+    }
+
+    /// The margin the proxy AABBs are loosened by.
+    pub fn margin(&self) -> Real {
+        self.margin
+    }
+
+    /// Iterates through all the pairs of proxies that were overlapping at the last update.
+    pub fn pairs(&self) -> impl Iterator<Item = SortedPair<u32>> + '_ {
+        self.pairs.keys().copied()
+    }
+
+    /// Returns `true` if the given pair of proxies was overlapping at the last update.
+    pub fn contains_pair(&self, proxy1: u32, proxy2: u32) -> bool {
+        self.pairs.contains_key(&SortedPair::new(proxy1, proxy2))
+    }
+
+    /// Inserts a new proxy, or updates the AABB of an existing one.
+    ///
+    /// The change is taken into account at the next [`BroadPhaseBvh::update`].
+    pub fn insert_or_update(&mut self, proxy: u32, aabb: Aabb) {
+        if self.tree.leaf_node(proxy).is_none() {
+            // New leaves aren’t loosened by `insert_or_update_partially`.
+            self.tree.insert(aabb.loosened(self.margin), proxy);
+        } else {
+            self.tree
+                .insert_or_update_partially(aabb, proxy, self.margin);
+        }
+    }
+
+    /// Removes a proxy.
+    ///
+    /// All the pairs involving this proxy are reported as deleted at the next
+    /// [`BroadPhaseBvh::update`].
+    pub fn remove(&mut self, proxy: u32) {
+        self.tree.remove(proxy);
+    }
+
+    /// Updates the tree and the overlapping pairs to reflect the proxy insertions, updates, and
+    /// removals since the last update.
+    ///
+    /// The pairs that started or stopped overlapping are pushed to `events`. All the
+    /// [`BroadPhasePairEvent::DeletePair`] are pushed after all the
+    /// [`BroadPhasePairEvent::AddPair`].
+    pub fn update(&mut self, events: &mut Vec<BroadPhasePairEvent>) {
+        self.frame_index = self.frame_index.wrapping_add(1);
+
+        if self.optimize_incrementally {
+            self.tree.optimize_incremental(&mut self.workspace);
+        }
+
+        // NOTE: the refit propagates the change flags to the ancestors of moved leaves.
+        self.tree.refit(&mut self.workspace);
+
+        let frame_index = self.frame_index;
+        let pairs = &mut self.pairs;
+        let mut report_pair = |leaf1: u32, leaf2: u32| {
+            let pair = SortedPair::new(leaf1, leaf2);
+            if pairs.insert(pair, frame_index).is_none() {
+                events.push(BroadPhasePairEvent::AddPair(pair));
+            }
+        };
+
+        // Without margin, leaves are never flagged as changed so we need a full traversal.
+        let change_detection = self.margin > 0.0;
+        if change_detection {
+            self.tree
+                .traverse_bvtt_single_tree::<true>(&mut self.workspace, &mut report_pair);
+        } else {
+            self.tree
+                .traverse_bvtt_single_tree::<false>(&mut self.workspace, &mut report_pair);
+        }
+
+        // Every overlapping pair involving a changed leaf was reported by the traversal.
+        // Pairs involving a changed or removed leaf that weren’t reported are no longer
+        // overlapping.
+        let tree = &self.tree;
+        let is_changed = |proxy: u32| {
+            !change_detection || tree.leaf_node(proxy).map(|leaf| leaf.is_changed()) != Some(false)
+        };
+        self.pairs.retain(|pair, last_frame| {
+            let keep = *last_frame == frame_index || (!is_changed(pair.0) && !is_changed(pair.1));
+            if !keep {
+                events.push(BroadPhasePairEvent::DeletePair(*pair));
+            }
+            keep
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{BroadPhaseBvh, BroadPhasePairEvent};
+    use crate::bounding_volume::{Aabb, BoundingVolume};
+    use crate::math::{Point, Real, Vector};
+    use crate::utils::SortedPair;
+    use alloc::collections::BTreeSet;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn broad_phase_pairs_match_brute_force() {
+        const NUM_PROXIES: u32 = 100;
+        let margin = 0.1;
+
+        for optimize in [false, true] {
+            let mut broad_phase = BroadPhaseBvh::new(margin, optimize);
+            let mut rng = oorandom::Rand32::new(0);
+            let mut centers: Vec<_> = (0..NUM_PROXIES)
+                .map(|_| {
+                    Point::from(
+                        Vector::repeat(0.0).map(|_| rng.rand_range(0..2000) as Real / 100.0),
+                    )
+                })
+                .collect();
+            let mut alive = [true; NUM_PROXIES as usize];
+            let mut pairs = BTreeSet::new();
+            let mut events = Vec::new();
+
+            for step in 0..30 {
+                for (i, center) in centers.iter_mut().enumerate() {
+                    *center +=
+                        Vector::repeat(0.0).map(|_| rng.rand_range(0..100) as Real / 100.0 - 0.5);
+
+                    // Remove and re-insert some proxies regularly.
+                    if (i + step) % 37 == 0 {
+                        alive[i] = !alive[i];
+                        if !alive[i] {
+                            broad_phase.remove(i as u32);
+                        }
+                    }
+
+                    if alive[i] {
+                        let aabb = Aabb::from_half_extents(*center, Vector::repeat(0.5));
+                        broad_phase.insert_or_update(i as u32, aabb);
+                    }
+                }
+
+                events.clear();
+                broad_phase.update(&mut events);
+
+                for event in &events {
+                    match event {
+                        BroadPhasePairEvent::AddPair(pair) => assert!(pairs.insert(*pair)),
+                        BroadPhasePairEvent::DeletePair(pair) => assert!(pairs.remove(pair)),
+                    }
+                }
+
+                // The reported pairs are exactly the pairs of overlapping loosened AABBs stored
+                // in the tree, as found by brute force.
+                let tree = broad_phase.tree();
+                for i in 0..NUM_PROXIES {
+                    let Some(leaf_i) = tree.leaf_node(i).map(|leaf| leaf.aabb()) else {
+                        assert!(!alive[i as usize]);
+                        continue;
+                    };
+
+                    // The stored AABB contains the actual one, and doesn’t exceed it by more
+                    // than twice the margin since it is only recomputed when the proxy moves
+                    // out of it.
+                    let aabb_i = Aabb::from_half_extents(centers[i as usize], Vector::repeat(0.5));
+                    assert!(alive[i as usize]);
+                    assert!(leaf_i.contains(&aabb_i));
+                    assert!(aabb_i.loosened(2.0 * margin + 1.0e-5).contains(&leaf_i));
+
+                    for j in i + 1..NUM_PROXIES {
+                        let pair = SortedPair::new(i, j);
+                        let expected = tree
+                            .leaf_node(j)
+                            .is_some_and(|leaf_j| leaf_i.intersects(&leaf_j.aabb()));
+
+                        assert_eq!(pairs.contains(&pair), expected);
+                        assert_eq!(pairs.contains(&pair), broad_phase.contains_pair(i, j));
+                    }
+                }
+            }
+        }
+    }
+}
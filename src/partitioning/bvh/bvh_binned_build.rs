@@ -3,6 +3,18 @@ use super::{Bvh, BvhNode, BvhWorkspace};
 use crate::bounding_volume::{Aabb, BoundingVolume};
 use crate::math::Real;
 
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+// PERF: calculate an optimal bin count dynamically based on the number of leaves to split?
+//       The paper suggests (4 + 2 * sqrt(num_leaves).floor()).min(16)
+const NUM_BINS: usize = 8;
+const BIN_EPSILON: Real = 1.0e-5;
+
+/// Number of leaves binned by each task when the binning runs in parallel.
+#[cfg(feature = "parallel")]
+const PARALLEL_BINNING_CHUNK: usize = 4096;
+
 impl Bvh {
     /// Fully rebuilds this BVH using the given strategy.
     ///
@@ -37,28 +49,68 @@ impl Bvh {
     }
 
     pub(crate) fn rebuild_range_binned(&mut self, target_node_id: u32, leaves: &mut [BvhNode]) {
-        // PERF: calculate an optimal bin count dynamically based on the number of leaves to split?
-        //       The paper suggests (4 + 2 * sqrt(num_leaves).floor()).min(16)
-        const NUM_BINS: usize = 8;
-        const BIN_EPSILON: Real = 1.0e-5;
+        let mid = Self::binned_split::<false>(leaves);
 
-        let mut bins = [BvhBin::default(); NUM_BINS];
+        // Recurse.
+        let (left_leaves, right_leaves) = leaves.split_at_mut(mid);
+
+        assert!(!left_leaves.is_empty() && !right_leaves.is_empty());
+
+        // Recurse.
+        if left_leaves.len() == 1 {
+            let target = &mut self.nodes[target_node_id as usize];
+            target.left = left_leaves[0];
 
+            if target.left.is_leaf() {
+                self.leaf_node_indices[target.left.children as usize] =
+                    BvhNodeIndex::left(target_node_id);
+            } else {
+                self.parents[target.left.children as usize] = BvhNodeIndex::left(target_node_id);
+            }
+        } else {
+            let left_id = self.nodes.len() as u32;
+            self.nodes.push(BvhNodeWide::zeros());
+            self.parents.push(BvhNodeIndex::left(target_node_id));
+            self.rebuild_range_binned(left_id, left_leaves);
+            self.nodes[target_node_id as usize].left = self.nodes[left_id as usize].merged(left_id);
+        }
+
+        if right_leaves.len() == 1 {
+            let target = &mut self.nodes[target_node_id as usize];
+            target.right = right_leaves[0];
+
+            if target.right.is_leaf() {
+                self.leaf_node_indices[target.right.children as usize] =
+                    BvhNodeIndex::right(target_node_id);
+            } else {
+                self.parents[target.right.children as usize] = BvhNodeIndex::right(target_node_id);
+            }
+        } else {
+            let right_id = self.nodes.len() as u32;
+            self.nodes.push(BvhNodeWide::zeros());
+            self.parents.push(BvhNodeIndex::right(target_node_id));
+            self.rebuild_range_binned(right_id, right_leaves);
+            self.nodes[target_node_id as usize].right =
+                self.nodes[right_id as usize].merged(right_id);
+        }
+    }
+
+    /// Partitions `leaves` in-place around the best SAH splitting plane and returns the index
+    /// of the first leaf of the right partition.
+    ///
+    /// If `PARALLEL` is `true` and the `parallel` feature is enabled, the binning of large leaf
+    /// sets is distributed over multiple threads. The result is the same in both cases.
+    pub(super) fn binned_split<const PARALLEL: bool>(leaves: &mut [BvhNode]) -> usize {
         assert!(leaves.len() > 1);
 
-        let centroid_aabb = Aabb::from_points(leaves.iter().map(|node| node.center()));
+        let centroid_aabb = centroid_aabb::<PARALLEL>(leaves);
         let bins_axis = centroid_aabb.extents().imax();
         let bins_range = [centroid_aabb.mins[bins_axis], centroid_aabb.maxs[bins_axis]];
 
         // Compute bins characteristics.
         let k1 = NUM_BINS as Real * (1.0 - BIN_EPSILON) / (bins_range[1] - bins_range[0]);
         let k0 = bins_range[0];
-        for leaf in &*leaves {
-            let bin_id = (k1 * (leaf.center()[bins_axis] - k0)) as usize;
-            let bin = &mut bins[bin_id];
-            bin.aabb.merge(&leaf.aabb());
-            bin.leaf_count += 1;
-        }
+        let bins = compute_bins::<PARALLEL>(leaves, bins_axis, k0, k1);
 
         // Select the best splitting plane (there are NUM_BINS - 1 splitting planes) based on SAH.
         let mut right_merges = bins;
@@ -131,49 +183,58 @@ impl Bvh {
             }
         }
 
-        // Recurse.
-        let (left_leaves, right_leaves) = leaves.split_at_mut(mid);
-
-        assert!(!left_leaves.is_empty() && !right_leaves.is_empty());
-
-        // Recurse.
-        if left_leaves.len() == 1 {
-            let target = &mut self.nodes[target_node_id as usize];
-            target.left = left_leaves[0];
+        mid
+    }
+}
 
-            if target.left.is_leaf() {
-                self.leaf_node_indices[target.left.children as usize] =
-                    BvhNodeIndex::left(target_node_id);
-            } else {
-                self.parents[target.left.children as usize] = BvhNodeIndex::left(target_node_id);
-            }
-        } else {
-            let left_id = self.nodes.len() as u32;
-            self.nodes.push(BvhNodeWide::zeros());
-            self.parents.push(BvhNodeIndex::left(target_node_id));
-            self.rebuild_range_binned(left_id, left_leaves);
-            self.nodes[target_node_id as usize].left = self.nodes[left_id as usize].merged(left_id);
-        }
+pub(super) fn centroid_aabb<const PARALLEL: bool>(leaves: &[BvhNode]) -> Aabb {
+    #[cfg(feature = "parallel")]
+    if PARALLEL && leaves.len() > PARALLEL_BINNING_CHUNK {
+        // NOTE: merging AABBs is exact so the result doesn’t depend on the chunking.
+        return leaves
+            .par_chunks(PARALLEL_BINNING_CHUNK)
+            .map(|chunk| Aabb::from_points(chunk.iter().map(|node| node.center())))
+            .reduce(Aabb::new_invalid, |a, b| a.merged(&b));
+    }
 
-        if right_leaves.len() == 1 {
-            let target = &mut self.nodes[target_node_id as usize];
-            target.right = right_leaves[0];
+    Aabb::from_points(leaves.iter().map(|node| node.center()))
+}
 
-            if target.right.is_leaf() {
-                self.leaf_node_indices[target.right.children as usize] =
-                    BvhNodeIndex::right(target_node_id);
-            } else {
-                self.parents[target.right.children as usize] = BvhNodeIndex::right(target_node_id);
-            }
-        } else {
-            let right_id = self.nodes.len() as u32;
-            self.nodes.push(BvhNodeWide::zeros());
-            self.parents.push(BvhNodeIndex::right(target_node_id));
-            self.rebuild_range_binned(right_id, right_leaves);
-            self.nodes[target_node_id as usize].right =
-                self.nodes[right_id as usize].merged(right_id);
+fn compute_bins<const PARALLEL: bool>(
+    leaves: &[BvhNode],
+    bins_axis: usize,
+    k0: Real,
+    k1: Real,
+) -> [BvhBin; NUM_BINS] {
+    let fill_bins = |leaves: &[BvhNode]| {
+        let mut bins = [BvhBin::default(); NUM_BINS];
+        for leaf in leaves {
+            let bin_id = (k1 * (leaf.center()[bins_axis] - k0)) as usize;
+            let bin = &mut bins[bin_id];
+            bin.aabb.merge(&leaf.aabb());
+            bin.leaf_count += 1;
         }
+        bins
+    };
+
+    #[cfg(feature = "parallel")]
+    if PARALLEL && leaves.len() > PARALLEL_BINNING_CHUNK {
+        return leaves
+            .par_chunks(PARALLEL_BINNING_CHUNK)
+            .map(fill_bins)
+            .reduce(
+                || [BvhBin::default(); NUM_BINS],
+                |mut a, b| {
+                    for (bin_a, bin_b) in a.iter_mut().zip(b.iter()) {
+                        bin_a.aabb.merge(&bin_b.aabb);
+                        bin_a.leaf_count += bin_b.leaf_count;
+                    }
+                    a
+                },
+            );
     }
+
+    fill_bins(leaves)
 }
 
 #[derive(Copy, Clone, Debug)]
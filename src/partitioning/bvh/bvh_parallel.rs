@@ -0,0 +1,297 @@
+use super::bvh_binned_build::centroid_aabb;
+use super::bvh_ploc_build::{ploc_merge_candidate, ploc_morton_key};
+use super::bvh_tree::{BvhNodeIndex, BvhNodeVec, BvhNodeWide};
+use super::{Bvh, BvhBuildStrategy, BvhNode, BvhWorkspace};
+use crate::bounding_volume::Aabb;
+use alloc::{vec, vec::Vec};
+use rayon::prelude::*;
+
+/// Subtrees with fewer leaves than this are built or refitted on a single thread.
+const PARALLEL_LEAF_COUNT_THRESHOLD: usize = 1024;
+
+impl Bvh {
+    /// Creates a new BVH with a slice of AABBs, using multiple threads.
+    ///
+    /// This results in the same tree as [`Bvh::from_leaves`] but runs the build and the final
+    /// refit on the rayon thread pool.
+    pub fn from_leaves_parallel(strategy: BvhBuildStrategy, leaves: &[Aabb]) -> Self {
+        Self::from_iter_parallel(strategy, leaves.par_iter().copied().enumerate())
+    }
+
+    /// Creates a new BVH with leaves given by a parallel iterator, using multiple threads.
+    ///
+    /// This results in the same tree as [`Bvh::from_iter`] called with the same leaves in the
+    /// same order.
+    pub fn from_iter_parallel<It>(strategy: BvhBuildStrategy, leaves: It) -> Self
+    where
+        It: IntoParallelIterator<Item = (usize, Aabb)>,
+    {
+        let mut result = Self::new();
+        let mut workspace = BvhWorkspace {
+            rebuild_leaves: leaves
+                .into_par_iter()
+                .map(|(leaf_id, leaf_aabb)| BvhNode::leaf(leaf_aabb, leaf_id as u32))
+                .collect(),
+            ..Default::default()
+        };
+        result
+            .leaf_node_indices
+            .reserve_len(workspace.rebuild_leaves.len());
+
+        for leaf in &workspace.rebuild_leaves {
+            let _ = result
+                .leaf_node_indices
+                .insert(leaf.children as usize, BvhNodeIndex::default());
+        }
+
+        if result.init_build(&workspace.rebuild_leaves) {
+            result.rebuild_range_parallel(strategy, &mut workspace.rebuild_leaves);
+
+            // Layout in depth-first order.
+            result.refit_parallel(&mut workspace);
+        }
+
+        result
+    }
+
+    /// Fully rebuilds this BVH using the given strategy, using multiple threads.
+    ///
+    /// This results in the same tree as [`Bvh::rebuild`].
+    pub fn rebuild_parallel(&mut self, workspace: &mut BvhWorkspace, strategy: BvhBuildStrategy) {
+        if self.nodes.len() < 2 {
+            // Nothing to rebuild if the tree is empty or only contains the root.
+            // This takes care of the case where we have a partial root too.
+            return;
+        }
+
+        workspace.rebuild_leaves.clear();
+        for node in self.nodes.iter() {
+            if node.left.is_leaf() {
+                workspace.rebuild_leaves.push(node.left);
+            }
+            if node.right.is_leaf() {
+                workspace.rebuild_leaves.push(node.right);
+            }
+        }
+
+        self.nodes.clear();
+        self.parents.clear();
+        self.nodes.push(BvhNodeWide::zeros());
+        self.parents.push(BvhNodeIndex::default());
+        self.rebuild_range_parallel(strategy, &mut workspace.rebuild_leaves);
+    }
+
+    /// Performs a tree refitting with internal storage cache optimizations, using multiple threads.
+    ///
+    /// This results in the same tree (including its internal storage layout) as [`Bvh::refit`].
+    pub fn refit_parallel(&mut self, workspace: &mut BvhWorkspace) {
+        if (self.leaf_count() as usize) < PARALLEL_LEAF_COUNT_THRESHOLD {
+            self.refit(workspace);
+            return;
+        }
+
+        // A tree with `n` leaves has exactly `n - 1` wide nodes.
+        let len = self.leaf_count() as usize - 1;
+        let target = &mut workspace.refit_tmp;
+        target.clear();
+        target.resize(len, BvhNodeWide::zeros());
+        self.parents.resize(len, BvhNodeIndex::default());
+        self.parents[0] = BvhNodeIndex::default();
+
+        refit_wide_node_parallel(&self.nodes, target, &mut self.parents, 0, 0);
+
+        // Swap the old nodes with the refitted ones.
+        core::mem::swap(&mut self.nodes, &mut workspace.refit_tmp);
+        self.parents.truncate(len);
+        self.update_leaf_node_indices();
+    }
+
+    /// Builds the tree from `leaves`, assuming the root was already initialized with
+    /// [`Bvh::init_build`].
+    fn rebuild_range_parallel(&mut self, strategy: BvhBuildStrategy, leaves: &mut Vec<BvhNode>) {
+        match strategy {
+            BvhBuildStrategy::Binned => {
+                // The binned builder lays out the nodes in depth-first order, so the subtree
+                // rooted at any wide node with `n` leaves occupies the next `n - 1` slots. This
+                // lets each thread write directly into its own disjoint part of the node buffer.
+                let len = leaves.len() - 1;
+                self.nodes.resize(len, BvhNodeWide::zeros());
+                self.parents.resize(len, BvhNodeIndex::default());
+                build_binned_parallel(leaves, &mut self.nodes, &mut self.parents, 0);
+                self.update_leaf_node_indices();
+            }
+            BvhBuildStrategy::Ploc => self.rebuild_range_ploc_parallel(leaves),
+        }
+    }
+
+    fn rebuild_range_ploc_parallel(&mut self, leaves: &mut Vec<BvhNode>) {
+        // Compute the centroids aabb.
+        let aabb = centroid_aabb::<true>(leaves);
+
+        // Sort the leaves.
+        leaves.par_sort_by_cached_key(|node| ploc_morton_key(node, &aabb));
+
+        // Build all the levels. Only the search for merge candidates runs in parallel: it
+        // dominates the build time and doesn’t depend on the order of evaluation.
+        let mut merge_candidates = vec![usize::MAX; leaves.len()];
+        let mut next_leaves = Vec::with_capacity(leaves.len());
+
+        while leaves.len() > 1 {
+            // Find merge candidates.
+            merge_candidates[..leaves.len()]
+                .par_iter_mut()
+                .enumerate()
+                .for_each(|(i, candidate)| *candidate = ploc_merge_candidate(leaves, i));
+
+            self.ploc_merge_level(0, leaves, &merge_candidates, &mut next_leaves);
+        }
+    }
+
+    /// Sets the index of every leaf from their current location in the node buffer.
+    fn update_leaf_node_indices(&mut self) {
+        for (id, node) in self.nodes.iter().enumerate() {
+            if node.left.is_leaf() {
+                self.leaf_node_indices[node.left.children as usize] = BvhNodeIndex::left(id as u32);
+            }
+            if node.right.is_leaf() {
+                self.leaf_node_indices[node.right.children as usize] =
+                    BvhNodeIndex::right(id as u32);
+            }
+        }
+    }
+}
+
+/// Builds the wide node `nodes[0]` (with global index `id`) and all its descendants.
+///
+/// `nodes` and `parents` must contain exactly `leaves.len() - 1` elements.
+fn build_binned_parallel(
+    leaves: &mut [BvhNode],
+    nodes: &mut [BvhNodeWide],
+    parents: &mut [BvhNodeIndex],
+    id: u32,
+) {
+    let parallel = leaves.len() >= PARALLEL_LEAF_COUNT_THRESHOLD;
+    let mid = Bvh::binned_split::<true>(leaves);
+    let (left_leaves, right_leaves) = leaves.split_at_mut(mid);
+    let (node, nodes) = nodes.split_first_mut().unwrap();
+    let (left_nodes, right_nodes) = nodes.split_at_mut(mid - 1);
+    let (left_parents, right_parents) = parents[1..].split_at_mut(mid - 1);
+
+    let left_id = id + 1;
+    let right_id = id + mid as u32;
+    let mut build_left = || {
+        build_binned_child(
+            left_leaves,
+            left_nodes,
+            left_parents,
+            left_id,
+            BvhNodeIndex::left(id),
+        )
+    };
+    let mut build_right = || {
+        build_binned_child(
+            right_leaves,
+            right_nodes,
+            right_parents,
+            right_id,
+            BvhNodeIndex::right(id),
+        )
+    };
+
+    let (left, right) = if parallel {
+        rayon::join(build_left, build_right)
+    } else {
+        (build_left(), build_right())
+    };
+
+    *node = BvhNodeWide { left, right };
+}
+
+/// Builds the child of a wide node, returning the node to store in its parent.
+fn build_binned_child(
+    leaves: &mut [BvhNode],
+    nodes: &mut [BvhNodeWide],
+    parents: &mut [BvhNodeIndex],
+    id: u32,
+    parent: BvhNodeIndex,
+) -> BvhNode {
+    if leaves.len() == 1 {
+        leaves[0]
+    } else {
+        parents[0] = parent;
+        build_binned_parallel(leaves, nodes, parents, id);
+        nodes[0].merged(id)
+    }
+}
+
+/// Refits the wide node `source[source_id]` and all its descendants into `target`, where
+/// `target[0]` has the global index `target_id`.
+///
+/// `target` and `parents` must contain exactly as many elements as there are wide nodes in
+/// that subtree.
+fn refit_wide_node_parallel(
+    source: &BvhNodeVec,
+    target: &mut [BvhNodeWide],
+    parents: &mut [BvhNodeIndex],
+    source_id: u32,
+    target_id: u32,
+) {
+    let node = &source[source_id as usize];
+    let parallel = node.leaf_count() as usize >= PARALLEL_LEAF_COUNT_THRESHOLD;
+    // A leaf has no wide node, an internal node with `n` leaves has `n - 1` of them.
+    let left_len = node.left.leaf_count() as usize - 1;
+    let (wide_node, target) = target.split_first_mut().unwrap();
+    let (left_target, right_target) = target.split_at_mut(left_len);
+    let (left_parents, right_parents) = parents[1..].split_at_mut(left_len);
+
+    let left_id = target_id + 1;
+    let right_id = target_id + 1 + left_len as u32;
+    let mut refit_left = || {
+        refit_child_parallel(
+            source,
+            &node.left,
+            left_target,
+            left_parents,
+            left_id,
+            BvhNodeIndex::left(target_id),
+        )
+    };
+    let mut refit_right = || {
+        refit_child_parallel(
+            source,
+            &node.right,
+            right_target,
+            right_parents,
+            right_id,
+            BvhNodeIndex::right(target_id),
+        )
+    };
+
+    let (left, right) = if parallel {
+        rayon::join(refit_left, refit_right)
+    } else {
+        (refit_left(), refit_right())
+    };
+
+    *wide_node = BvhNodeWide { left, right };
+}
+
+/// Refits the child of a wide node, returning the node to store in its parent.
+fn refit_child_parallel(
+    source: &BvhNodeVec,
+    child: &BvhNode,
+    target: &mut [BvhNodeWide],
+    parents: &mut [BvhNodeIndex],
+    target_id: u32,
+    parent: BvhNodeIndex,
+) -> BvhNode {
+    if child.is_leaf() {
+        let mut leaf = *child;
+        leaf.data.resolve_pending_change();
+        leaf
+    } else {
+        parents[0] = parent;
+        refit_wide_node_parallel(source, target, parents, child.children, target_id);
+        target[0].merged(target_id)
+    }
+}
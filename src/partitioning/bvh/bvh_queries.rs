@@ -1,9 +1,11 @@
 use super::{Bvh, BvhNode};
-use crate::bounding_volume::{Aabb, BoundingVolume};
+use crate::bounding_volume::{Aabb, BoundingVolume, SimdAabb};
 use crate::math::Point;
-use crate::math::Real;
+use crate::math::{Real, SimdBool, SimdReal, SIMD_WIDTH};
 use crate::query::PointProjection;
-use crate::query::{PointQuery, Ray};
+use crate::query::{stats, PointQuery, Ray, SimdRay};
+use simba::simd::{SimdBool as _, SimdPartialOrd, SimdValue};
+use smallvec::SmallVec;
 
 #[cfg(all(feature = "simd-is-enabled", feature = "dim3", feature = "f32"))]
 pub(super) struct SimdInvRay {
@@ -123,4 +125,101 @@ impl Bvh {
             |primitive, best_so_far| primitive_check(primitive, best_so_far),
         )
     }
+
+    /// Casts a packet of [`SIMD_WIDTH`] rays on this BVH using the provided leaf ray-cast function.
+    ///
+    /// All the rays of the packet traverse the tree together: each node is tested against every
+    /// ray at once, and a subtree is skipped only if none of the rays can hit it before the
+    /// closest hit already found for that ray. This is typically faster than calling
+    /// [`Bvh::cast_ray`] for each ray individually if the rays are coherent (i.e. have similar
+    /// origins and directions).
+    ///
+    /// The `primitive_check` delegates the ray-casting task to an external function that
+    /// is assumed to map a leaf index to an actual geometry to cast the rays on. Its [`SimdBool`]
+    /// argument indicates which rays need to be tested against that leaf (other lanes are
+    /// ignored), and its [`SimdReal`] argument contains the closest time of impact found so far
+    /// for each ray (or `max_time_of_impact` if no hit was found so far). It must return the time
+    /// of impact of each ray, or `Real::MAX` for the rays that don’t hit the leaf.
+    ///
+    /// Returns, for each ray of the packet, the index of the closest leaf hit and the
+    /// associated time of impact.
+    pub fn cast_ray_packet(
+        &self,
+        rays: &SimdRay,
+        max_time_of_impact: SimdReal,
+        mut primitive_check: impl FnMut(u32, SimdBool, SimdReal) -> SimdReal,
+    ) -> [Option<(u32, Real)>; SIMD_WIDTH] {
+        let mut best_toi = max_time_of_impact;
+        let mut best_ids = [u32::MAX; SIMD_WIDTH];
+        let mut stack: SmallVec<[(&BvhNode, SimdBool, SimdReal); 32]> = SmallVec::new();
+
+        // Returns the rays hitting the node’s AABB before their closest hit so far, as well as
+        // the time of impact on that AABB.
+        let check_node = |node: &BvhNode, best_toi: SimdReal| {
+            let (hit, tmin) = SimdAabb::splat(node.aabb()).cast_local_ray(rays, best_toi);
+            (hit & tmin.simd_lt(best_toi), tmin)
+        };
+
+        if let Some(root) = self.nodes.first() {
+            if root.right.leaf_count() > 0 {
+                let (hit, tmin) = check_node(&root.right, best_toi);
+                stack.push((&root.right, hit, tmin));
+            }
+
+            let (hit, tmin) = check_node(&root.left, best_toi);
+            stack.push((&root.left, hit, tmin));
+        }
+
+        while let Some((node, hit, tmin)) = stack.pop() {
+            // The closest hits may have improved since this node was pushed.
+            let active = hit & tmin.simd_lt(best_toi);
+
+            if active.none() {
+                continue;
+            }
+
+            stats::record_bvh_node_visit(node.is_leaf());
+
+            if node.is_leaf() {
+                let toi = primitive_check(node.children, active, best_toi);
+                let closer = active & toi.simd_lt(best_toi);
+                best_toi = toi.select(closer, best_toi);
+
+                let mut lanes = closer.bitmask();
+                while lanes != 0 {
+                    best_ids[lanes.trailing_zeros() as usize] = node.children;
+                    lanes &= lanes - 1;
+                }
+            } else {
+                let children = &self.nodes[node.children as usize];
+                let (hit_left, tmin_left) = check_node(&children.left, best_toi);
+                let (hit_right, tmin_right) = check_node(&children.right, best_toi);
+                let hit_left = hit_left & active;
+                let hit_right = hit_right & active;
+
+                // Traverse first the child that is hit the soonest by any of the rays.
+                let max = SimdReal::splat(Real::MAX);
+                let first_left = tmin_left.select(hit_left, max).simd_horizontal_min()
+                    <= tmin_right.select(hit_right, max).simd_horizontal_min();
+                let mut entries = [
+                    (&children.left, hit_left, tmin_left),
+                    (&children.right, hit_right, tmin_right),
+                ];
+
+                if first_left {
+                    entries.swap(0, 1);
+                }
+
+                for entry in entries {
+                    if entry.1.any() {
+                        stack.push(entry);
+                    }
+                }
+            }
+        }
+
+        core::array::from_fn(|lane| {
+            (best_ids[lane] != u32::MAX).then(|| (best_ids[lane], best_toi.extract(lane)))
+        })
+    }
 }
@@ -89,3 +89,95 @@ fn bvh_build_and_removal() {
         }
     }
 }
+
+#[test]
+#[cfg(feature = "parallel")]
+fn bvh_parallel_build_matches_serial_build() {
+    use crate::partitioning::BvhWorkspace;
+
+    // Use enough leaves for the parallel code paths to actually split the work.
+    let leaves: alloc::vec::Vec<_> = (0..10_000)
+        .map(|i| {
+            let center = Vector::repeat(((i * 7919) % 10_007) as Real) + Vector::x() * i as Real;
+            Aabb::from_half_extents(center.into(), Vector::repeat(1.0 + (i % 5) as Real))
+        })
+        .collect();
+
+    for strategy in [BvhBuildStrategy::Binned, BvhBuildStrategy::Ploc] {
+        let serial = Bvh::from_leaves(strategy, &leaves);
+        let mut parallel = Bvh::from_leaves_parallel(strategy, &leaves);
+        parallel.assert_well_formed();
+        assert_eq!(alloc::format!("{serial:?}"), alloc::format!("{parallel:?}"));
+
+        let mut workspace = BvhWorkspace::default();
+        let mut serial_rebuilt = serial.clone();
+        serial_rebuilt.rebuild(&mut workspace, strategy);
+        serial_rebuilt.refit(&mut workspace);
+        parallel.rebuild_parallel(&mut workspace, strategy);
+        parallel.refit_parallel(&mut workspace);
+        parallel.assert_well_formed();
+        assert_eq!(
+            alloc::format!("{serial_rebuilt:?}"),
+            alloc::format!("{parallel:?}")
+        );
+    }
+}
+
+#[test]
+fn bvh_traverse_bvtt_matches_brute_force() {
+    use crate::bounding_volume::BoundingVolume;
+    use crate::math::Isometry;
+    use crate::partitioning::TraversalAction;
+    use alloc::vec::Vec;
+
+    // Various sizes, including empty trees and partial roots.
+    for (len1, len2) in [(0, 10), (1, 1), (1, 7), (2, 2), (50, 3), (100, 80)] {
+        let leaves1: Vec<_> = (0..len1).map(make_test_aabb).collect();
+        let leaves2: Vec<_> = (0..len2).map(make_test_aabb).collect();
+        let bvh1 = Bvh::from_leaves(BvhBuildStrategy::Binned, &leaves1);
+        let bvh2 = Bvh::from_leaves(BvhBuildStrategy::Ploc, &leaves2);
+        #[cfg(feature = "dim2")]
+        let pos12 = Isometry::new(Vector::new(3.0, -2.0), 0.7);
+        #[cfg(feature = "dim3")]
+        let pos12 = Isometry::new(Vector::new(3.0, -2.0, 1.0), Vector::new(0.7, -0.3, 0.2));
+
+        let mut expected = Vec::new();
+        for (i, aabb1) in leaves1.iter().enumerate() {
+            for (j, aabb2) in leaves2.iter().enumerate() {
+                if aabb1.intersects(&aabb2.transform_by(&pos12)) {
+                    expected.push((i as u32, j as u32));
+                }
+            }
+        }
+
+        let mut found = Vec::new();
+        bvh1.traverse_bvtt(&bvh2, &pos12, |node1, node2, aabb2| {
+            if !node1.aabb().intersects(aabb2) {
+                return TraversalAction::Prune;
+            }
+
+            if let (Some(leaf1), Some(leaf2)) = (node1.leaf_data(), node2.leaf_data()) {
+                found.push((leaf1, leaf2));
+            }
+
+            TraversalAction::Continue
+        });
+
+        found.sort();
+        assert_eq!(found, expected);
+
+        // The traversal stops at the first leaf pair if requested.
+        let mut num_found = 0;
+        bvh1.traverse_bvtt(&bvh2, &pos12, |node1, node2, aabb2| {
+            if !node1.aabb().intersects(aabb2) {
+                TraversalAction::Prune
+            } else if node1.is_leaf() && node2.is_leaf() {
+                num_found += 1;
+                TraversalAction::EarlyExit
+            } else {
+                TraversalAction::Continue
+            }
+        });
+        assert_eq!(num_found, expected.len().min(1));
+    }
+}
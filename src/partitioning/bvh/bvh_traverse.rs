@@ -1,6 +1,7 @@
 use super::BvhNode;
 use crate::math::Real;
 use crate::partitioning::Bvh;
+use crate::query::stats;
 use smallvec::SmallVec;
 
 const TRAVERSAL_STACK_SIZE: usize = 32;
@@ -21,6 +22,7 @@ impl<'a, Check: Fn(&BvhNode) -> bool> Iterator for Leaves<'a, Check> {
             }
 
             let node = self.next.take()?;
+            stats::record_bvh_node_visit(node.is_leaf());
 
             if node.is_leaf() {
                 return Some(node.children);
@@ -116,6 +118,30 @@ impl Bvh {
         Default::default()
     }
 
+    /// The children of the root of this tree.
+    ///
+    /// This yields zero, one, or two nodes depending on the number of leaves.
+    #[cfg(feature = "dim3")]
+    pub(crate) fn root_nodes(&self) -> impl Iterator<Item = &BvhNode> {
+        self.nodes
+            .first()
+            .into_iter()
+            .flat_map(|root| [&root.left, &root.right])
+            .filter(|node| node.leaf_count() > 0)
+    }
+
+    /// The two children of `node`, or `None` if it is a leaf.
+    ///
+    /// The `node` must be part of this tree.
+    #[cfg(feature = "dim3")]
+    pub(crate) fn node_children(&self, node: &BvhNode) -> Option<[&BvhNode; 2]> {
+        if node.is_leaf() {
+            None
+        } else {
+            Some(self.nodes[node.children as usize].as_array())
+        }
+    }
+
     /// Traverse the tree in depth-first order.
     ///
     /// The `check_node` closure is called on every traversed node. The returned [`TraversalAction`]
@@ -135,6 +161,7 @@ impl Bvh {
             return;
         } else if self.nodes[0].right.leaf_count() == 0 {
             // Special case for partial root.
+            stats::record_bvh_node_visit(true);
             let _ = check_node(&self.nodes[0].left);
             return;
         }
@@ -143,11 +170,13 @@ impl Bvh {
             let node = &self.nodes[curr_id as usize];
             let left = &node.left;
             let right = &node.right;
+            stats::record_bvh_node_visit(left.is_leaf());
             let go_left = match check_node(left) {
                 TraversalAction::Continue => !left.is_leaf(),
                 TraversalAction::Prune => false,
                 TraversalAction::EarlyExit => return,
             };
+            stats::record_bvh_node_visit(right.is_leaf());
             let go_right = match check_node(right) {
                 TraversalAction::Continue => !right.is_leaf(),
                 TraversalAction::Prune => false,
@@ -190,6 +219,7 @@ impl Bvh {
         } else if self.nodes[0].right.leaf_count() == 0 {
             // Special case for partial root.
             let leaf = &self.nodes[0].left;
+            stats::record_bvh_node_visit(true);
             if aabb_cost(leaf, max_cost) < max_cost {
                 let cost = leaf_cost(leaf.children, best_cost)?;
                 return (cost.cost() < max_cost).then_some((leaf.children, cost));
@@ -203,6 +233,9 @@ impl Bvh {
             let mut left = &node.left;
             let mut right = &node.right;
 
+            stats::record_bvh_node_visit(left.is_leaf());
+            stats::record_bvh_node_visit(right.is_leaf());
+
             let mut left_score = aabb_cost(left, best_cost);
             let mut right_score = aabb_cost(right, best_cost);
 
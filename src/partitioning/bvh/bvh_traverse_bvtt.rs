@@ -1,4 +1,7 @@
-use super::{Bvh, BvhNode, BvhWorkspace};
+use super::{Bvh, BvhNode, BvhWorkspace, TraversalAction};
+use crate::bounding_volume::Aabb;
+use crate::math::{Isometry, Real};
+use crate::query::stats;
 use smallvec::SmallVec;
 
 const TRAVERSAL_STACK_SIZE: usize = 32;
@@ -203,6 +206,77 @@ impl Bvh {
         }
     }
 
+    /*
+     * Traversal of two trees.
+     */
+    /// Traverses the Bounding Volume Test Tree of `self` against `other`, where `other` is
+    /// positioned relative to `self` by `pos12`.
+    ///
+    /// The closure `check` is called on every traversed pair of nodes `(node1, node2)` where
+    /// `node1` belongs to `self` and `node2` belongs to `other`. Its third argument is the AABB of
+    /// `node2` transformed into the local-space of `self`, computed on the fly so that none of
+    /// the trees need to be modified. The returned [`TraversalAction`] controls whether the
+    /// children of this pair of nodes need to be traversed, skipped, or if the traversal needs
+    /// to exit immediately. The action returned for a pair of leaves only matters if it is
+    /// [`TraversalAction::EarlyExit`].
+    ///
+    /// Note that no overlap check is performed by the traversal itself: pairs of nodes that
+    /// are irrelevant (for example because their AABBs don’t intersect, or are too far apart
+    /// for a distance query) must be pruned by `check`. Since the AABBs of `other` are
+    /// transformed during the traversal, it is generally faster to pass the smallest tree as
+    /// `other`.
+    pub fn traverse_bvtt(
+        &self,
+        other: &Self,
+        pos12: &Isometry<Real>,
+        mut check: impl FnMut(&BvhNode, &BvhNode, &Aabb) -> TraversalAction,
+    ) {
+        let (Some(root1), Some(root2)) = (self.nodes.first(), other.nodes.first()) else {
+            return;
+        };
+
+        let mut stack: SmallVec<[(&BvhNode, &BvhNode); TRAVERSAL_STACK_SIZE]> = SmallVec::new();
+
+        // NOTE: the right node of a partial root has a leaf count of zero.
+        for node1 in [&root1.right, &root1.left] {
+            for node2 in [&root2.right, &root2.left] {
+                if node1.leaf_count() > 0 && node2.leaf_count() > 0 {
+                    stack.push((node1, node2));
+                }
+            }
+        }
+
+        while let Some((node1, node2)) = stack.pop() {
+            stats::record_bvh_node_pair_visit();
+            let aabb2 = node2.aabb().transform_by(pos12);
+
+            match check(node1, node2, &aabb2) {
+                TraversalAction::Continue => {}
+                TraversalAction::Prune => continue,
+                TraversalAction::EarlyExit => return,
+            }
+
+            // Descend into the largest internal node. The volume of `node2` is the same in both
+            // local-spaces since `pos12` is an isometry.
+            let descend1 = match (node1.is_leaf(), node2.is_leaf()) {
+                (true, true) => continue,
+                (true, false) => false,
+                (false, true) => true,
+                (false, false) => node1.volume() >= node2.volume(),
+            };
+
+            if descend1 {
+                let children1 = &self.nodes[node1.children as usize];
+                stack.push((&children1.right, node2));
+                stack.push((&children1.left, node2));
+            } else {
+                let children2 = &other.nodes[node2.children as usize];
+                stack.push((node1, &children2.right));
+                stack.push((node1, &children2.left));
+            }
+        }
+    }
+
     /// Performs a simultaneous traversal of the BVHs `self` and `other`, and yields the pairs
     /// of leaves it reached.
     ///
@@ -265,6 +339,7 @@ impl<'a, Check: Fn(&BvhNode, &BvhNode) -> bool> Iterator for LeafPairs<'a, Check
             }
 
             let (node1, node2) = self.next.take()?;
+            stats::record_bvh_node_pair_visit();
 
             match (node1.is_leaf(), node2.is_leaf()) {
                 (true, true) => return Some((node1.children, node2.children)),
@@ -16,13 +16,15 @@ pub enum BvhBuildStrategy {
     /// The tree is built using the binned strategy.
     ///
     /// This implements the strategy from "On fast Construction of SAH-based Bounding Volume Hierarchies", Ingo Ward.
-    /// This is **not** parallelized yet.
+    /// A parallel version is available through `Bvh::from_leaves_parallel` and
+    /// `Bvh::rebuild_parallel` if the `parallel` feature is enabled.
     #[default]
     Binned,
     /// The tree is built using the Locally-Ordered Clustering technique.
     ///
     /// This implements the strategy from "Parallel Locally-Ordered Clustering for Bounding Volume Hierarchy Construction", Meister, Bittner.
-    /// This is **not** parallelized yet.
+    /// A parallel version is available through `Bvh::from_leaves_parallel` and
+    /// `Bvh::rebuild_parallel` if the `parallel` feature is enabled.
     Ploc,
 }
 
@@ -548,48 +550,59 @@ impl Bvh {
                 .insert(leaf_id, BvhNodeIndex::default());
         }
 
+        if result.init_build(&workspace.rebuild_leaves) {
+            match strategy {
+                BvhBuildStrategy::Ploc => {
+                    result.rebuild_range_ploc(0, &mut workspace.rebuild_leaves)
+                }
+                BvhBuildStrategy::Binned => {
+                    result.rebuild_range_binned(0, &mut workspace.rebuild_leaves)
+                }
+            }
+
+            // Layout in depth-first order.
+            result.refit(&mut workspace);
+        }
+
+        result
+    }
+
+    /// Initializes the root of an empty tree that is about to be built from the given leaves.
+    ///
+    /// Returns `true` if the rest of the tree still needs to be built by one of the rebuild
+    /// strategies, or `false` if there are too few leaves for that to be needed.
+    pub(super) fn init_build(&mut self, leaves: &[BvhNode]) -> bool {
         // Handle special cases that don’t play well with the rebuilds.
-        match workspace.rebuild_leaves.len() {
-            0 => {}
+        match leaves.len() {
+            0 => false,
             1 => {
-                result.nodes.push(BvhNodeWide {
-                    left: workspace.rebuild_leaves[0],
+                self.nodes.push(BvhNodeWide {
+                    left: leaves[0],
                     right: BvhNode::zeros(),
                 });
-                result.parents.push(BvhNodeIndex::default());
-                result.leaf_node_indices[0] = BvhNodeIndex::left(0);
+                self.parents.push(BvhNodeIndex::default());
+                self.leaf_node_indices[leaves[0].children as usize] = BvhNodeIndex::left(0);
+                false
             }
             2 => {
-                result.nodes.push(BvhNodeWide {
-                    left: workspace.rebuild_leaves[0],
-                    right: workspace.rebuild_leaves[1],
+                self.nodes.push(BvhNodeWide {
+                    left: leaves[0],
+                    right: leaves[1],
                 });
-                result.parents.push(BvhNodeIndex::default());
-                result.leaf_node_indices[0] = BvhNodeIndex::left(0);
-                result.leaf_node_indices[1] = BvhNodeIndex::right(0);
+                self.parents.push(BvhNodeIndex::default());
+                self.leaf_node_indices[leaves[0].children as usize] = BvhNodeIndex::left(0);
+                self.leaf_node_indices[leaves[1].children as usize] = BvhNodeIndex::right(0);
+                false
             }
             _ => {
-                result.nodes.reserve(capacity);
-                result.parents.reserve(capacity);
-                result.parents.clear();
-                result.nodes.push(BvhNodeWide::zeros());
-                result.parents.push(BvhNodeIndex::default());
-
-                match strategy {
-                    BvhBuildStrategy::Ploc => {
-                        result.rebuild_range_ploc(0, &mut workspace.rebuild_leaves)
-                    }
-                    BvhBuildStrategy::Binned => {
-                        result.rebuild_range_binned(0, &mut workspace.rebuild_leaves)
-                    }
-                }
-
-                // Layout in depth-first order.
-                result.refit(&mut workspace);
+                self.nodes.reserve(leaves.len());
+                self.parents.reserve(leaves.len());
+                self.parents.clear();
+                self.nodes.push(BvhNodeWide::zeros());
+                self.parents.push(BvhNodeIndex::default());
+                true
             }
         }
-
-        result
     }
 
     /// The AABB bounding everything contained by this BVH.
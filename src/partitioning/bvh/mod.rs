@@ -6,6 +6,8 @@ use bvh_optimize::BvhOptimizationHeapEntry;
 mod bvh_binned_build;
 mod bvh_insert;
 mod bvh_optimize;
+#[cfg(feature = "parallel")]
+mod bvh_parallel;
 mod bvh_ploc_build;
 mod bvh_queries;
 mod bvh_refit;
@@ -1,7 +1,11 @@
 //! Spatial partitioning tools.
 
+#[cfg(feature = "alloc")]
+pub use self::broad_phase_bvh::{BroadPhaseBvh, BroadPhasePairEvent};
 #[cfg(feature = "alloc")]
 pub use self::bvh::{Bvh, BvhBuildStrategy, BvhLeafCost, BvhNode, BvhWorkspace, TraversalAction};
 
+#[cfg(feature = "alloc")]
+mod broad_phase_bvh;
 #[cfg(feature = "alloc")]
 mod bvh;
@@ -0,0 +1,209 @@
+use crate::math::{Isometry, Point, Real};
+use crate::query::details::closest_points_support_map_support_map_with_cache;
+use crate::query::gjk::{GJKResult, GjkCache};
+use crate::query::Unsupported;
+use crate::shape::{FeatureId, Shape, TypedShape};
+
+#[cfg(feature = "dim2")]
+use super::polytope2::{walk, Polytope, PolytopeShape};
+#[cfg(feature = "dim3")]
+use super::polytope3::{walk, Polytope, PolytopeShape};
+
+/// Tolerance used to decide if a point lies outside of the Voronoi region of a feature.
+pub(super) const VORONOI_EPSILON: Real = crate::math::DEFAULT_EPSILON * 100.0;
+/// The maximum number of features visited by a single walk.
+const MAX_WALK_STEPS: usize = 64;
+
+/// A pair of features and the closest points between them.
+#[derive(Copy, Clone, Debug)]
+pub(super) struct FeaturePair {
+    pub dist: Real,
+    pub point1: Point<Real>,
+    pub point2: Point<Real>,
+    pub feature1: FeatureId,
+    pub feature2: FeatureId,
+}
+
+impl FeaturePair {
+    /// Replaces `self` by the given points and features if they are closer.
+    pub fn keep_closest(
+        &mut self,
+        point1: Point<Real>,
+        point2: Point<Real>,
+        feature1: FeatureId,
+        feature2: FeatureId,
+    ) {
+        let dist = na::distance(&point1, &point2);
+
+        if dist < self.dist {
+            *self = Self {
+                dist,
+                point1,
+                point2,
+                feature1,
+                feature2,
+            };
+        }
+    }
+}
+
+/// The outcome of a closest-feature walk.
+pub(super) enum WalkResult {
+    /// The walk converged to the pair of closest features.
+    Closest(FeaturePair),
+    /// The walk found the shapes to be intersecting.
+    #[cfg(feature = "dim3")]
+    Intersecting,
+    /// The walk got stuck, or the shapes are touching.
+    Failed,
+}
+
+/// The closest points between two shapes, as computed by a [`ClosestFeatureTracker`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TrackedClosestPoints {
+    /// The distance between both shapes.
+    pub distance: Real,
+    /// The closest point on the first shape, expressed in its local-space.
+    pub point1: Point<Real>,
+    /// The closest point on the second shape, expressed in its local-space.
+    pub point2: Point<Real>,
+    /// The smallest feature of the first shape containing `point1`.
+    ///
+    /// This is [`FeatureId::Unknown`] if the closest points had to be computed with GJK and the
+    /// feature walk failed.
+    pub feature1: FeatureId,
+    /// The smallest feature of the second shape containing `point2`.
+    ///
+    /// This is [`FeatureId::Unknown`] if the closest points had to be computed with GJK and the
+    /// feature walk failed.
+    pub feature2: FeatureId,
+}
+
+/// Incremental tracking of the closest features between two convex polytopes.
+///
+/// This implements a Lin–Canny-style closest-feature algorithm: starting from the pair of
+/// closest features found by the previous call, it walks the adjacency graph of both shapes
+/// (vertices, edges and faces) until reaching a pair of features whose points lie inside of each
+/// other’s Voronoi regions. Since the closest features rarely change much between two
+/// consecutive frames, each query runs in near-constant time.
+///
+/// When no previous features are known, or when the walk fails (for example because the shapes
+/// were penetrating), the closest points are computed with a warm-started GJK instead, and used
+/// to seed the walk.
+///
+#[cfg_attr(
+    feature = "dim2",
+    doc = "Supported shapes are [`Cuboid`](crate::shape::Cuboid) and [`ConvexPolygon`](crate::shape::ConvexPolygon)."
+)]
+#[cfg_attr(
+    feature = "dim3",
+    doc = "Supported shapes are [`Cuboid`](crate::shape::Cuboid) and [`ConvexPolyhedron`](crate::shape::ConvexPolyhedron)."
+)]
+/// A tracker must only be used with a single pair of shapes.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct ClosestFeatureTracker {
+    /// The closest feature of the first shape found by the last query.
+    pub feature1: FeatureId,
+    /// The closest feature of the second shape found by the last query.
+    pub feature2: FeatureId,
+    gjk_cache: GjkCache,
+}
+
+impl ClosestFeatureTracker {
+    /// Creates a tracker without any known closest features.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Forgets the tracked features so the next query starts from scratch.
+    pub fn reset(&mut self) {
+        self.feature1 = FeatureId::Unknown;
+        self.feature2 = FeatureId::Unknown;
+        self.gjk_cache.clear();
+    }
+
+    /// Computes the closest points between `shape1` and `shape2`, starting the search from the
+    /// features found by the previous call.
+    ///
+    /// The position of `shape2` is given by `pos12`, relative to `shape1`. Returns `Ok(None)` if
+    /// the shapes are intersecting, and `Err(Unsupported)` if any of the shapes isn’t a cuboid or
+    /// a convex polyhedron (convex polygon in 2D).
+    pub fn closest_points(
+        &mut self,
+        pos12: &Isometry<Real>,
+        shape1: &dyn Shape,
+        shape2: &dyn Shape,
+    ) -> Result<Option<TrackedClosestPoints>, Unsupported> {
+        let poly1 = Polytope::new(polytope_shape(shape1)?, Isometry::identity());
+        let poly2 = Polytope::new(polytope_shape(shape2)?, *pos12);
+
+        if self.feature1 != FeatureId::Unknown && self.feature2 != FeatureId::Unknown {
+            match walk(&poly1, &poly2, self.feature1, self.feature2, MAX_WALK_STEPS) {
+                WalkResult::Closest(pair) => return Ok(Some(self.record(pos12, pair))),
+                #[cfg(feature = "dim3")]
+                WalkResult::Intersecting => return Ok(None),
+                WalkResult::Failed => {}
+            }
+        }
+
+        // Fall back to GJK.
+        let g1 = shape1.as_support_map().ok_or(Unsupported)?;
+        let g2 = shape2.as_support_map().ok_or(Unsupported)?;
+        let (point1, point2) = match closest_points_support_map_support_map_with_cache(
+            pos12,
+            g1,
+            g2,
+            Real::MAX,
+            &mut self.gjk_cache,
+        ) {
+            GJKResult::ClosestPoints(point1, point2, _) => (point1, point2),
+            _ => {
+                self.feature1 = FeatureId::Unknown;
+                self.feature2 = FeatureId::Unknown;
+                return Ok(None);
+            }
+        };
+
+        // Seed the walk with the vertices supporting the separating direction.
+        let dir = point2 - point1;
+        let seed1 = poly1.support_vertex(&dir);
+        let seed2 = poly2.support_vertex(&-dir);
+
+        if let WalkResult::Closest(pair) = walk(&poly1, &poly2, seed1, seed2, MAX_WALK_STEPS) {
+            return Ok(Some(self.record(pos12, pair)));
+        }
+
+        self.feature1 = FeatureId::Unknown;
+        self.feature2 = FeatureId::Unknown;
+        Ok(Some(TrackedClosestPoints {
+            distance: na::distance(&point1, &point2),
+            point1,
+            point2: pos12.inverse_transform_point(&point2),
+            feature1: FeatureId::Unknown,
+            feature2: FeatureId::Unknown,
+        }))
+    }
+
+    fn record(&mut self, pos12: &Isometry<Real>, pair: FeaturePair) -> TrackedClosestPoints {
+        self.feature1 = pair.feature1;
+        self.feature2 = pair.feature2;
+        TrackedClosestPoints {
+            distance: pair.dist,
+            point1: pair.point1,
+            point2: pos12.inverse_transform_point(&pair.point2),
+            feature1: pair.feature1,
+            feature2: pair.feature2,
+        }
+    }
+}
+
+fn polytope_shape(shape: &dyn Shape) -> Result<PolytopeShape<'_>, Unsupported> {
+    match shape.as_typed_shape() {
+        TypedShape::Cuboid(cuboid) => Ok(PolytopeShape::Cuboid(cuboid)),
+        #[cfg(feature = "dim2")]
+        TypedShape::ConvexPolygon(poly) => Ok(PolytopeShape::ConvexPolygon(poly)),
+        #[cfg(feature = "dim3")]
+        TypedShape::ConvexPolyhedron(poly) => Ok(PolytopeShape::ConvexPolyhedron(poly)),
+        _ => Err(Unsupported),
+    }
+}
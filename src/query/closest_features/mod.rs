@@ -0,0 +1,9 @@
+//! Incremental closest-feature tracking between convex polytopes.
+
+pub use self::closest_feature_tracker::{ClosestFeatureTracker, TrackedClosestPoints};
+
+mod closest_feature_tracker;
+#[cfg(feature = "dim2")]
+mod polytope2;
+#[cfg(feature = "dim3")]
+mod polytope3;
@@ -0,0 +1,280 @@
+use super::closest_feature_tracker::{FeaturePair, WalkResult, VORONOI_EPSILON};
+use crate::math::{Isometry, Point, Real, Vector};
+use crate::query::details::closest_points_segment_segment_with_locations_nD;
+use crate::shape::{ConvexPolygon, Cuboid, FeatureId, SegmentPointLocation};
+use crate::utils;
+
+// The vertices of a cuboid, in counterclockwise order, starting from the bottom-left corner.
+const CUBOID_VERTICES: [u32; 4] = [0b11, 0b10, 0b00, 0b01];
+// The faces of a cuboid, the i-th face linking `CUBOID_VERTICES[i]` to `CUBOID_VERTICES[i + 1]`.
+const CUBOID_FACES: [u32; 4] = [3, 0, 1, 2];
+
+/// A convex polygon with an explicit topology, positioned relative to the first shape of a
+/// closest-feature query.
+///
+/// All the points and vectors returned by its methods are expressed in the local-space of the
+/// first shape.
+pub(super) struct Polytope<'a> {
+    shape: PolytopeShape<'a>,
+    pos: Isometry<Real>,
+}
+
+#[derive(Copy, Clone)]
+pub(super) enum PolytopeShape<'a> {
+    /// The features of a cuboid are numbered as in [`Cuboid::feature_normal`].
+    Cuboid(&'a Cuboid),
+    ConvexPolygon(&'a ConvexPolygon),
+}
+
+impl<'a> Polytope<'a> {
+    pub fn new(shape: PolytopeShape<'a>, pos: Isometry<Real>) -> Self {
+        Self { shape, pos }
+    }
+
+    /// The vertex with the largest dot product with `dir`, expressed in the local-space of the
+    /// first shape.
+    pub fn support_vertex(&self, dir: &Vector<Real>) -> FeatureId {
+        let local_dir = self.pos.inverse_transform_vector(dir);
+        let vid = match self.shape {
+            PolytopeShape::Cuboid(_) => (0..2)
+                .filter(|i| local_dir[*i] < 0.0)
+                .fold(0, |mask, i| mask | (1 << i)),
+            PolytopeShape::ConvexPolygon(poly) => {
+                utils::point_cloud_support_point_id(&local_dir, poly.points()) as u32
+            }
+        };
+        FeatureId::Vertex(vid)
+    }
+
+    fn vertex(&self, vid: u32) -> Point<Real> {
+        let local = match self.shape {
+            PolytopeShape::Cuboid(cuboid) => {
+                let mut pt = Point::from(cuboid.half_extents);
+                for i in 0..2 {
+                    if vid & (1 << i) != 0 {
+                        pt[i] = -pt[i];
+                    }
+                }
+                pt
+            }
+            PolytopeShape::ConvexPolygon(poly) => poly.points()[vid as usize],
+        };
+        self.pos * local
+    }
+
+    /// The endpoints of the face `fid`, in counterclockwise order.
+    fn face_vertices(&self, fid: u32) -> [u32; 2] {
+        match self.shape {
+            PolytopeShape::Cuboid(_) => {
+                let i = CUBOID_FACES.iter().position(|f| *f == fid).unwrap_or(0);
+                [CUBOID_VERTICES[i], CUBOID_VERTICES[(i + 1) % 4]]
+            }
+            PolytopeShape::ConvexPolygon(poly) => {
+                let n = poly.points().len() as u32;
+                [fid, (fid + 1) % n]
+            }
+        }
+    }
+
+    /// The faces adjacent to the vertex `vid`, in counterclockwise order.
+    fn vertex_faces(&self, vid: u32) -> [u32; 2] {
+        match self.shape {
+            PolytopeShape::Cuboid(_) => {
+                let i = CUBOID_VERTICES.iter().position(|v| *v == vid).unwrap_or(0);
+                [CUBOID_FACES[(i + 3) % 4], CUBOID_FACES[i]]
+            }
+            PolytopeShape::ConvexPolygon(poly) => {
+                let n = poly.points().len() as u32;
+                [(vid + n - 1) % n, vid]
+            }
+        }
+    }
+
+    fn face_normal(&self, fid: u32) -> Vector<Real> {
+        let local = match self.shape {
+            PolytopeShape::Cuboid(_) => {
+                let sign = if fid < 2 { 1.0 } else { -1.0 };
+                Vector::ith(fid as usize % 2, sign)
+            }
+            PolytopeShape::ConvexPolygon(poly) => *poly.normals()[fid as usize],
+        };
+        self.pos * local
+    }
+
+    fn feature_vertices(&self, feature: FeatureId) -> ([u32; 2], usize) {
+        match feature {
+            FeatureId::Vertex(vid) => ([vid, vid], 1),
+            FeatureId::Face(fid) => (self.face_vertices(fid), 2),
+            FeatureId::Unknown => ([0, 0], 0),
+        }
+    }
+
+    /// Projects `pt` on the given feature, returning the projection and the smallest feature
+    /// containing it.
+    fn project_on_feature(&self, feature: FeatureId, pt: &Point<Real>) -> (Point<Real>, FeatureId) {
+        match feature {
+            FeatureId::Face(fid) => {
+                let [va, vb] = self.face_vertices(fid);
+                let (a, b) = (self.vertex(va), self.vertex(vb));
+                let ab = b - a;
+                let t = (pt - a).dot(&ab) / ab.norm_squared();
+
+                if t <= 0.0 {
+                    (a, FeatureId::Vertex(va))
+                } else if t >= 1.0 {
+                    (b, FeatureId::Vertex(vb))
+                } else {
+                    (a + ab * t, feature)
+                }
+            }
+            FeatureId::Vertex(vid) => (self.vertex(vid), feature),
+            FeatureId::Unknown => (*pt, feature),
+        }
+    }
+
+    /// Checks if `pt` lies inside of the Voronoi region of `feature`.
+    ///
+    /// Returns `Ok(None)` if it does, `Ok(Some(neighbor))` with the neighbor feature to move to
+    /// if it doesn’t, and `Err(())` if `pt` is behind the face `feature`.
+    fn voronoi_violation(
+        &self,
+        feature: FeatureId,
+        pt: &Point<Real>,
+    ) -> Result<Option<FeatureId>, ()> {
+        match feature {
+            FeatureId::Vertex(vid) => {
+                let v = self.vertex(vid);
+                let [prev_face, next_face] = self.vertex_faces(vid);
+                let prev = self.vertex(self.face_vertices(prev_face)[0]);
+                let next = self.vertex(self.face_vertices(next_face)[1]);
+
+                if (pt - v).dot(&(prev - v).normalize()) > VORONOI_EPSILON {
+                    Ok(Some(FeatureId::Face(prev_face)))
+                } else if (pt - v).dot(&(next - v).normalize()) > VORONOI_EPSILON {
+                    Ok(Some(FeatureId::Face(next_face)))
+                } else {
+                    Ok(None)
+                }
+            }
+            FeatureId::Face(fid) => {
+                let [va, vb] = self.face_vertices(fid);
+                let (a, b) = (self.vertex(va), self.vertex(vb));
+                let dir = (b - a).normalize();
+
+                if (pt - a).dot(&self.face_normal(fid)) < -VORONOI_EPSILON {
+                    Err(())
+                } else if (pt - a).dot(&dir) < -VORONOI_EPSILON {
+                    Ok(Some(FeatureId::Vertex(va)))
+                } else if (pt - b).dot(&dir) > VORONOI_EPSILON {
+                    Ok(Some(FeatureId::Vertex(vb)))
+                } else {
+                    Ok(None)
+                }
+            }
+            FeatureId::Unknown => Err(()),
+        }
+    }
+}
+
+/// Computes the closest points between the features `f1` of `poly1` and `f2` of `poly2`.
+fn closest_points_between_features(
+    poly1: &Polytope,
+    f1: FeatureId,
+    poly2: &Polytope,
+    f2: FeatureId,
+) -> FeaturePair {
+    let mut best = FeaturePair {
+        dist: Real::MAX,
+        point1: Point::origin(),
+        point2: Point::origin(),
+        feature1: f1,
+        feature2: f2,
+    };
+
+    let (vertices1, len1) = poly1.feature_vertices(f1);
+    let (vertices2, len2) = poly2.feature_vertices(f2);
+
+    for vid1 in &vertices1[..len1] {
+        let pt1 = poly1.vertex(*vid1);
+        let (pt2, sub2) = poly2.project_on_feature(f2, &pt1);
+        best.keep_closest(pt1, pt2, FeatureId::Vertex(*vid1), sub2);
+    }
+
+    for vid2 in &vertices2[..len2] {
+        let pt2 = poly2.vertex(*vid2);
+        let (pt1, sub1) = poly1.project_on_feature(f1, &pt2);
+        best.keep_closest(pt1, pt2, sub1, FeatureId::Vertex(*vid2));
+    }
+
+    if let (FeatureId::Face(fid1), FeatureId::Face(fid2)) = (f1, f2) {
+        let [a1, b1] = poly1.face_vertices(fid1).map(|vid| poly1.vertex(vid));
+        let [a2, b2] = poly2.face_vertices(fid2).map(|vid| poly2.vertex(vid));
+        let locs = closest_points_segment_segment_with_locations_nD((&a1, &b1), (&a2, &b2));
+
+        // Non-parallel segments can only have interior closest points if they intersect.
+        if let (SegmentPointLocation::OnEdge(bcoords1), SegmentPointLocation::OnEdge(bcoords2)) =
+            locs
+        {
+            let pt1 = a1 * bcoords1[0] + b1.coords * bcoords1[1];
+            let pt2 = a2 * bcoords2[0] + b2.coords * bcoords2[1];
+            best.keep_closest(pt1, pt2, f1, f2);
+        }
+    }
+
+    best
+}
+
+/// Walks the features of both polygons, starting from `f1` and `f2`, until reaching the pair of
+/// closest features.
+pub(super) fn walk(
+    poly1: &Polytope,
+    poly2: &Polytope,
+    mut f1: FeatureId,
+    mut f2: FeatureId,
+    max_steps: usize,
+) -> WalkResult {
+    let mut last_pair: Option<FeaturePair> = None;
+
+    for _ in 0..max_steps {
+        let pair = closest_points_between_features(poly1, f1, poly2, f2);
+
+        if let Some(last_pair) = last_pair {
+            if pair.feature1 == last_pair.feature1 && pair.feature2 == last_pair.feature2 {
+                // Moving to the neighbor feature didn’t change anything, so the Voronoi region
+                // violation is only due to rounding errors.
+                return WalkResult::Closest(pair);
+            }
+        }
+
+        if pair.dist <= VORONOI_EPSILON {
+            // The shapes are touching or penetrating.
+            return WalkResult::Failed;
+        }
+
+        match poly1.voronoi_violation(pair.feature1, &pair.point2) {
+            Ok(Some(neighbor)) => {
+                last_pair = Some(pair);
+                f1 = neighbor;
+                f2 = pair.feature2;
+                continue;
+            }
+            Ok(None) => {}
+            Err(()) => return WalkResult::Failed,
+        }
+
+        match poly2.voronoi_violation(pair.feature2, &pair.point1) {
+            Ok(Some(neighbor)) => {
+                last_pair = Some(pair);
+                f1 = pair.feature1;
+                f2 = neighbor;
+                continue;
+            }
+            Ok(None) => {}
+            Err(()) => return WalkResult::Failed,
+        }
+
+        return WalkResult::Closest(pair);
+    }
+
+    WalkResult::Failed
+}
@@ -0,0 +1,457 @@
+use super::closest_feature_tracker::{FeaturePair, WalkResult, VORONOI_EPSILON};
+use crate::math::{Isometry, Point, Real, Vector};
+use crate::query::details::closest_points_segment_segment_with_locations_nD;
+use crate::shape::{ConvexPolyhedron, Cuboid, FeatureId, SegmentPointLocation};
+use crate::utils;
+
+/// A convex polytope with an explicit topology, positioned relative to the first shape of a
+/// closest-feature query.
+///
+/// All the points and vectors returned by its methods are expressed in the local-space of the
+/// first shape.
+pub(super) struct Polytope<'a> {
+    shape: PolytopeShape<'a>,
+    pos: Isometry<Real>,
+}
+
+#[derive(Copy, Clone)]
+pub(super) enum PolytopeShape<'a> {
+    /// The features of a cuboid are numbered as in [`Cuboid::feature_normal`].
+    Cuboid(&'a Cuboid),
+    ConvexPolyhedron(&'a ConvexPolyhedron),
+}
+
+impl<'a> Polytope<'a> {
+    pub fn new(shape: PolytopeShape<'a>, pos: Isometry<Real>) -> Self {
+        Self { shape, pos }
+    }
+
+    /// The vertex with the largest dot product with `dir`, expressed in the local-space of the
+    /// first shape.
+    pub fn support_vertex(&self, dir: &Vector<Real>) -> FeatureId {
+        let local_dir = self.pos.inverse_transform_vector(dir);
+        let vid = match self.shape {
+            PolytopeShape::Cuboid(_) => (0..3)
+                .filter(|i| local_dir[*i] < 0.0)
+                .fold(0, |mask, i| mask | (1 << i)),
+            PolytopeShape::ConvexPolyhedron(poly) => {
+                utils::point_cloud_support_point_id(&local_dir, poly.points()) as u32
+            }
+        };
+        FeatureId::Vertex(vid)
+    }
+
+    fn vertex(&self, vid: u32) -> Point<Real> {
+        let local = match self.shape {
+            PolytopeShape::Cuboid(cuboid) => {
+                let mut pt = Point::from(cuboid.half_extents);
+                for i in 0..3 {
+                    if vid & (1 << i) != 0 {
+                        pt[i] = -pt[i];
+                    }
+                }
+                pt
+            }
+            PolytopeShape::ConvexPolyhedron(poly) => poly.points()[vid as usize],
+        };
+        self.pos * local
+    }
+
+    fn edge_vertices(&self, eid: u32) -> [u32; 2] {
+        match self.shape {
+            PolytopeShape::Cuboid(_) => {
+                let axis = eid & 0b11;
+                let mask = eid >> 2;
+                [mask, mask | (1 << axis)]
+            }
+            PolytopeShape::ConvexPolyhedron(poly) => {
+                let vertices = poly.edges()[eid as usize].vertices;
+                [vertices.x, vertices.y]
+            }
+        }
+    }
+
+    fn edge_faces(&self, eid: u32) -> [u32; 2] {
+        match self.shape {
+            PolytopeShape::Cuboid(_) => {
+                let axis = eid & 0b11;
+                let mask = eid >> 2;
+                let face = |i: u32| i + 3 * ((mask >> i) & 1);
+                [face((axis + 1) % 3), face((axis + 2) % 3)]
+            }
+            PolytopeShape::ConvexPolyhedron(poly) => {
+                let faces = poly.edges()[eid as usize].faces;
+                [faces.x, faces.y]
+            }
+        }
+    }
+
+    fn face_normal(&self, fid: u32) -> Vector<Real> {
+        let local = match self.shape {
+            PolytopeShape::Cuboid(_) => {
+                let sign = if fid < 3 { 1.0 } else { -1.0 };
+                Vector::ith(fid as usize % 3, sign)
+            }
+            PolytopeShape::ConvexPolyhedron(poly) => *poly.faces()[fid as usize].normal,
+        };
+        self.pos * local
+    }
+
+    /// A vertex of the face `fid`.
+    fn face_origin(&self, fid: u32) -> Point<Real> {
+        match self.shape {
+            PolytopeShape::Cuboid(_) => {
+                let axis = fid % 3;
+                self.vertex((fid / 3) << axis)
+            }
+            PolytopeShape::ConvexPolyhedron(poly) => {
+                let face = &poly.faces()[fid as usize];
+                self.vertex(poly.vertices_adj_to_face()[face.first_vertex_or_edge as usize])
+            }
+        }
+    }
+
+    fn for_each_vertex_edge(&self, vid: u32, mut f: impl FnMut(u32)) {
+        match self.shape {
+            PolytopeShape::Cuboid(_) => {
+                for axis in 0..3 {
+                    f(axis | ((vid & !(1 << axis)) << 2));
+                }
+            }
+            PolytopeShape::ConvexPolyhedron(poly) => {
+                let vertex = &poly.vertices()[vid as usize];
+                let first = vertex.first_adj_face_or_edge as usize;
+                let last = first + vertex.num_adj_faces_or_edge as usize;
+                poly.edges_adj_to_vertex()[first..last]
+                    .iter()
+                    .for_each(|eid| f(*eid));
+            }
+        }
+    }
+
+    fn for_each_face_edge(&self, fid: u32, mut f: impl FnMut(u32)) {
+        match self.shape {
+            PolytopeShape::Cuboid(_) => {
+                let axis = fid % 3;
+                let sign_mask = (fid / 3) << axis;
+
+                for dir in [(axis + 1) % 3, (axis + 2) % 3] {
+                    let other = 3 - axis - dir;
+                    f(dir | (sign_mask << 2));
+                    f(dir | ((sign_mask | (1 << other)) << 2));
+                }
+            }
+            PolytopeShape::ConvexPolyhedron(poly) => {
+                let face = &poly.faces()[fid as usize];
+                let first = face.first_vertex_or_edge as usize;
+                let last = first + face.num_vertices_or_edges as usize;
+                poly.edges_adj_to_face()[first..last]
+                    .iter()
+                    .for_each(|eid| f(*eid));
+            }
+        }
+    }
+
+    fn for_each_feature_vertex(&self, feature: FeatureId, mut f: impl FnMut(u32)) {
+        match feature {
+            FeatureId::Vertex(vid) => f(vid),
+            FeatureId::Edge(eid) => self.edge_vertices(eid).into_iter().for_each(f),
+            // Every vertex of the face is visited twice, once for each adjacent edge.
+            FeatureId::Face(fid) => self.for_each_face_edge(fid, |eid| {
+                for vid in self.edge_vertices(eid) {
+                    f(vid)
+                }
+            }),
+            FeatureId::Unknown => {}
+        }
+    }
+
+    fn for_each_feature_edge(&self, feature: FeatureId, mut f: impl FnMut(u32)) {
+        match feature {
+            FeatureId::Edge(eid) => f(eid),
+            FeatureId::Face(fid) => self.for_each_face_edge(fid, f),
+            FeatureId::Vertex(_) | FeatureId::Unknown => {}
+        }
+    }
+
+    fn other_edge_face(&self, eid: u32, fid: u32) -> u32 {
+        let [f1, f2] = self.edge_faces(eid);
+        if f1 == fid {
+            f2
+        } else {
+            f1
+        }
+    }
+
+    /// The unit vector lying on the face `fid`, perpendicular to its edge `eid`, and pointing
+    /// toward the inside of the face.
+    fn edge_inward_perp(&self, fid: u32, eid: u32) -> Vector<Real> {
+        let [a, b] = self.edge_vertices(eid);
+        let dir = self.vertex(b) - self.vertex(a);
+        let perp = self.face_normal(fid).cross(&dir).normalize();
+        // The face lies below the plane of the other face adjacent to the edge.
+        let other_normal = self.face_normal(self.other_edge_face(eid, fid));
+
+        if perp.dot(&other_normal) > 0.0 {
+            -perp
+        } else {
+            perp
+        }
+    }
+
+    fn is_inside_face(&self, fid: u32, pt: &Point<Real>, eps: Real) -> bool {
+        let mut inside = true;
+        self.for_each_face_edge(fid, |eid| {
+            let a = self.vertex(self.edge_vertices(eid)[0]);
+            inside = inside && (pt - a).dot(&self.edge_inward_perp(fid, eid)) >= -eps;
+        });
+        inside
+    }
+
+    /// Projects `pt` on the given feature, returning the projection and the smallest feature
+    /// containing it.
+    fn project_on_feature(&self, feature: FeatureId, pt: &Point<Real>) -> (Point<Real>, FeatureId) {
+        match feature {
+            FeatureId::Edge(eid) => {
+                let [va, vb] = self.edge_vertices(eid);
+                let (a, b) = (self.vertex(va), self.vertex(vb));
+                let ab = b - a;
+                let t = (pt - a).dot(&ab) / ab.norm_squared();
+
+                if t <= 0.0 {
+                    (a, FeatureId::Vertex(va))
+                } else if t >= 1.0 {
+                    (b, FeatureId::Vertex(vb))
+                } else {
+                    (a + ab * t, feature)
+                }
+            }
+            FeatureId::Face(fid) => {
+                if self.is_inside_face(fid, pt, 0.0) {
+                    let n = self.face_normal(fid);
+                    let a = self.face_origin(fid);
+                    (pt - n * (pt - a).dot(&n), feature)
+                } else {
+                    let mut best = (Real::MAX, *pt, feature);
+                    self.for_each_face_edge(fid, |eid| {
+                        let (proj, sub) = self.project_on_feature(FeatureId::Edge(eid), pt);
+                        let dist = na::distance_squared(&proj, pt);
+                        if dist < best.0 {
+                            best = (dist, proj, sub);
+                        }
+                    });
+                    (best.1, best.2)
+                }
+            }
+            FeatureId::Vertex(vid) => (self.vertex(vid), feature),
+            FeatureId::Unknown => (*pt, feature),
+        }
+    }
+
+    /// Does any edge of `feature` cross the face `fid` of `other`?
+    fn pierces_face(&self, feature: FeatureId, other: &Self, fid: u32) -> bool {
+        let n = other.face_normal(fid);
+        let origin = other.face_origin(fid);
+        let mut pierces = false;
+
+        self.for_each_feature_edge(feature, |eid| {
+            let [va, vb] = self.edge_vertices(eid);
+            let (a, b) = (self.vertex(va), self.vertex(vb));
+            let (da, db) = ((a - origin).dot(&n), (b - origin).dot(&n));
+
+            if (da < 0.0) != (db < 0.0) {
+                let crossing = a + (b - a) * (da / (da - db));
+                pierces = pierces || other.is_inside_face(fid, &crossing, 0.0);
+            }
+        });
+
+        pierces
+    }
+
+    /// Checks if `pt` lies inside of the Voronoi region of `feature`.
+    ///
+    /// Returns `Ok(None)` if it does, `Ok(Some(neighbor))` with the neighbor feature to move to
+    /// if it doesn’t, and `Err(())` if `pt` is behind the face `feature`.
+    fn voronoi_violation(
+        &self,
+        feature: FeatureId,
+        pt: &Point<Real>,
+    ) -> Result<Option<FeatureId>, ()> {
+        let mut violation = None;
+
+        match feature {
+            FeatureId::Vertex(vid) => {
+                let v = self.vertex(vid);
+                self.for_each_vertex_edge(vid, |eid| {
+                    let [a, b] = self.edge_vertices(eid);
+                    let other = if a == vid { b } else { a };
+                    let dir = (self.vertex(other) - v).normalize();
+
+                    if violation.is_none() && (pt - v).dot(&dir) > VORONOI_EPSILON {
+                        violation = Some(FeatureId::Edge(eid));
+                    }
+                });
+            }
+            FeatureId::Edge(eid) => {
+                let [va, vb] = self.edge_vertices(eid);
+                let (a, b) = (self.vertex(va), self.vertex(vb));
+                let dir = (b - a).normalize();
+
+                if (pt - a).dot(&dir) < -VORONOI_EPSILON {
+                    return Ok(Some(FeatureId::Vertex(va)));
+                }
+
+                if (pt - b).dot(&dir) > VORONOI_EPSILON {
+                    return Ok(Some(FeatureId::Vertex(vb)));
+                }
+
+                for fid in self.edge_faces(eid) {
+                    if (pt - a).dot(&self.edge_inward_perp(fid, eid)) > VORONOI_EPSILON {
+                        return Ok(Some(FeatureId::Face(fid)));
+                    }
+                }
+            }
+            FeatureId::Face(fid) => {
+                let n = self.face_normal(fid);
+                let origin = self.face_origin(fid);
+
+                if (pt - origin).dot(&n) < -VORONOI_EPSILON {
+                    return Err(());
+                }
+
+                self.for_each_face_edge(fid, |eid| {
+                    let a = self.vertex(self.edge_vertices(eid)[0]);
+                    if violation.is_none()
+                        && (pt - a).dot(&self.edge_inward_perp(fid, eid)) < -VORONOI_EPSILON
+                    {
+                        violation = Some(FeatureId::Edge(eid));
+                    }
+                });
+            }
+            FeatureId::Unknown => return Err(()),
+        }
+
+        Ok(violation)
+    }
+}
+
+/// Computes the closest points between the features `f1` of `poly1` and `f2` of `poly2`.
+///
+/// Returns `None` if an edge of one feature crosses the other feature.
+fn closest_points_between_features(
+    poly1: &Polytope,
+    f1: FeatureId,
+    poly2: &Polytope,
+    f2: FeatureId,
+) -> Option<FeaturePair> {
+    if let FeatureId::Face(fid2) = f2 {
+        if poly1.pierces_face(f1, poly2, fid2) {
+            return None;
+        }
+    }
+
+    if let FeatureId::Face(fid1) = f1 {
+        if poly2.pierces_face(f2, poly1, fid1) {
+            return None;
+        }
+    }
+
+    let mut best = FeaturePair {
+        dist: Real::MAX,
+        point1: Point::origin(),
+        point2: Point::origin(),
+        feature1: f1,
+        feature2: f2,
+    };
+
+    poly1.for_each_feature_vertex(f1, |vid1| {
+        let pt1 = poly1.vertex(vid1);
+        let (pt2, sub2) = poly2.project_on_feature(f2, &pt1);
+        best.keep_closest(pt1, pt2, FeatureId::Vertex(vid1), sub2);
+    });
+
+    poly2.for_each_feature_vertex(f2, |vid2| {
+        let pt2 = poly2.vertex(vid2);
+        let (pt1, sub1) = poly1.project_on_feature(f1, &pt2);
+        best.keep_closest(pt1, pt2, sub1, FeatureId::Vertex(vid2));
+    });
+
+    poly1.for_each_feature_edge(f1, |eid1| {
+        let [a1, b1] = poly1.edge_vertices(eid1).map(|vid| poly1.vertex(vid));
+
+        poly2.for_each_feature_edge(f2, |eid2| {
+            let [a2, b2] = poly2.edge_vertices(eid2).map(|vid| poly2.vertex(vid));
+            let locs = closest_points_segment_segment_with_locations_nD((&a1, &b1), (&a2, &b2));
+
+            // The cases where the closest points are on vertices are handled above.
+            if let (
+                SegmentPointLocation::OnEdge(bcoords1),
+                SegmentPointLocation::OnEdge(bcoords2),
+            ) = locs
+            {
+                let pt1 = a1 * bcoords1[0] + b1.coords * bcoords1[1];
+                let pt2 = a2 * bcoords2[0] + b2.coords * bcoords2[1];
+                best.keep_closest(pt1, pt2, FeatureId::Edge(eid1), FeatureId::Edge(eid2));
+            }
+        });
+    });
+
+    Some(best)
+}
+
+/// Walks the features of both polytopes, starting from `f1` and `f2`, until reaching the pair of
+/// closest features.
+pub(super) fn walk(
+    poly1: &Polytope,
+    poly2: &Polytope,
+    mut f1: FeatureId,
+    mut f2: FeatureId,
+    max_steps: usize,
+) -> WalkResult {
+    let mut last_pair: Option<FeaturePair> = None;
+
+    for _ in 0..max_steps {
+        let Some(pair) = closest_points_between_features(poly1, f1, poly2, f2) else {
+            return WalkResult::Intersecting;
+        };
+
+        if let Some(last_pair) = last_pair {
+            if pair.feature1 == last_pair.feature1 && pair.feature2 == last_pair.feature2 {
+                // Moving to the neighbor feature didn’t change anything, so the Voronoi region
+                // violation is only due to rounding errors (e.g. with parallel edges).
+                return WalkResult::Closest(pair);
+            }
+        }
+
+        if pair.dist <= VORONOI_EPSILON {
+            // The shapes are touching or penetrating.
+            return WalkResult::Failed;
+        }
+
+        match poly1.voronoi_violation(pair.feature1, &pair.point2) {
+            Ok(Some(neighbor)) => {
+                last_pair = Some(pair);
+                f1 = neighbor;
+                f2 = pair.feature2;
+                continue;
+            }
+            Ok(None) => {}
+            Err(()) => return WalkResult::Failed,
+        }
+
+        match poly2.voronoi_violation(pair.feature2, &pair.point1) {
+            Ok(Some(neighbor)) => {
+                last_pair = Some(pair);
+                f1 = pair.feature1;
+                f2 = neighbor;
+                continue;
+            }
+            Ok(None) => {}
+            Err(()) => return WalkResult::Failed,
+        }
+
+        return WalkResult::Closest(pair);
+    }
+
+    WalkResult::Failed
+}
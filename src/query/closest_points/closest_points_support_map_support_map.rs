@@ -1,5 +1,5 @@
 use crate::math::{Isometry, Real, Vector};
-use crate::query::gjk::{self, CSOPoint, GJKResult, VoronoiSimplex};
+use crate::query::gjk::{self, CSOPoint, GJKResult, GjkCache, VoronoiSimplex};
 use crate::query::ClosestPoints;
 use crate::shape::SupportMap;
 
@@ -67,3 +67,22 @@ where
 
     gjk::closest_points(pos12, g1, g2, prediction, true, simplex)
 }
+
+/// Closest points between support-mapped shapes (`Cuboid`, `ConvexHull`, etc.), warm-started
+/// with the simplex from a previous call.
+///
+/// The GJK algorithm starts from the simplex stored in `cache`, and stores its final simplex
+/// back into `cache`. The `cache` must only be used with this pair of shapes.
+pub fn closest_points_support_map_support_map_with_cache<G1, G2>(
+    pos12: &Isometry<Real>,
+    g1: &G1,
+    g2: &G2,
+    prediction: Real,
+    cache: &mut GjkCache,
+) -> GJKResult
+where
+    G1: ?Sized + SupportMap,
+    G2: ?Sized + SupportMap,
+{
+    gjk::closest_points_with_cache(pos12, g1, g2, prediction, true, cache)
+}
@@ -26,6 +26,7 @@ pub use self::closest_points_segment_segment::{
 };
 pub use self::closest_points_shape_shape::closest_points;
 pub use self::closest_points_support_map_support_map::closest_points_support_map_support_map;
+pub use self::closest_points_support_map_support_map::closest_points_support_map_support_map_with_cache;
 pub use self::closest_points_support_map_support_map::closest_points_support_map_support_map_with_params;
 
 mod closest_points;
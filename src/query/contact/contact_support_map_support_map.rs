@@ -1,6 +1,6 @@
 use crate::math::{Isometry, Real, Vector};
 use crate::query::epa::EPA;
-use crate::query::gjk::{self, CSOPoint, GJKResult, VoronoiSimplex};
+use crate::query::gjk::{self, CSOPoint, GJKResult, GjkCache, VoronoiSimplex};
 use crate::query::Contact;
 use crate::shape::SupportMap;
 
@@ -60,7 +60,47 @@ where
     };
 
     simplex.reset(CSOPoint::from_shapes(pos12, g1, g2, &dir));
+    contact_from_simplex(pos12, g1, g2, prediction, simplex)
+}
+
+/// Contact between support-mapped shapes (`Cuboid`, `ConvexHull`, etc.), warm-started with
+/// the simplex from a previous call.
+///
+/// This is the same as [`contact_support_map_support_map_with_params`] except that the GJK
+/// algorithm starts from the simplex stored in `cache`, and stores its final simplex back into
+/// `cache`. When the shapes moved only slightly since the last call, this generally converges
+/// in one or two iterations. The `cache` must only be used with this pair of shapes.
+pub fn contact_support_map_support_map_with_cache<G1, G2>(
+    pos12: &Isometry<Real>,
+    g1: &G1,
+    g2: &G2,
+    prediction: Real,
+    cache: &mut GjkCache,
+) -> GJKResult
+where
+    G1: ?Sized + SupportMap,
+    G2: ?Sized + SupportMap,
+{
+    let simplex = &mut VoronoiSimplex::new();
+    gjk::warm_start_or_reset(pos12, g1, g2, cache, simplex);
+    let result = contact_from_simplex(pos12, g1, g2, prediction, simplex);
+    cache.update(pos12, simplex, &result);
+    result
+}
 
+/// Runs the GJK algorithm from the given initial simplex, with the EPA algorithm as a fallback
+/// in case of penetration.
+fn contact_from_simplex<G1, G2>(
+    pos12: &Isometry<Real>,
+    g1: &G1,
+    g2: &G2,
+    prediction: Real,
+    simplex: &mut VoronoiSimplex,
+) -> GJKResult
+where
+    G1: ?Sized + SupportMap,
+    G2: ?Sized + SupportMap,
+{
     let cpts = gjk::closest_points(pos12, g1, g2, prediction, true, simplex);
     if cpts != GJKResult::Intersection {
         return cpts;
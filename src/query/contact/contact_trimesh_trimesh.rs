@@ -0,0 +1,338 @@
+use crate::bounding_volume::{Aabb, BoundingVolume};
+use crate::math::{Isometry, Point, Real, Vector};
+use crate::query::{Contact, PointQuery, QueryDispatcher, Ray, RayCast};
+use crate::shape::{Segment, TriMesh};
+use alloc::vec::Vec;
+use na::{ComplexField, Unit};
+
+/// The edges of both meshes are sampled with a spacing equal to the largest extent of the
+/// overlap of their AABBs divided by this number.
+const OVERLAP_SUBDIVISIONS: Real = 16.0;
+/// The maximum number of separation directions tested by [`penetration_trimesh_trimesh`].
+const MAX_CANDIDATE_DIRECTIONS: usize = 32;
+
+/// The penetration between two solid triangle meshes, computed by
+/// [`penetration_trimesh_trimesh`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct TriMeshPenetration {
+    /// The direction, in the local-space of the first mesh, along which the second mesh has to
+    /// be translated to separate both meshes.
+    pub normal1: Unit<Vector<Real>>,
+    /// The direction, in the local-space of the second mesh, along which the first mesh has to
+    /// be translated to separate both meshes.
+    pub normal2: Unit<Vector<Real>>,
+    /// The distance the second mesh has to be translated along `normal1` to separate both
+    /// meshes.
+    pub depth: Real,
+    /// The contact points between both meshes, sorted from the deepest to the shallowest.
+    ///
+    /// They all share the normals `normal1` and `normal2`, and their (negative) distances are
+    /// the penetration depths of each contact along these normals. Together, they form a contact
+    /// manifold suitable for pushing the meshes out of each other.
+    pub contacts: Vec<Contact>,
+}
+
+impl TriMeshPenetration {
+    /// The deepest contact point between both meshes.
+    pub fn deepest_contact(&self) -> Option<&Contact> {
+        self.contacts.first()
+    }
+}
+
+/// A point on the boundary of one of the meshes, close to the other mesh.
+struct Sample {
+    /// The sample, in the local-space of the mesh it belongs to.
+    point: Point<Real>,
+    /// Does this sample belong to the first mesh?
+    on_mesh1: bool,
+    /// If this sample is inside of the other mesh, the direction (in the local-space of the
+    /// first mesh) along which the second mesh must be moved to push this sample out by the
+    /// shortest path, and the length of that path.
+    penetration: Option<(Unit<Vector<Real>>, Real)>,
+}
+
+/// Checks if the point-containment test of `mesh` is based on a closed, oriented, surface.
+pub(crate) fn is_solid(mesh: &TriMesh) -> bool {
+    mesh.pseudo_normals_if_oriented().is_some() || mesh.winding_numbers.is_some()
+}
+
+/// Computes the volumetric penetration between two closed triangle meshes.
+///
+/// Unlike [`contact`](crate::query::contact()) which only reports the deepest contact between
+/// individual triangles, this treats both meshes as solids. This requires both meshes to be
+/// closed and to have a reliable point-containment test, i.e., they must have been created with
+/// the [`TriMeshFlags::ORIENTED`](crate::shape::TriMeshFlags::ORIENTED) or
+/// [`TriMeshFlags::WINDING_NUMBERS`](crate::shape::TriMeshFlags::WINDING_NUMBERS) flags.
+/// Otherwise, `None` is returned.
+///
+/// The vertices and edges of each mesh close to the other mesh are sampled. The directions
+/// pushing the deepest samples out of the other mesh are used as candidate separation
+/// directions. The candidate needing the smallest translation for every sample to end up outside
+/// of the other mesh (measured by ray-casting along it) is selected as the global minimum
+/// translation vector. Because only the vertices and edges are sampled, the result is an
+/// approximation when a face of one mesh penetrates the other mesh without any of its edges
+/// doing so.
+///
+/// Returns `None` if no sample of any mesh lies inside of the other mesh.
+pub fn penetration_trimesh_trimesh(
+    pos12: &Isometry<Real>,
+    mesh1: &TriMesh,
+    mesh2: &TriMesh,
+) -> Option<TriMeshPenetration> {
+    if !is_solid(mesh1) || !is_solid(mesh2) {
+        return None;
+    }
+
+    let pos21 = pos12.inverse();
+    let aabb2_1 = mesh2.aabb(pos12);
+    let overlap1 = mesh1.local_aabb().intersection(&aabb2_1)?;
+    // Translating the second mesh by the smallest extent of the overlap along the corresponding
+    // axis separates both AABBs. So no better translation can move a point farther than
+    // `margin` from the other mesh inside of it.
+    let margin = overlap1.extents().max();
+    let spacing = margin / OVERLAP_SUBDIVISIONS;
+
+    let mut samples = Vec::new();
+    collect_samples(
+        mesh1,
+        mesh2,
+        &pos21,
+        &aabb2_1.loosened(margin),
+        spacing,
+        true,
+        &mut samples,
+    );
+    collect_samples(
+        mesh2,
+        mesh1,
+        pos12,
+        &mesh1.aabb(&pos21).loosened(margin),
+        spacing,
+        false,
+        &mut samples,
+    );
+
+    let mut penetrating: Vec<_> = samples
+        .iter()
+        .filter_map(|sample| Some((sample, sample.penetration?)))
+        .collect();
+
+    if penetrating.is_empty() {
+        return None;
+    }
+
+    penetrating.sort_by(|a, b| b.1 .1.total_cmp(&a.1 .1));
+
+    // Select the candidate directions from the deepest samples.
+    let mut candidates: Vec<Unit<Vector<Real>>> = Vec::new();
+    for (_, (normal1, _)) in &penetrating {
+        if candidates.len() == MAX_CANDIDATE_DIRECTIONS {
+            break;
+        }
+
+        if candidates
+            .iter()
+            .all(|n| n.dot(normal1) < crate::utils::COS_1_DEGREES)
+        {
+            candidates.push(*normal1);
+        }
+    }
+
+    // The axis of smallest overlap always separates the meshes, within `margin`.
+    let axis = overlap1.extents().imin();
+    let mut axis_dir = Vector::zeros();
+    axis_dir[axis] = 1.0;
+    candidates.push(Unit::new_unchecked(axis_dir));
+    candidates.push(Unit::new_unchecked(-axis_dir));
+
+    // Find the candidate needing the smallest translation.
+    let mut best: Option<(Real, Unit<Vector<Real>>)> = None;
+    for normal1 in candidates {
+        let max_depth = best.map(|b| b.0).unwrap_or(Real::MAX);
+        let mut depth: Real = 0.0;
+
+        for sample in &samples {
+            depth = depth.max(exit_distance(pos12, mesh1, mesh2, sample, &normal1));
+
+            if depth >= max_depth {
+                break;
+            }
+        }
+
+        if depth < max_depth {
+            best = Some((depth, normal1));
+        }
+    }
+
+    let (depth, normal1) = best?;
+    let normal2 = pos21 * -normal1;
+    let mut contacts: Vec<_> = penetrating
+        .iter()
+        .map(|(sample, _)| {
+            let dist = exit_distance(pos12, mesh1, mesh2, sample, &normal1);
+            let (point1, point2) = if sample.on_mesh1 {
+                (sample.point, pos21 * (sample.point - *normal1 * dist))
+            } else {
+                (pos12 * sample.point + *normal1 * dist, sample.point)
+            };
+            Contact::new(point1, point2, normal1, normal2, -dist)
+        })
+        .collect();
+    contacts.sort_by(|a, b| a.dist.total_cmp(&b.dist));
+
+    Some(TriMeshPenetration {
+        normal1,
+        normal2,
+        depth,
+        contacts,
+    })
+}
+
+/// Collects the vertices of `mesh`, and points along its edges, located inside of `region`.
+///
+/// The `pos` isometry maps the local-space of `mesh` to the local-space of `other`. The
+/// `region` is expressed in the local-space of `mesh`.
+fn collect_samples(
+    mesh: &TriMesh,
+    other: &TriMesh,
+    pos: &Isometry<Real>,
+    region: &Aabb,
+    spacing: Real,
+    on_mesh1: bool,
+    samples: &mut Vec<Sample>,
+) {
+    let mut add_sample = |point: Point<Real>| {
+        if !region.contains_local_point(&point) {
+            return;
+        }
+
+        let local_point = pos * point;
+        let mut penetration = None;
+
+        if other.contains_local_point(&local_point) {
+            let proj = other.project_local_point(&local_point, false).point;
+            // The direction moving `point` toward the boundary of `other`, in the
+            // local-space of `mesh`.
+            if let Some((dir, depth)) =
+                Unit::try_new_and_get(pos.inverse_transform_vector(&(proj - local_point)), 0.0)
+            {
+                let normal1 = if on_mesh1 { -dir } else { dir };
+                penetration = Some((normal1, depth));
+            }
+        }
+
+        samples.push(Sample {
+            point,
+            on_mesh1,
+            penetration,
+        });
+    };
+
+    for pt in mesh.vertices() {
+        add_sample(*pt);
+    }
+
+    for idx in mesh.indices() {
+        for (i, j) in [(0, 1), (1, 2), (2, 0)] {
+            // Each edge shared by two triangles is only sampled once.
+            if idx[i] > idx[j] && mesh.topology().is_some() {
+                continue;
+            }
+
+            let segment = Segment::new(
+                mesh.vertices()[idx[i] as usize],
+                mesh.vertices()[idx[j] as usize],
+            );
+
+            if !segment.local_aabb().intersects(region) {
+                continue;
+            }
+
+            let subdivisions = ComplexField::ceil(segment.length() / spacing) as usize;
+            for k in 1..subdivisions {
+                let t = k as Real / subdivisions as Real;
+                add_sample(segment.a + segment.scaled_direction() * t);
+            }
+        }
+    }
+}
+
+/// The distance the second mesh has to be translated along `normal1` for `sample` to be
+/// outside of the other mesh, and to stay outside of it for any further translation.
+fn exit_distance(
+    pos12: &Isometry<Real>,
+    mesh1: &TriMesh,
+    mesh2: &TriMesh,
+    sample: &Sample,
+    normal1: &Unit<Vector<Real>>,
+) -> Real {
+    // The sample, and its motion relative to the other mesh, in the local-space of the other
+    // mesh.
+    let (other, point, dir) = if sample.on_mesh1 {
+        (
+            mesh2,
+            pos12.inverse_transform_point(&sample.point),
+            pos12.inverse_transform_vector(&-**normal1),
+        )
+    } else {
+        (mesh1, pos12 * sample.point, **normal1)
+    };
+
+    // Cast a ray backward, from a point outside of the other mesh's bounding sphere, to find
+    // the last time the sample exits the other mesh.
+    let aabb = other.local_aabb();
+    let length = na::distance(&aabb.center(), &point) + aabb.half_extents().norm();
+    let ray = Ray::new(point + dir * length, -dir);
+    other
+        .cast_local_ray(&ray, length, false)
+        .map(|toi| length - toi)
+        .unwrap_or(0.0)
+}
+
+/// Contact between two triangle meshes.
+///
+/// If both meshes are closed and oriented (see [`penetration_trimesh_trimesh`]) and
+/// penetrate each other, this returns the deepest contact of their volumetric penetration.
+/// Otherwise, this returns the closest (or deepest) contact between their triangles, like
+/// [`contact_composite_shape_shape`](super::contact_composite_shape_shape()), taking
+/// `prediction` into account.
+///
+/// The contact between triangles is computed first. The penetration is only sampled if the
+/// triangles intersect, or if one mesh is nested inside of the other one, so separated meshes
+/// cost about as much as with `contact_composite_shape_shape`.
+pub fn contact_trimesh_trimesh<D: ?Sized + QueryDispatcher>(
+    dispatcher: &D,
+    pos12: &Isometry<Real>,
+    mesh1: &TriMesh,
+    mesh2: &TriMesh,
+    prediction: Real,
+) -> Option<Contact> {
+    let triangle_contact =
+        super::contact_composite_shape_shape(dispatcher, pos12, mesh1, mesh2, prediction);
+
+    if !is_solid(mesh1) || !is_solid(mesh2) {
+        return triangle_contact;
+    }
+
+    // If the surfaces don’t intersect, the meshes can only overlap if one of them contains the
+    // other, in which case it contains all of its vertices.
+    let surfaces_intersect = triangle_contact.is_some_and(|c| c.dist <= 0.0);
+    let nested = || {
+        mesh2
+            .vertices()
+            .first()
+            .is_some_and(|pt| mesh1.contains_local_point(&(pos12 * pt)))
+            || mesh1
+                .vertices()
+                .first()
+                .is_some_and(|pt| mesh2.contains_local_point(&pos12.inverse_transform_point(pt)))
+    };
+
+    if !surfaces_intersect && !nested() {
+        return triangle_contact;
+    }
+
+    penetration_trimesh_trimesh(pos12, mesh1, mesh2)
+        .and_then(|penetration| penetration.deepest_contact().copied())
+        .or(triangle_contact)
+}
@@ -16,7 +16,14 @@ pub use self::contact_halfspace_support_map::{
 pub use self::contact_shape_shape::contact;
 #[cfg(feature = "alloc")]
 pub use self::contact_support_map_support_map::{
-    contact_support_map_support_map, contact_support_map_support_map_with_params,
+    contact_support_map_support_map, contact_support_map_support_map_with_cache,
+    contact_support_map_support_map_with_params,
+};
+#[cfg(all(feature = "dim3", feature = "alloc"))]
+pub(crate) use self::contact_trimesh_trimesh::is_solid as is_solid_trimesh;
+#[cfg(all(feature = "dim3", feature = "alloc"))]
+pub use self::contact_trimesh_trimesh::{
+    contact_trimesh_trimesh, penetration_trimesh_trimesh, TriMeshPenetration,
 };
 
 mod contact;
@@ -29,3 +36,5 @@ mod contact_halfspace_support_map;
 mod contact_shape_shape;
 #[cfg(feature = "alloc")]
 mod contact_support_map_support_map;
+#[cfg(all(feature = "dim3", feature = "alloc"))]
+mod contact_trimesh_trimesh;
@@ -1,4 +1,5 @@
 use crate::math::{Isometry, Point, Real, Vector};
+use crate::query::gjk::GjkCache;
 use crate::shape::PackedFeatureId;
 #[cfg(feature = "dim3")]
 use alloc::vec::Vec;
@@ -105,6 +106,8 @@ pub struct ContactManifold<ManifoldData, ContactData> {
     /// If the second shape involved is a composite shape, this contains the position of its subshape
     /// involved in this contact.
     pub subshape_pos2: Option<Isometry<Real>>,
+    /// The GJK simplex from the last contact computation between the shapes of this manifold.
+    gjk_cache: GjkCache,
     /// Additional tracked data associated to this contact manifold.
     pub data: ManifoldData,
 }
@@ -131,6 +134,7 @@ impl<ManifoldData, ContactData: Default + Copy> ContactManifold<ManifoldData, Co
             subshape2,
             subshape_pos1: None,
             subshape_pos2: None,
+            gjk_cache: GjkCache::new(),
             data,
         }
     }
@@ -154,6 +158,7 @@ impl<ManifoldData, ContactData: Default + Copy> ContactManifold<ManifoldData, Co
             subshape2: self.subshape2,
             subshape_pos1: self.subshape_pos1,
             subshape_pos2: self.subshape_pos2,
+            gjk_cache: self.gjk_cache,
             data: self.data.clone(),
         }
     }
@@ -172,6 +177,23 @@ impl<ManifoldData, ContactData: Default + Copy> ContactManifold<ManifoldData, Co
     }
     */
 
+    /// The GJK simplex from the last contact computation between the shapes of this manifold.
+    ///
+    /// This is used to warm-start the next contact computation between convex shapes.
+    #[inline]
+    pub fn gjk_cache(&self) -> &GjkCache {
+        &self.gjk_cache
+    }
+
+    /// A mutable reference to the GJK simplex cached by this manifold.
+    ///
+    /// This must be cleared with [`GjkCache::clear`] if one of the shapes of this manifold is
+    /// modified.
+    #[inline]
+    pub fn gjk_cache_mut(&mut self) -> &mut GjkCache {
+        &mut self.gjk_cache
+    }
+
     /// The slice of all the contacts, active or not, on this contact manifold.
     #[inline]
     pub fn contacts(&self) -> &[TrackedContact<ContactData>] {
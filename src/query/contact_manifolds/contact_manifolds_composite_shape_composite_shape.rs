@@ -1,8 +1,9 @@
 use alloc::boxed::Box;
 use alloc::vec::Vec;
 
-use crate::bounding_volume::BoundingVolume;
+use crate::bounding_volume::{Aabb, BoundingVolume};
 use crate::math::{Isometry, Real};
+use crate::partitioning::TraversalAction;
 use crate::query::contact_manifolds::contact_manifolds_workspace::{
     TypedWorkspaceData, WorkspaceData,
 };
@@ -94,15 +95,14 @@ pub fn contact_manifolds_composite_shape_composite_shape<'a, ManifoldData, Conta
         core::mem::swap(&mut ls_aabb1, &mut ls_aabb2);
     }
 
-    // Traverse bvh1 first.
-    let ls_aabb2_1 = ls_aabb2.transform_by(&pos12).loosened(prediction);
     let mut old_manifolds = core::mem::take(manifolds);
 
-    let mut leaf_fn1 = |leaf1: u32| {
+    let mut leaf_pair_fn = |leaf1: u32, leaf2: u32, leaf_aabb2: &Aabb| {
         composite1.map_part_at(leaf1, &mut |part_pos1, part_shape1, normal_constraints1| {
             let pos211 = part_pos1.prepend_to(&pos21); // == pos21 * part_pos1
             let ls_part_aabb1_2 = part_shape1.compute_aabb(&pos211).loosened(prediction);
-            let mut leaf_fn2 = |leaf2: u32| {
+
+            if ls_part_aabb1_2.intersects(leaf_aabb2) {
                 composite2.map_part_at(
                     leaf2,
                     &mut |part_pos2, part_shape2, normal_constraints2| {
@@ -172,17 +172,23 @@ pub fn contact_manifolds_composite_shape_composite_shape<'a, ManifoldData, Conta
                         }
                     },
                 );
-            };
-
-            for leaf_id in composite2.bvh().intersect_aabb(&ls_part_aabb1_2) {
-                leaf_fn2(leaf_id);
             }
         });
     };
 
-    for leaf_id in composite1.bvh().intersect_aabb(&ls_aabb2_1) {
-        leaf_fn1(leaf_id);
-    }
+    // Traverse both trees simultaneously. After the flip above, `bvh2` is the smallest tree so
+    // its AABBs are the ones transformed during the traversal.
+    bvh1.traverse_bvtt(bvh2, &pos12, |node1, node2, node_aabb2_1| {
+        if !node1.aabb().loosened(prediction).intersects(node_aabb2_1) {
+            return TraversalAction::Prune;
+        }
+
+        if let (Some(leaf1), Some(leaf2)) = (node1.leaf_data(), node2.leaf_data()) {
+            leaf_pair_fn(leaf1, leaf2, &node2.aabb());
+        }
+
+        TraversalAction::Continue
+    });
 
     workspace
         .sub_detectors
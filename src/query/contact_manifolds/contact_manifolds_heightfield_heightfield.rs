@@ -0,0 +1,255 @@
+use alloc::{boxed::Box, vec::Vec};
+
+use crate::bounding_volume::{Aabb, BoundingVolume};
+use crate::math::{Isometry, Real};
+use crate::query::contact_manifolds::contact_manifolds_workspace::{
+    TypedWorkspaceData, WorkspaceData,
+};
+use crate::query::contact_manifolds::{ContactManifoldsWorkspace, NormalConstraints};
+use crate::query::query_dispatcher::PersistentQueryDispatcher;
+use crate::query::ContactManifold;
+#[cfg(feature = "dim3")]
+use crate::shape::Triangle;
+#[cfg(feature = "dim2")]
+use crate::shape::{Capsule, Segment};
+use crate::shape::{HeightField, Shape};
+use crate::utils::hashmap::{Entry, HashMap};
+
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Deserialize, rkyv::Serialize),
+    archive(check_bytes)
+)]
+#[derive(Clone)]
+struct SubDetector {
+    manifold_id: usize,
+    timestamp: bool,
+}
+
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[derive(Clone, Default)]
+pub struct HeightFieldHeightFieldContactManifoldsWorkspace {
+    timestamp: bool,
+    sub_detectors: HashMap<(u32, u32), SubDetector>,
+}
+
+impl HeightFieldHeightFieldContactManifoldsWorkspace {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+fn ensure_workspace_exists(workspace: &mut Option<ContactManifoldsWorkspace>) {
+    if workspace
+        .as_ref()
+        .and_then(|w| {
+            w.0.downcast_ref::<HeightFieldHeightFieldContactManifoldsWorkspace>()
+        })
+        .is_some()
+    {
+        return;
+    }
+
+    *workspace = Some(ContactManifoldsWorkspace(Box::new(
+        HeightFieldHeightFieldContactManifoldsWorkspace::new(),
+    )));
+}
+
+/// Applies `f` to each segment of the cells of `heightfield` within the range of cells covered
+/// by the given `aabb`.
+///
+/// Unlike [`HeightField::map_elements_in_local_aabb`], this doesn’t cull the segments based on
+/// their heights.
+#[cfg(feature = "dim2")]
+pub(super) fn map_cells_in_local_aabb(
+    heightfield: &HeightField,
+    aabb: &Aabb,
+    f: &mut impl FnMut(u32, &Segment),
+) {
+    let range = heightfield.unclamped_elements_range_in_local_aabb(aabb);
+    let num_cells = heightfield.num_cells() as isize;
+
+    for i in range.start.clamp(0, num_cells)..range.end.clamp(0, num_cells) {
+        if let Some(segment) = heightfield.segment_at(i as usize) {
+            f(i as u32, &segment);
+        }
+    }
+}
+
+/// Applies `f` to each triangle of the cells of `heightfield` within the range of cells covered
+/// by the given `aabb`.
+///
+/// Unlike [`HeightField::map_elements_in_local_aabb`], this doesn’t cull the triangles based on
+/// their heights.
+#[cfg(feature = "dim3")]
+pub(super) fn map_cells_in_local_aabb(
+    heightfield: &HeightField,
+    aabb: &Aabb,
+    f: &mut impl FnMut(u32, &Triangle),
+) {
+    let (range_i, range_j) = heightfield.unclamped_elements_range_in_local_aabb(aabb);
+    let (ncells_i, ncells_j) = heightfield.num_cells_ij();
+    let (ncells_i, ncells_j) = (ncells_i as isize, ncells_j as isize);
+
+    for j in range_j.start.clamp(0, ncells_j)..range_j.end.clamp(0, ncells_j) {
+        for i in range_i.start.clamp(0, ncells_i)..range_i.end.clamp(0, ncells_i) {
+            let (i, j) = (i as usize, j as usize);
+            let (left, right) = heightfield.triangles_at(i, j);
+
+            if let Some(left) = left {
+                f(heightfield.triangle_id(i, j, true), &left);
+            }
+
+            if let Some(right) = right {
+                f(heightfield.triangle_id(i, j, false), &right);
+            }
+        }
+    }
+}
+
+/// Computes the contact manifolds between two heightfields, both represented as `Shape` trait-objects.
+pub fn contact_manifolds_heightfield_heightfield_shapes<ManifoldData, ContactData>(
+    dispatcher: &dyn PersistentQueryDispatcher<ManifoldData, ContactData>,
+    pos12: &Isometry<Real>,
+    shape1: &dyn Shape,
+    shape2: &dyn Shape,
+    prediction: Real,
+    manifolds: &mut Vec<ContactManifold<ManifoldData, ContactData>>,
+    workspace: &mut Option<ContactManifoldsWorkspace>,
+) where
+    ManifoldData: Default + Clone,
+    ContactData: Default + Copy,
+{
+    if let (Some(heightfield1), Some(heightfield2)) =
+        (shape1.as_heightfield(), shape2.as_heightfield())
+    {
+        contact_manifolds_heightfield_heightfield(
+            dispatcher,
+            pos12,
+            heightfield1,
+            heightfield2,
+            prediction,
+            manifolds,
+            workspace,
+        )
+    }
+}
+
+/// Computes the contact manifolds between two heightfields.
+///
+/// Only the cells of `heightfield1` overlapping the [`Aabb`] of `heightfield2` are visited and,
+/// for each of their triangles (segments in 2D), only the cells of `heightfield2` overlapping
+/// that triangle. One contact manifold is generated per pair of overlapping triangles, and is
+/// kept across calls as long as this pair remains overlapping.
+pub fn contact_manifolds_heightfield_heightfield<ManifoldData, ContactData>(
+    dispatcher: &dyn PersistentQueryDispatcher<ManifoldData, ContactData>,
+    pos12: &Isometry<Real>,
+    heightfield1: &HeightField,
+    heightfield2: &HeightField,
+    prediction: Real,
+    manifolds: &mut Vec<ContactManifold<ManifoldData, ContactData>>,
+    workspace: &mut Option<ContactManifoldsWorkspace>,
+) where
+    ManifoldData: Default + Clone,
+    ContactData: Default + Copy,
+{
+    ensure_workspace_exists(workspace);
+    let workspace: &mut HeightFieldHeightFieldContactManifoldsWorkspace =
+        workspace.as_mut().unwrap().0.downcast_mut().unwrap();
+    let new_timestamp = !workspace.timestamp;
+    workspace.timestamp = new_timestamp;
+
+    /*
+     * Compute interferences.
+     */
+    let pos21 = pos12.inverse();
+    let aabb2_1 = heightfield2.aabb(pos12).loosened(prediction);
+    let mut old_manifolds = core::mem::take(manifolds);
+
+    if let Some(intersection_aabb1) = heightfield1.local_aabb().intersection(&aabb2_1) {
+        map_cells_in_local_aabb(heightfield1, &intersection_aabb1, &mut |leaf1, part1| {
+            #[cfg(feature = "dim2")]
+            let sub_shape1 = Capsule::new(part1.a, part1.b, 0.0); // TODO: use a segment instead.
+            #[cfg(feature = "dim3")]
+            let sub_shape1 = *part1;
+
+            #[cfg(feature = "dim2")]
+            let triangle_normals1 = None::<()>;
+            #[cfg(feature = "dim3")]
+            let triangle_normals1 = heightfield1.triangle_normal_constraints(leaf1);
+            let normal_constraints1 = triangle_normals1
+                .as_ref()
+                .map(|proj| proj as &dyn NormalConstraints);
+
+            let aabb1_2 = part1.compute_aabb(&pos21).loosened(prediction);
+
+            map_cells_in_local_aabb(heightfield2, &aabb1_2, &mut |leaf2, part2| {
+                if !part2.local_aabb().intersects(&aabb1_2) {
+                    return;
+                }
+
+                #[cfg(feature = "dim2")]
+                let sub_shape2 = Capsule::new(part2.a, part2.b, 0.0); // TODO: use a segment instead.
+                #[cfg(feature = "dim3")]
+                let sub_shape2 = *part2;
+
+                let sub_detector = match workspace.sub_detectors.entry((leaf1, leaf2)) {
+                    Entry::Occupied(entry) => {
+                        let sub_detector = entry.into_mut();
+                        let manifold = old_manifolds[sub_detector.manifold_id].take();
+                        sub_detector.manifold_id = manifolds.len();
+                        sub_detector.timestamp = new_timestamp;
+                        manifolds.push(manifold);
+                        sub_detector
+                    }
+                    Entry::Vacant(entry) => {
+                        let sub_detector = SubDetector {
+                            manifold_id: manifolds.len(),
+                            timestamp: new_timestamp,
+                        };
+                        manifolds.push(ContactManifold::with_data(
+                            leaf1,
+                            leaf2,
+                            ManifoldData::default(),
+                        ));
+                        entry.insert(sub_detector)
+                    }
+                };
+
+                #[cfg(feature = "dim2")]
+                let triangle_normals2 = None::<()>;
+                #[cfg(feature = "dim3")]
+                let triangle_normals2 = heightfield2.triangle_normal_constraints(leaf2);
+                let normal_constraints2 = triangle_normals2
+                    .as_ref()
+                    .map(|proj| proj as &dyn NormalConstraints);
+
+                let manifold = &mut manifolds[sub_detector.manifold_id];
+                let _ = dispatcher.contact_manifold_convex_convex(
+                    pos12,
+                    &sub_shape1,
+                    &sub_shape2,
+                    normal_constraints1,
+                    normal_constraints2,
+                    prediction,
+                    manifold,
+                );
+            });
+        });
+    }
+
+    workspace
+        .sub_detectors
+        .retain(|_, detector| detector.timestamp == new_timestamp);
+}
+
+impl WorkspaceData for HeightFieldHeightFieldContactManifoldsWorkspace {
+    fn as_typed_workspace_data(&self) -> TypedWorkspaceData<'_> {
+        TypedWorkspaceData::HeightfieldHeightfieldContactManifoldsWorkspace(self)
+    }
+
+    fn clone_dyn(&self) -> Box<dyn WorkspaceData> {
+        Box::new(self.clone())
+    }
+}
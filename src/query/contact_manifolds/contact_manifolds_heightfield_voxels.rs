@@ -0,0 +1,266 @@
+use alloc::{boxed::Box, vec::Vec};
+
+use super::contact_manifolds_heightfield_heightfield::map_cells_in_local_aabb;
+use crate::bounding_volume::BoundingVolume;
+use crate::math::{Isometry, Real, Translation, Vector, DIM};
+use crate::query::contact_manifolds::contact_manifolds_workspace::{
+    TypedWorkspaceData, WorkspaceData,
+};
+use crate::query::contact_manifolds::{ContactManifoldsWorkspace, NormalConstraints};
+use crate::query::query_dispatcher::PersistentQueryDispatcher;
+use crate::query::ContactManifold;
+#[cfg(feature = "dim2")]
+use crate::shape::Capsule;
+use crate::shape::{AxisMask, Cuboid, HeightField, Shape, VoxelState, VoxelType, Voxels};
+use crate::utils::hashmap::{Entry, HashMap};
+
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Deserialize, rkyv::Serialize),
+    archive(check_bytes)
+)]
+#[derive(Clone)]
+struct SubDetector {
+    manifold_id: usize,
+    timestamp: bool,
+}
+
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[derive(Clone, Default)]
+pub struct HeightFieldVoxelsContactManifoldsWorkspace {
+    timestamp: bool,
+    sub_detectors: HashMap<(u32, u32), SubDetector>,
+}
+
+impl HeightFieldVoxelsContactManifoldsWorkspace {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+fn ensure_workspace_exists(workspace: &mut Option<ContactManifoldsWorkspace>) {
+    if workspace
+        .as_ref()
+        .and_then(|w| {
+            w.0.downcast_ref::<HeightFieldVoxelsContactManifoldsWorkspace>()
+        })
+        .is_some()
+    {
+        return;
+    }
+
+    *workspace = Some(ContactManifoldsWorkspace(Box::new(
+        HeightFieldVoxelsContactManifoldsWorkspace::new(),
+    )));
+}
+
+/// Checks if a contact normal, pointing outward the voxel with the given state, exits the voxel
+/// through one of its free faces.
+///
+/// Contacts exiting through a face shared with a neighbor voxel are caused by internal edges and
+/// must be ignored.
+fn is_normal_on_free_face(state: VoxelState, local_n: &Vector<Real>) -> bool {
+    let axis = local_n.iamax();
+
+    if local_n[axis] == 0.0 {
+        return false;
+    }
+
+    let bit = 2 * axis + (local_n[axis] < 0.0) as usize;
+    debug_assert!(bit < 2 * DIM);
+    state
+        .free_faces()
+        .contains(AxisMask::from_bits_truncate(1 << bit))
+}
+
+/// Computes the contact manifolds between an heightfield and voxels, both represented as `Shape` trait-objects.
+pub fn contact_manifolds_heightfield_voxels_shapes<ManifoldData, ContactData>(
+    dispatcher: &dyn PersistentQueryDispatcher<ManifoldData, ContactData>,
+    pos12: &Isometry<Real>,
+    shape1: &dyn Shape,
+    shape2: &dyn Shape,
+    prediction: Real,
+    manifolds: &mut Vec<ContactManifold<ManifoldData, ContactData>>,
+    workspace: &mut Option<ContactManifoldsWorkspace>,
+) where
+    ManifoldData: Default + Clone,
+    ContactData: Default + Copy,
+{
+    if let (Some(heightfield1), Some(voxels2)) = (shape1.as_heightfield(), shape2.as_voxels()) {
+        contact_manifolds_heightfield_voxels(
+            dispatcher,
+            pos12,
+            heightfield1,
+            voxels2,
+            prediction,
+            manifolds,
+            workspace,
+            false,
+        )
+    } else if let (Some(voxels1), Some(heightfield2)) =
+        (shape1.as_voxels(), shape2.as_heightfield())
+    {
+        contact_manifolds_heightfield_voxels(
+            dispatcher,
+            &pos12.inverse(),
+            heightfield2,
+            voxels1,
+            prediction,
+            manifolds,
+            workspace,
+            true,
+        )
+    }
+}
+
+/// Computes the contact manifolds between an heightfield and voxels.
+///
+/// Only the cells of `heightfield1` overlapping the [`Aabb`](crate::bounding_volume::Aabb) of
+/// `voxels2` are visited and, for each of their triangles (segments in 2D), only the voxels
+/// overlapping that triangle. One contact manifold is generated per pair of overlapping
+/// triangle and voxel, and is kept across calls as long as this pair remains overlapping.
+///
+/// Contacts with a normal exiting a voxel through a face shared with one of its neighbors are
+/// discarded to avoid the internal edges problem.
+pub fn contact_manifolds_heightfield_voxels<ManifoldData, ContactData>(
+    dispatcher: &dyn PersistentQueryDispatcher<ManifoldData, ContactData>,
+    pos12: &Isometry<Real>,
+    heightfield1: &HeightField,
+    voxels2: &Voxels,
+    prediction: Real,
+    manifolds: &mut Vec<ContactManifold<ManifoldData, ContactData>>,
+    workspace: &mut Option<ContactManifoldsWorkspace>,
+    flipped: bool,
+) where
+    ManifoldData: Default + Clone,
+    ContactData: Default + Copy,
+{
+    ensure_workspace_exists(workspace);
+    let workspace: &mut HeightFieldVoxelsContactManifoldsWorkspace =
+        workspace.as_mut().unwrap().0.downcast_mut().unwrap();
+    let new_timestamp = !workspace.timestamp;
+    workspace.timestamp = new_timestamp;
+
+    /*
+     * Compute interferences.
+     */
+    let pos21 = pos12.inverse();
+    let aabb2 = voxels2.local_aabb();
+    let aabb2_1 = aabb2.transform_by(pos12).loosened(prediction);
+    let voxel2 = Cuboid::new(voxels2.voxel_size() / 2.0);
+    let mut old_manifolds = core::mem::take(manifolds);
+
+    if let Some(intersection_aabb1) = heightfield1.local_aabb().intersection(&aabb2_1) {
+        map_cells_in_local_aabb(heightfield1, &intersection_aabb1, &mut |leaf1, part1| {
+            let Some(part_aabb2) = part1
+                .compute_aabb(&pos21)
+                .loosened(prediction)
+                .intersection(&aabb2)
+            else {
+                return;
+            };
+
+            #[cfg(feature = "dim2")]
+            let sub_shape1 = Capsule::new(part1.a, part1.b, 0.0); // TODO: use a segment instead.
+            #[cfg(feature = "dim3")]
+            let sub_shape1 = *part1;
+
+            #[cfg(feature = "dim2")]
+            let triangle_normals1 = None::<()>;
+            #[cfg(feature = "dim3")]
+            let triangle_normals1 = heightfield1.triangle_normal_constraints(leaf1);
+            let normal_constraints1 = triangle_normals1
+                .as_ref()
+                .map(|proj| proj as &dyn NormalConstraints);
+
+            for vox2 in voxels2.voxels_intersecting_local_aabb(&part_aabb2) {
+                let vox_type2 = vox2.state.voxel_type();
+
+                // TODO: would be nice to have a strategy to handle interior voxels for depenetration.
+                if vox_type2 == VoxelType::Empty || vox_type2 == VoxelType::Interior {
+                    continue;
+                }
+
+                let leaf2 = vox2.linear_id;
+                let sub_detector = match workspace.sub_detectors.entry((leaf1, leaf2)) {
+                    Entry::Occupied(entry) => {
+                        let sub_detector = entry.into_mut();
+                        let manifold = old_manifolds[sub_detector.manifold_id].take();
+                        sub_detector.manifold_id = manifolds.len();
+                        sub_detector.timestamp = new_timestamp;
+                        manifolds.push(manifold);
+                        sub_detector
+                    }
+                    Entry::Vacant(entry) => {
+                        let sub_detector = SubDetector {
+                            manifold_id: manifolds.len(),
+                            timestamp: new_timestamp,
+                        };
+                        let (id1, id2) = if flipped {
+                            (leaf2, leaf1)
+                        } else {
+                            (leaf1, leaf2)
+                        };
+                        manifolds.push(ContactManifold::with_data(
+                            id1,
+                            id2,
+                            ManifoldData::default(),
+                        ));
+                        entry.insert(sub_detector)
+                    }
+                };
+
+                let manifold = &mut manifolds[sub_detector.manifold_id];
+                let voxel_pos2 = Isometry::from(vox2.center);
+                let voxel_pos12 = pos12 * Translation::from(vox2.center.coords);
+
+                // NOTE: the local normal of the voxel is expressed in the local-space of the
+                //       voxels shape since the voxel’s sub-shape position is a pure translation.
+                let voxel_n2 = if flipped {
+                    manifold.subshape_pos1 = Some(voxel_pos2);
+                    let _ = dispatcher.contact_manifold_convex_convex(
+                        &voxel_pos12.inverse(),
+                        &voxel2,
+                        &sub_shape1,
+                        None,
+                        normal_constraints1,
+                        prediction,
+                        manifold,
+                    );
+                    manifold.local_n1
+                } else {
+                    manifold.subshape_pos2 = Some(voxel_pos2);
+                    let _ = dispatcher.contact_manifold_convex_convex(
+                        &voxel_pos12,
+                        &sub_shape1,
+                        &voxel2,
+                        normal_constraints1,
+                        None,
+                        prediction,
+                        manifold,
+                    );
+                    manifold.local_n2
+                };
+
+                if !is_normal_on_free_face(vox2.state, &voxel_n2) {
+                    manifold.points.clear();
+                }
+            }
+        });
+    }
+
+    workspace
+        .sub_detectors
+        .retain(|_, detector| detector.timestamp == new_timestamp);
+}
+
+impl WorkspaceData for HeightFieldVoxelsContactManifoldsWorkspace {
+    fn as_typed_workspace_data(&self) -> TypedWorkspaceData<'_> {
+        TypedWorkspaceData::HeightfieldVoxelsContactManifoldsWorkspace(self)
+    }
+
+    fn clone_dyn(&self) -> Box<dyn WorkspaceData> {
+        Box::new(self.clone())
+    }
+}
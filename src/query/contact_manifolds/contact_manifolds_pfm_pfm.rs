@@ -1,12 +1,7 @@
 use crate::math::{Isometry, Real};
 use crate::query::contact_manifolds::{NormalConstraints, NormalConstraintsPair};
-use crate::query::{
-    self,
-    gjk::{GJKResult, VoronoiSimplex},
-    ContactManifold, TrackedContact,
-};
+use crate::query::{self, gjk::GJKResult, ContactManifold, TrackedContact};
 use crate::shape::{PackedFeatureId, PolygonalFeature, PolygonalFeatureMap, Shape};
-use na::Unit;
 
 /// Computes the contact manifold between two convex shapes implementing the `PolygonalSupportMap`
 /// trait, both represented as `Shape` trait-objects.
@@ -64,15 +59,14 @@ pub fn contact_manifold_pfm_pfm<'a, ManifoldData, ContactData, S1, S2>(
         return;
     }
 
-    let init_dir = Unit::try_new(manifold.local_n1, crate::math::DEFAULT_EPSILON);
+    // Warm-start the GJK algorithm with the simplex from the last frame.
     let total_prediction = prediction + border_radius1 + border_radius2;
-    let contact = query::details::contact_support_map_support_map_with_params(
+    let contact = query::details::contact_support_map_support_map_with_cache(
         pos12,
         pfm1,
         pfm2,
         total_prediction,
-        &mut VoronoiSimplex::new(),
-        init_dir,
+        manifold.gjk_cache_mut(),
     );
 
     let old_manifold_points = manifold.points.clone();
@@ -154,6 +148,7 @@ pub fn contact_manifold_pfm_pfm<'a, ManifoldData, ContactData, S1, S2>(
         _ => {
             // Reset the cached direction.
             manifold.local_n1.fill(0.0);
+            manifold.gjk_cache_mut().clear();
         }
     }
 
@@ -6,8 +6,10 @@ use downcast_rs::{impl_downcast, DowncastSync};
 use crate::query::contact_manifolds::{
     CompositeShapeCompositeShapeContactManifoldsWorkspace,
     CompositeShapeShapeContactManifoldsWorkspace,
-    HeightFieldCompositeShapeContactManifoldsWorkspace, HeightFieldShapeContactManifoldsWorkspace,
-    TriMeshShapeContactManifoldsWorkspace, VoxelsShapeContactManifoldsWorkspace,
+    HeightFieldCompositeShapeContactManifoldsWorkspace,
+    HeightFieldHeightFieldContactManifoldsWorkspace, HeightFieldShapeContactManifoldsWorkspace,
+    HeightFieldVoxelsContactManifoldsWorkspace, TriMeshShapeContactManifoldsWorkspace,
+    VoxelsShapeContactManifoldsWorkspace,
 };
 
 #[derive(Copy, Clone)]
@@ -34,6 +36,12 @@ pub enum TypedWorkspaceData<'a> {
     VoxelsCompositeShapeContactManifoldsWorkspace(&'a VoxelsShapeContactManifoldsWorkspace<3>),
     /// A voxels vs. voxels workspace.
     VoxelsVoxelsContactManifoldsWorkspace(&'a VoxelsShapeContactManifoldsWorkspace<4>),
+    /// A heightfield vs. heightfield workspace.
+    HeightfieldHeightfieldContactManifoldsWorkspace(
+        &'a HeightFieldHeightFieldContactManifoldsWorkspace,
+    ),
+    /// A heightfield vs. voxels workspace.
+    HeightfieldVoxelsContactManifoldsWorkspace(&'a HeightFieldVoxelsContactManifoldsWorkspace),
     /// A custom workspace.
     Custom,
 }
@@ -54,6 +62,10 @@ enum DeserializableWorkspaceData {
     VoxelsShapeContactManifoldsWorkspace(VoxelsShapeContactManifoldsWorkspace<2>),
     VoxelsCompositeShapeContactManifoldsWorkspace(VoxelsShapeContactManifoldsWorkspace<3>),
     VoxelsVoxelsContactManifoldsWorkspace(VoxelsShapeContactManifoldsWorkspace<4>),
+    HeightfieldHeightfieldContactManifoldsWorkspace(
+        HeightFieldHeightFieldContactManifoldsWorkspace,
+    ),
+    HeightfieldVoxelsContactManifoldsWorkspace(HeightFieldVoxelsContactManifoldsWorkspace),
     #[allow(dead_code)]
     Custom,
 }
@@ -86,6 +98,12 @@ impl DeserializableWorkspaceData {
             DeserializableWorkspaceData::VoxelsVoxelsContactManifoldsWorkspace(w) => {
                 Some(ContactManifoldsWorkspace(Box::new(w)))
             }
+            DeserializableWorkspaceData::HeightfieldHeightfieldContactManifoldsWorkspace(w) => {
+                Some(ContactManifoldsWorkspace(Box::new(w)))
+            }
+            DeserializableWorkspaceData::HeightfieldVoxelsContactManifoldsWorkspace(w) => {
+                Some(ContactManifoldsWorkspace(Box::new(w)))
+            }
             DeserializableWorkspaceData::Custom => None,
         }
     }
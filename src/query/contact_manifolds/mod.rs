@@ -23,9 +23,15 @@ pub use self::contact_manifolds_halfspace_pfm::{
     contact_manifold_halfspace_pfm, contact_manifold_halfspace_pfm_shapes,
 };
 pub use self::contact_manifolds_heightfield_composite_shape::contact_manifolds_heightfield_composite_shape;
+pub use self::contact_manifolds_heightfield_heightfield::{
+    contact_manifolds_heightfield_heightfield, contact_manifolds_heightfield_heightfield_shapes,
+};
 pub use self::contact_manifolds_heightfield_shape::{
     contact_manifolds_heightfield_shape, contact_manifolds_heightfield_shape_shapes,
 };
+pub use self::contact_manifolds_heightfield_voxels::{
+    contact_manifolds_heightfield_voxels, contact_manifolds_heightfield_voxels_shapes,
+};
 pub use self::contact_manifolds_pfm_pfm::{
     contact_manifold_pfm_pfm, contact_manifold_pfm_pfm_shapes,
 };
@@ -55,7 +61,9 @@ use {
     self::contact_manifolds_composite_shape_composite_shape::CompositeShapeCompositeShapeContactManifoldsWorkspace,
     self::contact_manifolds_composite_shape_shape::CompositeShapeShapeContactManifoldsWorkspace,
     self::contact_manifolds_heightfield_composite_shape::HeightFieldCompositeShapeContactManifoldsWorkspace,
+    self::contact_manifolds_heightfield_heightfield::HeightFieldHeightFieldContactManifoldsWorkspace,
     self::contact_manifolds_heightfield_shape::HeightFieldShapeContactManifoldsWorkspace,
+    self::contact_manifolds_heightfield_voxels::HeightFieldVoxelsContactManifoldsWorkspace,
     self::contact_manifolds_trimesh_shape::TriMeshShapeContactManifoldsWorkspace,
 };
 
@@ -70,7 +78,9 @@ mod contact_manifolds_cuboid_cuboid;
 mod contact_manifolds_cuboid_triangle;
 mod contact_manifolds_halfspace_pfm;
 mod contact_manifolds_heightfield_composite_shape;
+mod contact_manifolds_heightfield_heightfield;
 mod contact_manifolds_heightfield_shape;
+mod contact_manifolds_heightfield_voxels;
 mod contact_manifolds_pfm_pfm;
 mod contact_manifolds_trimesh_shape;
 mod contact_manifolds_voxels_ball;
@@ -1,5 +1,6 @@
 use crate::math::{Isometry, Point, Real, Vector};
 use crate::query::details::ShapeCastOptions;
+use crate::query::stats::{self, Query};
 use crate::query::{
     self, details::NonlinearShapeCastMode, ClosestPoints, Contact, NonlinearRigidMotion,
     QueryDispatcher, ShapeCastHit, Unsupported,
@@ -10,7 +11,9 @@ use crate::query::{
     query_dispatcher::PersistentQueryDispatcher,
     ContactManifold,
 };
-use crate::shape::{HalfSpace, Segment, Shape, ShapeType};
+#[cfg(feature = "alloc")]
+use crate::shape::ShapeType;
+use crate::shape::{HalfSpace, Segment, Shape};
 #[cfg(feature = "alloc")]
 use alloc::vec::Vec;
 
@@ -25,6 +28,8 @@ impl QueryDispatcher for DefaultQueryDispatcher {
         shape1: &dyn Shape,
         shape2: &dyn Shape,
     ) -> Result<bool, Unsupported> {
+        stats::record_query(Query::IntersectionTest);
+
         if let (Some(b1), Some(b2)) = (shape1.as_ball(), shape2.as_ball()) {
             let p12 = Point::from(pos12.translation.vector);
             Ok(query::details::intersection_test_ball_ball(&p12, b1, b2))
@@ -97,6 +102,8 @@ impl QueryDispatcher for DefaultQueryDispatcher {
         shape1: &dyn Shape,
         shape2: &dyn Shape,
     ) -> Result<Real, Unsupported> {
+        stats::record_query(Query::Distance);
+
         let ball1 = shape1.as_ball();
         let ball2 = shape2.as_ball();
 
@@ -154,6 +161,8 @@ impl QueryDispatcher for DefaultQueryDispatcher {
         shape2: &dyn Shape,
         prediction: Real,
     ) -> Result<Option<Contact>, Unsupported> {
+        stats::record_query(Query::Contact);
+
         let ball1 = shape1.as_ball();
         let ball2 = shape2.as_ball();
 
@@ -189,7 +198,20 @@ impl QueryDispatcher for DefaultQueryDispatcher {
                 return Ok(query::details::contact_support_map_support_map(
                     pos12, s1, s2, prediction,
                 ));
-            } else if let Some(c1) = shape1.as_composite_shape() {
+            }
+
+            #[cfg(all(feature = "dim3", feature = "alloc"))]
+            if let (Some(m1), Some(m2)) = (shape1.as_trimesh(), shape2.as_trimesh()) {
+                // Non-solid meshes keep the contact between their triangles.
+                if query::contact::is_solid_trimesh(m1) && query::contact::is_solid_trimesh(m2) {
+                    return Ok(query::details::contact_trimesh_trimesh(
+                        self, pos12, m1, m2, prediction,
+                    ));
+                }
+            }
+
+            #[cfg(feature = "alloc")]
+            if let Some(c1) = shape1.as_composite_shape() {
                 return Ok(query::details::contact_composite_shape_shape(
                     self, pos12, c1, shape2, prediction,
                 ));
@@ -210,6 +232,8 @@ impl QueryDispatcher for DefaultQueryDispatcher {
         shape2: &dyn Shape,
         max_dist: Real,
     ) -> Result<ClosestPoints, Unsupported> {
+        stats::record_query(Query::ClosestPoints);
+
         let ball1 = shape1.as_ball();
         let ball2 = shape2.as_ball();
 
@@ -287,6 +311,8 @@ impl QueryDispatcher for DefaultQueryDispatcher {
         shape2: &dyn Shape,
         options: ShapeCastOptions,
     ) -> Result<Option<ShapeCastHit>, Unsupported> {
+        stats::record_query(Query::CastShapes);
+
         if let (Some(b1), Some(b2)) = (shape1.as_ball(), shape2.as_ball()) {
             Ok(query::details::cast_shapes_ball_ball(
                 pos12,
@@ -396,6 +422,8 @@ impl QueryDispatcher for DefaultQueryDispatcher {
         end_time: Real,
         stop_at_penetration: bool,
     ) -> Result<Option<ShapeCastHit>, Unsupported> {
+        stats::record_query(Query::CastShapesNonlinear);
+
         if let (Some(sm1), Some(sm2)) = (shape1.as_support_map(), shape2.as_support_map()) {
             let mode = if stop_at_penetration {
                 NonlinearShapeCastMode::StopAtPenetration
@@ -485,8 +513,24 @@ where
     ) -> Result<(), Unsupported> {
         use crate::query::contact_manifolds::*;
 
+        stats::record_query(Query::ContactManifolds);
+
         let composite1 = shape1.as_composite_shape();
         let composite2 = shape2.as_composite_shape();
+        let (type1, type2) = (shape1.shape_type(), shape2.shape_type());
+
+        // The generators for pairs of convex shapes are recorded by `contact_manifold_convex_convex`.
+        if composite1.is_some()
+            || composite2.is_some()
+            || [type1, type2].iter().any(|ty| {
+                matches!(
+                    ty,
+                    ShapeType::TriMesh | ShapeType::HeightField | ShapeType::Voxels
+                )
+            })
+        {
+            stats::record_contact_manifold_generator(type1, type2);
+        }
 
         if let (Some(composite1), Some(composite2)) = (composite1, composite2) {
             contact_manifolds_composite_shape_composite_shape(
@@ -496,12 +540,23 @@ where
             return Ok(());
         }
 
-        match (shape1.shape_type(), shape2.shape_type()) {
+        match (type1, type2) {
             (ShapeType::TriMesh, _) | (_, ShapeType::TriMesh) => {
                 contact_manifolds_trimesh_shape_shapes(
                     self, pos12, shape1, shape2, prediction, manifolds, workspace,
                 );
             }
+            (ShapeType::HeightField, ShapeType::HeightField) => {
+                contact_manifolds_heightfield_heightfield_shapes(
+                    self, pos12, shape1, shape2, prediction, manifolds, workspace,
+                );
+            }
+            (ShapeType::HeightField, ShapeType::Voxels)
+            | (ShapeType::Voxels, ShapeType::HeightField) => {
+                contact_manifolds_heightfield_voxels_shapes(
+                    self, pos12, shape1, shape2, prediction, manifolds, workspace,
+                );
+            }
             (ShapeType::HeightField, _) => {
                 if let Some(composite2) = composite2 {
                     contact_manifolds_heightfield_composite_shape(
@@ -606,7 +661,10 @@ where
     ) -> Result<(), Unsupported> {
         use crate::query::contact_manifolds::*;
 
-        match (shape1.shape_type(), shape2.shape_type()) {
+        let (type1, type2) = (shape1.shape_type(), shape2.shape_type());
+        stats::record_contact_manifold_generator(type1, type2);
+
+        match (type1, type2) {
             (ShapeType::Ball, ShapeType::Ball) => {
                 contact_manifold_ball_ball_shapes(pos12, shape1, shape2, prediction, manifold)
             }
@@ -0,0 +1,163 @@
+use crate::math::{Point, Real, DEFAULT_EPSILON};
+use crate::utils::{Interval, IntervalFunction};
+use alloc::vec::Vec;
+
+/// The cubic polynomial `k[0] + k[1] * t + k[2] * t² + k[3] * t³`.
+struct Cubic([Real; 4]);
+
+impl Cubic {
+    /// The signed volume (times six) of the tetrahedron formed by four points moving linearly
+    /// from `start` (at `t = 0`) to `end` (at `t = 1`).
+    ///
+    /// This vanishes exactly when the four points are coplanar.
+    fn tetrahedron_volume(start: &[Point<Real>; 4], end: &[Point<Real>; 4]) -> Self {
+        // Each edge of the tetrahedron is a vector `e0 + t * de`.
+        let e = |i: usize| {
+            (
+                start[i] - start[0],
+                (end[i] - end[0]) - (start[i] - start[0]),
+            )
+        };
+        let (u0, du) = e(1);
+        let (v0, dv) = e(2);
+        let (w0, dw) = e(3);
+
+        // cross(u, v) = c0 + t * c1 + t² * c2
+        let c0 = u0.cross(&v0);
+        let c1 = u0.cross(&dv) + du.cross(&v0);
+        let c2 = du.cross(&dv);
+
+        Self([
+            c0.dot(&w0),
+            c0.dot(&dw) + c1.dot(&w0),
+            c1.dot(&dw) + c2.dot(&w0),
+            c2.dot(&dw),
+        ])
+    }
+
+    /// Checks if this cubic is guaranteed not to vanish on `[0, 1]`.
+    ///
+    /// The control points of the Bernstein form of a polynomial enclose its graph on `[0, 1]`,
+    /// so there is no root if they all have the same sign.
+    fn has_no_root_in_unit_interval(&self) -> bool {
+        let [k0, k1, k2, k3] = self.0;
+        let bernstein = [
+            k0,
+            k0 + k1 / 3.0,
+            k0 + k1 * (2.0 / 3.0) + k2 / 3.0,
+            k0 + k1 + k2 + k3,
+        ];
+        bernstein.iter().all(|b| *b > 0.0) || bernstein.iter().all(|b| *b < 0.0)
+    }
+}
+
+impl IntervalFunction<Real> for Cubic {
+    fn eval(&self, t: Real) -> Real {
+        let [k0, k1, k2, k3] = self.0;
+        ((k3 * t + k2) * t + k1) * t + k0
+    }
+
+    fn eval_interval(&self, t: Interval<Real>) -> Interval<Real> {
+        let [k0, k1, k2, k3] = self.0;
+        ((t * k3 + k2) * t + k1) * t + k0
+    }
+
+    fn eval_interval_gradient(&self, t: Interval<Real>) -> Interval<Real> {
+        let [_, k1, k2, k3] = self.0;
+        (t * (3.0 * k3) + 2.0 * k2) * t + k1
+    }
+}
+
+/// Finds the smallest time `t` in `[0, 1]` such that the four points moving linearly from
+/// `start` to `end` are coplanar and `is_hit(t, tolerance)` returns `true`.
+///
+/// Each coplanarity time is refined until the points move by less than `tolerance` over its
+/// uncertainty. If this precision can’t be reached, the tolerance given to `is_hit` is enlarged
+/// by the distance the points may move over the remaining uncertainty. If the points remain
+/// coplanar during the whole motion, only `t = 0` is checked.
+pub(super) fn first_coplanarity_time(
+    start: &[Point<Real>; 4],
+    end: &[Point<Real>; 4],
+    tolerance: Real,
+    mut is_hit: impl FnMut(Real, Real) -> bool,
+) -> Option<Real> {
+    let mut cubic = Cubic::tetrahedron_volume(start, end);
+
+    // Normalize the cubic so that the root-finding tolerances don’t depend on the size of the
+    // primitives. The volume is bounded by the product of the lengths of the edges it is
+    // computed from.
+    let edge_length = |i: usize| Real::max((start[i] - start[0]).norm(), (end[i] - end[0]).norm());
+    let max_volume = edge_length(1) * edge_length(2) * edge_length(3);
+    let magnitude: Real = cubic.0.iter().fold(0.0, |acc, k| acc.max(k.abs()));
+
+    if magnitude <= DEFAULT_EPSILON * max_volume {
+        // The points are coplanar during the whole motion.
+        return is_hit(0.0, tolerance).then_some(0.0);
+    }
+
+    cubic.0.iter_mut().for_each(|k| *k /= magnitude);
+
+    if cubic.has_no_root_in_unit_interval() {
+        return None;
+    }
+
+    let mut roots = Vec::new();
+    let mut candidates = Vec::new();
+    crate::utils::find_root_intervals_to(
+        &cubic,
+        Interval(0.0, 1.0),
+        1.0e-5,
+        1.0e-7,
+        100,
+        &mut roots,
+        &mut candidates,
+    );
+
+    // The largest relative displacement between two of the points during the whole motion.
+    let mut max_speed: Real = 0.0;
+    for i in 0..4 {
+        for j in i + 1..4 {
+            max_speed = max_speed.max(((end[i] - start[i]) - (end[j] - start[j])).norm());
+        }
+    }
+
+    roots.sort_by(|a, b| a.0.total_cmp(&b.0));
+    roots.into_iter().find_map(|root| {
+        let root = refine_root(&cubic, root, tolerance / max_speed);
+        let slack = max_speed * (root.width() / 2.0 + Real::EPSILON);
+        let t = root.midpoint();
+        is_hit(t, tolerance + slack).then_some(t)
+    })
+}
+
+/// Narrows the interval `root` by bisection until its width is smaller than `max_width`.
+///
+/// The interval is only narrowed if the cubic changes sign over it.
+fn refine_root(cubic: &Cubic, root: Interval<Real>, max_width: Real) -> Interval<Real> {
+    let Interval(mut a, mut b) = root;
+    let mut fa = cubic.eval(a);
+
+    if fa * cubic.eval(b) > 0.0 {
+        return root;
+    }
+
+    while b - a > max_width {
+        let mid = (a + b) / 2.0;
+
+        if mid <= a || mid >= b {
+            // We reached the floating-point precision.
+            break;
+        }
+
+        let fmid = cubic.eval(mid);
+
+        if fa * fmid <= 0.0 {
+            b = mid;
+        } else {
+            a = mid;
+            fa = fmid;
+        }
+    }
+
+    Interval(a, b)
+}
@@ -0,0 +1,103 @@
+use super::coplanarity::first_coplanarity_time;
+use crate::math::{Point, Real};
+use crate::query::details::closest_points_segment_segment_with_locations_nD;
+use crate::query::PointQuery;
+use crate::shape::{Segment, Triangle};
+
+#[inline]
+fn lerp(start: &Point<Real>, end: &Point<Real>, t: Real) -> Point<Real> {
+    start + (end - start) * t
+}
+
+#[inline]
+fn lerp_triangle(start: &Triangle, end: &Triangle, t: Real) -> Triangle {
+    Triangle::new(
+        lerp(&start.a, &end.a, t),
+        lerp(&start.b, &end.b, t),
+        lerp(&start.c, &end.c, t),
+    )
+}
+
+#[inline]
+fn lerp_segment(start: &Segment, end: &Segment, t: Real) -> Segment {
+    Segment::new(lerp(&start.a, &end.a, t), lerp(&start.b, &end.b, t))
+}
+
+/// Computes the first time when a point hits a triangle, both moving linearly.
+///
+/// The point moves linearly from `point_start` at `t = 0` to `point_end` at `t = 1`, and each
+/// vertex of the triangle moves linearly from its position in `triangle_start` to its position in
+/// `triangle_end`. The triangle may therefore deform during the motion.
+///
+/// Returns the smallest time `t` in `[0, 1]` where the point is coplanar with the triangle and
+/// at a distance smaller than `tolerance` from it. A small positive `tolerance` is needed to
+/// compensate for numerical errors. It is enlarged for fast motions where the time of impact
+/// can’t be computed precisely enough for the primitives to move by less than `tolerance` over
+/// its uncertainty. If the point and the triangle remain coplanar during the
+/// whole motion, only their configuration at `t = 0` is tested; such motions are generally
+/// detected by [`cast_deforming_segment_segment`] on the triangle’s edges instead.
+pub fn cast_deforming_point_triangle(
+    point_start: &Point<Real>,
+    point_end: &Point<Real>,
+    triangle_start: &Triangle,
+    triangle_end: &Triangle,
+    tolerance: Real,
+) -> Option<Real> {
+    let start = [
+        triangle_start.a,
+        triangle_start.b,
+        triangle_start.c,
+        *point_start,
+    ];
+    let end = [triangle_end.a, triangle_end.b, triangle_end.c, *point_end];
+
+    first_coplanarity_time(&start, &end, tolerance, |t, tolerance| {
+        let triangle = lerp_triangle(triangle_start, triangle_end, t);
+        let point = lerp(point_start, point_end, t);
+        triangle.distance_to_local_point(&point, true) <= tolerance
+    })
+}
+
+/// Computes the first time when two segments hit each other, both moving linearly.
+///
+/// Each endpoint of both segments moves linearly from its position in `segment1_start`
+/// (resp. `segment2_start`) at `t = 0` to its position in `segment1_end` (resp. `segment2_end`)
+/// at `t = 1`.
+///
+/// Returns the smallest time `t` in `[0, 1]` where both segments are coplanar and at a distance
+/// smaller than `tolerance` from each other. A small positive `tolerance` is needed to
+/// compensate for numerical errors. It is enlarged for fast motions, as for
+/// [`cast_deforming_point_triangle`]. If the segments remain coplanar (for example because they
+/// are parallel) during the whole motion, only their configuration at `t = 0` is tested.
+pub fn cast_deforming_segment_segment(
+    segment1_start: &Segment,
+    segment1_end: &Segment,
+    segment2_start: &Segment,
+    segment2_end: &Segment,
+    tolerance: Real,
+) -> Option<Real> {
+    let start = [
+        segment1_start.a,
+        segment1_start.b,
+        segment2_start.a,
+        segment2_start.b,
+    ];
+    let end = [
+        segment1_end.a,
+        segment1_end.b,
+        segment2_end.a,
+        segment2_end.b,
+    ];
+
+    first_coplanarity_time(&start, &end, tolerance, |t, tolerance| {
+        let segment1 = lerp_segment(segment1_start, segment1_end, t);
+        let segment2 = lerp_segment(segment2_start, segment2_end, t);
+        let (loc1, loc2) = closest_points_segment_segment_with_locations_nD(
+            (&segment1.a, &segment1.b),
+            (&segment2.a, &segment2.b),
+        );
+        let pt1 = segment1.point_at(&loc1);
+        let pt2 = segment2.point_at(&loc2);
+        na::distance(&pt1, &pt2) <= tolerance
+    })
+}
@@ -0,0 +1,168 @@
+use super::{cast_deforming_point_triangle, cast_deforming_segment_segment};
+use crate::bounding_volume::{Aabb, BoundingVolume};
+use crate::math::{Isometry, Point, Real};
+use crate::partitioning::{Bvh, BvhBuildStrategy, TraversalAction};
+use crate::shape::{FeatureId, TriMesh, Triangle};
+use alloc::vec::Vec;
+
+/// The first impact between two triangles of deforming meshes, computed by
+/// [`cast_deforming_trimesh_trimesh`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DeformingTriMeshHit {
+    /// The index of the triangle of the first mesh.
+    pub triangle1: u32,
+    /// The index of the triangle of the second mesh.
+    pub triangle2: u32,
+    /// The time of impact, in `[0, 1]`.
+    pub time_of_impact: Real,
+    /// The feature of the first triangle involved in the impact.
+    ///
+    /// This is either a vertex, an edge, or the face of the triangle. Vertices and edges are
+    /// numbered like in [`Triangle::vertices`] and [`Triangle::edges`].
+    pub feature1: FeatureId,
+    /// The feature of the second triangle involved in the impact.
+    ///
+    /// This is either a vertex, an edge, or the face of the triangle. Vertices and edges are
+    /// numbered like in [`Triangle::vertices`] and [`Triangle::edges`].
+    pub feature2: FeatureId,
+}
+
+/// Computes the first impacts between the triangles of two deforming meshes.
+///
+/// The vertices of each mesh move linearly from their current position (given by
+/// [`TriMesh::vertices`]) at `t = 0` to their position in `end_vertices1` (resp.
+/// `end_vertices2`) at `t = 1`. Both meshes, as well as the end vertices, must be expressed in
+/// the same coordinate frame.
+///
+/// The potentially colliding triangle pairs are found with a traversal of BVHs built from the
+/// AABBs swept by each triangle during the motion. Each candidate pair then goes through the six
+/// vertex–face tests performed by [`cast_deforming_point_triangle`] and the nine edge–edge tests
+/// performed by [`cast_deforming_segment_segment`]. One [`DeformingTriMeshHit`] is reported for
+/// each pair of triangles that hit each other, with the earliest time of impact among all
+/// these tests.
+///
+/// # Panics
+///
+/// Panics if `end_vertices1` (resp. `end_vertices2`) doesn’t have the same length as the vertex
+/// buffer of `mesh1` (resp. `mesh2`).
+pub fn cast_deforming_trimesh_trimesh(
+    mesh1: &TriMesh,
+    end_vertices1: &[Point<Real>],
+    mesh2: &TriMesh,
+    end_vertices2: &[Point<Real>],
+    tolerance: Real,
+) -> Vec<DeformingTriMeshHit> {
+    assert_eq!(mesh1.vertices().len(), end_vertices1.len());
+    assert_eq!(mesh2.vertices().len(), end_vertices2.len());
+
+    let bvh1 = swept_triangles_bvh(mesh1, end_vertices1, tolerance);
+    let bvh2 = swept_triangles_bvh(mesh2, end_vertices2, tolerance);
+    let mut result = Vec::new();
+
+    bvh1.traverse_bvtt(&bvh2, &Isometry::identity(), |node1, node2, aabb2| {
+        if !node1.aabb().intersects(aabb2) {
+            return TraversalAction::Prune;
+        }
+
+        if let (Some(tri1), Some(tri2)) = (node1.leaf_data(), node2.leaf_data()) {
+            let start1 = mesh1.triangle(tri1);
+            let end1 = end_triangle(mesh1, end_vertices1, tri1);
+            let start2 = mesh2.triangle(tri2);
+            let end2 = end_triangle(mesh2, end_vertices2, tri2);
+
+            if let Some((time_of_impact, feature1, feature2)) =
+                cast_deforming_triangle_triangle(&start1, &end1, &start2, &end2, tolerance)
+            {
+                result.push(DeformingTriMeshHit {
+                    triangle1: tri1,
+                    triangle2: tri2,
+                    time_of_impact,
+                    feature1,
+                    feature2,
+                });
+            }
+        }
+
+        TraversalAction::Continue
+    });
+
+    result
+}
+
+fn end_triangle(mesh: &TriMesh, end_vertices: &[Point<Real>], i: u32) -> Triangle {
+    let idx = mesh.indices()[i as usize];
+    Triangle::new(
+        end_vertices[idx[0] as usize],
+        end_vertices[idx[1] as usize],
+        end_vertices[idx[2] as usize],
+    )
+}
+
+fn swept_triangles_bvh(mesh: &TriMesh, end_vertices: &[Point<Real>], tolerance: Real) -> Bvh {
+    let leaves = mesh.indices().iter().enumerate().map(|(i, idx)| {
+        let start = idx.iter().map(|k| &mesh.vertices()[*k as usize]);
+        let end = idx.iter().map(|k| &end_vertices[*k as usize]);
+        (
+            i,
+            Aabb::from_points_ref(start.chain(end)).loosened(tolerance),
+        )
+    });
+    Bvh::from_iter(BvhBuildStrategy::Binned, leaves)
+}
+
+fn cast_deforming_triangle_triangle(
+    start1: &Triangle,
+    end1: &Triangle,
+    start2: &Triangle,
+    end2: &Triangle,
+    tolerance: Real,
+) -> Option<(Real, FeatureId, FeatureId)> {
+    let mut best: Option<(Real, FeatureId, FeatureId)> = None;
+    let mut keep_earliest = |toi: Option<Real>, feature1, feature2| {
+        if let Some(toi) = toi {
+            if best.map(|(best_toi, _, _)| toi < best_toi).unwrap_or(true) {
+                best = Some((toi, feature1, feature2));
+            }
+        }
+    };
+
+    // Vertex–face tests.
+    for i in 0..3 {
+        let toi = cast_deforming_point_triangle(
+            &start1.vertices()[i],
+            &end1.vertices()[i],
+            start2,
+            end2,
+            tolerance,
+        );
+        keep_earliest(toi, FeatureId::Vertex(i as u32), FeatureId::Face(0));
+
+        let toi = cast_deforming_point_triangle(
+            &start2.vertices()[i],
+            &end2.vertices()[i],
+            start1,
+            end1,
+            tolerance,
+        );
+        keep_earliest(toi, FeatureId::Face(0), FeatureId::Vertex(i as u32));
+    }
+
+    // Edge–edge tests.
+    let (start_edges1, end_edges1) = (start1.edges(), end1.edges());
+    let (start_edges2, end_edges2) = (start2.edges(), end2.edges());
+
+    for i in 0..3 {
+        for j in 0..3 {
+            let toi = cast_deforming_segment_segment(
+                &start_edges1[i],
+                &end_edges1[i],
+                &start_edges2[j],
+                &end_edges2[j],
+                tolerance,
+            );
+            keep_earliest(toi, FeatureId::Edge(i as u32), FeatureId::Edge(j as u32));
+        }
+    }
+
+    best
+}
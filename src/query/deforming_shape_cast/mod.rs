@@ -0,0 +1,12 @@
+//! Continuous collision detection between primitives whose vertices move linearly.
+
+pub use self::deforming_shape_cast_primitives::{
+    cast_deforming_point_triangle, cast_deforming_segment_segment,
+};
+pub use self::deforming_shape_cast_trimesh_trimesh::{
+    cast_deforming_trimesh_trimesh, DeformingTriMeshHit,
+};
+
+mod coplanarity;
+mod deforming_shape_cast_primitives;
+mod deforming_shape_cast_trimesh_trimesh;
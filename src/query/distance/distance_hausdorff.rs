@@ -0,0 +1,197 @@
+use crate::math::{Isometry, Point, Real};
+use crate::shape::{Shape, TriMesh, Triangle};
+
+#[cfg(not(feature = "std"))]
+use na::ComplexField;
+
+/// Statistics about the distances from the surface of a triangle mesh to another shape.
+///
+/// This is computed by [`hausdorff_distance_trimesh_shape`] and
+/// [`hausdorff_distance_trimesh_trimesh`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SurfaceDistance {
+    /// The largest distance found, i.e., the (one-sided or symmetric) Hausdorff distance.
+    pub max: Real,
+    /// The mean distance, weighted by surface area.
+    pub mean: Real,
+    /// The root mean square distance, weighted by surface area.
+    pub rms: Real,
+    /// The point on the first shape where the largest distance is reached, in world-space.
+    pub worst_point1: Point<Real>,
+    /// The point on the second shape that is the closest to [`Self::worst_point1`], in
+    /// world-space.
+    pub worst_point2: Point<Real>,
+}
+
+/// The maximum number of subdivisions of each triangle edge, bounding the number of samples per
+/// triangle when the sample spacing is tiny compared to the triangle.
+const MAX_SUBDIVISIONS: Real = 256.0;
+
+#[derive(Default)]
+struct SurfaceDistanceAccumulator {
+    max: Option<(Real, Point<Real>, Point<Real>)>,
+    weighted_sum: Real,
+    weighted_sq_sum: Real,
+    total_weight: Real,
+}
+
+impl SurfaceDistanceAccumulator {
+    fn add_sample(&mut self, pt1: Point<Real>, pt2: Point<Real>, weight: Real) {
+        let dist = na::distance(&pt1, &pt2);
+
+        if self.max.map(|(max, _, _)| dist > max).unwrap_or(true) {
+            self.max = Some((dist, pt1, pt2));
+        }
+
+        self.weighted_sum += dist * weight;
+        self.weighted_sq_sum += dist * dist * weight;
+        self.total_weight += weight;
+    }
+
+    /// Samples the surface of `mesh1` and projects each sample on `shape2`.
+    fn sample(
+        &mut self,
+        pos1: &Isometry<Real>,
+        mesh1: &TriMesh,
+        pos2: &Isometry<Real>,
+        shape2: &dyn Shape,
+        sample_spacing: Real,
+    ) {
+        assert!(
+            sample_spacing > 0.0 && sample_spacing.is_finite(),
+            "the sample spacing must be positive and finite"
+        );
+
+        let mut add_sample = |pt: Point<Real>, weight: Real| {
+            let pt1 = pos1 * pt;
+            let pt2 = shape2.project_point(pos2, &pt1, false).point;
+            self.add_sample(pt1, pt2, weight);
+        };
+
+        // The vertices are where the largest distance is generally reached but they don’t
+        // contribute to the averages.
+        for pt in mesh1.vertices() {
+            add_sample(*pt, 0.0);
+        }
+
+        for tri in mesh1.triangles() {
+            sample_triangle(&tri, sample_spacing, &mut add_sample);
+        }
+    }
+
+    fn finish(self, flipped: bool) -> SurfaceDistance {
+        let (max, mut worst_point1, mut worst_point2) =
+            self.max.unwrap_or((0.0, Point::origin(), Point::origin()));
+
+        if flipped {
+            core::mem::swap(&mut worst_point1, &mut worst_point2);
+        }
+
+        let (mean, rms) = if self.total_weight > 0.0 {
+            (
+                self.weighted_sum / self.total_weight,
+                (self.weighted_sq_sum / self.total_weight).sqrt(),
+            )
+        } else {
+            (max, max)
+        };
+
+        SurfaceDistance {
+            max,
+            mean,
+            rms,
+            worst_point1,
+            worst_point2,
+        }
+    }
+}
+
+/// Calls `f` on the barycenter of each sub-triangle of a regular subdivision of `tri` with
+/// edges no longer than `spacing`, together with the sub-triangle’s area.
+///
+/// Each edge is split into at most [`MAX_SUBDIVISIONS`] segments.
+fn sample_triangle(tri: &Triangle, spacing: Real, f: &mut impl FnMut(Point<Real>, Real)) {
+    let max_edge_length = tri
+        .edges()
+        .iter()
+        .map(|edge| edge.length())
+        .fold(0.0, Real::max);
+    let n = (max_edge_length / spacing)
+        .ceil()
+        .clamp(1.0, MAX_SUBDIVISIONS) as usize;
+    let inv_n = 1.0 / n as Real;
+    let weight = tri.area() * inv_n * inv_n;
+    let ab = (tri.b - tri.a) * inv_n;
+    let ac = (tri.c - tri.a) * inv_n;
+    let point_at = |u: Real, v: Real| tri.a + ab * u + ac * v;
+
+    for i in 0..n {
+        for j in 0..n - i {
+            let (u, v) = (i as Real, j as Real);
+            f(point_at(u + 1.0 / 3.0, v + 1.0 / 3.0), weight);
+
+            if i + j + 1 < n {
+                f(point_at(u + 2.0 / 3.0, v + 2.0 / 3.0), weight);
+            }
+        }
+    }
+}
+
+/// Computes the one-sided Hausdorff distance, as well as the mean and RMS distances, from the
+/// surface of `mesh1` to the boundary of `shape2`.
+///
+/// The surface of `mesh1` is sampled at its vertices and on a regular subdivision of each
+/// triangle with edges no longer than `sample_spacing`. Each sample is then projected on
+/// `shape2`, which is accelerated by its BVH if it is a composite shape like a [`TriMesh`].
+/// The result is therefore an approximation that converges to the exact values as
+/// `sample_spacing` decreases.
+///
+/// # Panics
+///
+/// Panics if `sample_spacing` isn’t positive and finite. Each triangle edge is split into at
+/// most 256 segments, so spacings smaller than 1/256 of a triangle’s longest edge don’t
+/// increase the sampling density of that triangle further.
+pub fn hausdorff_distance_trimesh_shape(
+    pos1: &Isometry<Real>,
+    mesh1: &TriMesh,
+    pos2: &Isometry<Real>,
+    shape2: &dyn Shape,
+    sample_spacing: Real,
+) -> SurfaceDistance {
+    let mut acc = SurfaceDistanceAccumulator::default();
+    acc.sample(pos1, mesh1, pos2, shape2, sample_spacing);
+    acc.finish(false)
+}
+
+/// Computes the symmetric Hausdorff distance, as well as the mean and RMS distances, between
+/// the surfaces of two triangle meshes.
+///
+/// Both surfaces are sampled as described in [`hausdorff_distance_trimesh_shape`]. The mean
+/// and RMS distances are weighted by the area of both surfaces.
+///
+/// # Panics
+///
+/// Panics if `sample_spacing` isn’t positive and finite.
+pub fn hausdorff_distance_trimesh_trimesh(
+    pos1: &Isometry<Real>,
+    mesh1: &TriMesh,
+    pos2: &Isometry<Real>,
+    mesh2: &TriMesh,
+    sample_spacing: Real,
+) -> SurfaceDistance {
+    let mut acc12 = SurfaceDistanceAccumulator::default();
+    let mut acc21 = SurfaceDistanceAccumulator::default();
+    acc12.sample(pos1, mesh1, pos2, mesh2, sample_spacing);
+    acc21.sample(pos2, mesh2, pos1, mesh1, sample_spacing);
+
+    let flipped = acc21.max.map(|m| m.0) > acc12.max.map(|m| m.0);
+    let (mut acc, other) = if flipped {
+        (acc21, acc12)
+    } else {
+        (acc12, acc21)
+    };
+    acc.weighted_sum += other.weighted_sum;
+    acc.weighted_sq_sum += other.weighted_sq_sum;
+    acc.total_weight += other.total_weight;
+    acc.finish(flipped)
+}
@@ -1,5 +1,5 @@
 use crate::math::{Isometry, Real, Vector};
-use crate::query::gjk::{self, CSOPoint, GJKResult, VoronoiSimplex};
+use crate::query::gjk::{self, CSOPoint, GJKResult, GjkCache, VoronoiSimplex};
 use crate::shape::SupportMap;
 
 use na::{self, Unit};
@@ -42,7 +42,42 @@ where
         ));
     }
 
-    match gjk::closest_points(pos12, g1, g2, Real::max_value(), true, simplex) {
+    gjk_result_to_distance(gjk::closest_points(
+        pos12,
+        g1,
+        g2,
+        Real::max_value(),
+        true,
+        simplex,
+    ))
+}
+
+/// Distance between support-mapped shapes, warm-started with the simplex from a previous call.
+///
+/// The GJK algorithm starts from the simplex stored in `cache`, and stores its final simplex
+/// back into `cache`. The `cache` must only be used with this pair of shapes.
+pub fn distance_support_map_support_map_with_cache<G1, G2>(
+    pos12: &Isometry<Real>,
+    g1: &G1,
+    g2: &G2,
+    cache: &mut GjkCache,
+) -> Real
+where
+    G1: ?Sized + SupportMap,
+    G2: ?Sized + SupportMap,
+{
+    gjk_result_to_distance(gjk::closest_points_with_cache(
+        pos12,
+        g1,
+        g2,
+        Real::max_value(),
+        true,
+        cache,
+    ))
+}
+
+fn gjk_result_to_distance(result: GJKResult) -> Real {
+    match result {
         GJKResult::Intersection => 0.0,
         GJKResult::ClosestPoints(p1, p2, _) => na::distance(&p1, &p2),
         GJKResult::Proximity(_) => unreachable!(),
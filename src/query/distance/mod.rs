@@ -13,9 +13,14 @@ pub use self::distance_cuboid_cuboid::distance_cuboid_cuboid;
 pub use self::distance_halfspace_support_map::{
     distance_halfspace_support_map, distance_support_map_halfspace,
 };
+#[cfg(all(feature = "dim3", feature = "alloc"))]
+pub use self::distance_hausdorff::{
+    hausdorff_distance_trimesh_shape, hausdorff_distance_trimesh_trimesh, SurfaceDistance,
+};
 pub use self::distance_segment_segment::distance_segment_segment;
 pub use self::distance_support_map_support_map::{
-    distance_support_map_support_map, distance_support_map_support_map_with_params,
+    distance_support_map_support_map, distance_support_map_support_map_with_cache,
+    distance_support_map_support_map_with_params,
 };
 
 mod distance;
@@ -25,5 +30,7 @@ mod distance_ball_convex_polyhedron;
 mod distance_composite_shape_shape;
 mod distance_cuboid_cuboid;
 mod distance_halfspace_support_map;
+#[cfg(all(feature = "dim3", feature = "alloc"))]
+mod distance_hausdorff;
 mod distance_segment_segment;
 mod distance_support_map_support_map;
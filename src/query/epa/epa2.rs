@@ -8,6 +8,7 @@ use num::Bounded;
 
 use crate::math::{Isometry, Point, Real, Vector};
 use crate::query::gjk::{self, CSOPoint, ConstantOrigin, VoronoiSimplex};
+use crate::query::stats::IterationCounter;
 use crate::shape::SupportMap;
 use crate::utils;
 
@@ -281,6 +282,7 @@ impl EPA {
         }
 
         let mut niter = 0;
+        let mut iterations = IterationCounter::epa();
         let mut max_dist = Real::max_value();
         let mut best_face_id = *self.heap.peek().unwrap();
         let mut old_dist = 0.0;
@@ -296,6 +298,7 @@ impl EPA {
                 continue;
             }
 
+            iterations.step();
             let cso_point = CSOPoint::from_shapes(pos12, g1, g2, &face.normal);
             let support_point_id = self.vertices.len();
             self.vertices.push(cso_point);
@@ -2,6 +2,7 @@
 
 use crate::math::{Isometry, Point, Real, Vector};
 use crate::query::gjk::{self, CSOPoint, ConstantOrigin, VoronoiSimplex};
+use crate::query::stats::IterationCounter;
 use crate::query::PointQueryWithLocation;
 use crate::shape::{SupportMap, Triangle, TrianglePointLocation};
 use crate::utils;
@@ -330,6 +331,7 @@ impl EPA {
         }
 
         let mut niter = 0;
+        let mut iterations = IterationCounter::epa();
         let mut max_dist = Real::max_value();
         let mut best_face_id = *self.heap.peek()?;
         let mut old_dist = 0.0;
@@ -345,6 +347,7 @@ impl EPA {
                 continue;
             }
 
+            iterations.step();
             let cso_point = CSOPoint::from_shapes(pos12, g1, g2, &face.normal);
             let support_point_id = self.vertices.len();
             self.vertices.push(cso_point);
@@ -2,10 +2,11 @@
 
 use na::{self, ComplexField, Unit};
 
-use crate::query::gjk::{CSOPoint, ConstantOrigin, VoronoiSimplex};
+use crate::query::gjk::{CSOPoint, ConstantOrigin, GjkCache, VoronoiSimplex};
 use crate::shape::SupportMap;
 // use query::Proximity;
 use crate::math::{Isometry, Point, Real, Vector, DIM};
+use crate::query::stats::IterationCounter;
 use crate::query::{self, Ray};
 
 use num::{Bounded, Zero};
@@ -110,8 +111,10 @@ where
     let mut max_bound = Real::max_value();
     let mut dir;
     let mut niter = 0;
+    let mut iterations = IterationCounter::gjk();
 
     loop {
+        iterations.step();
         let old_max_bound = max_bound;
 
         if let Some((new_dir, dist)) = Unit::try_new_and_get(-proj.coords, _eps_tol) {
@@ -182,6 +185,55 @@ where
     }
 }
 
+/// Projects the origin on a shape using the Separating Axis GJK algorithm, warm-started with the
+/// simplex from a previous run.
+///
+/// This is the same as [`closest_points`] except that the initial simplex is taken from `cache`
+/// instead of being provided by the caller. If `cache` is empty, the simplex is initialized with
+/// the support point along the cached separating axis or, if there is none, along the
+/// direction between the shapes’ origins. The final simplex and separating axis are stored
+/// back into `cache` for subsequent calls with the same pair of shapes.
+pub fn closest_points_with_cache<G1, G2>(
+    pos12: &Isometry<Real>,
+    g1: &G1,
+    g2: &G2,
+    max_dist: Real,
+    exact_dist: bool,
+    cache: &mut GjkCache,
+) -> GJKResult
+where
+    G1: ?Sized + SupportMap,
+    G2: ?Sized + SupportMap,
+{
+    let mut simplex = VoronoiSimplex::new();
+    warm_start_or_reset(pos12, g1, g2, cache, &mut simplex);
+    let result = closest_points(pos12, g1, g2, max_dist, exact_dist, &mut simplex);
+    cache.update(pos12, &simplex, &result);
+    result
+}
+
+/// Initializes `simplex` from `cache` if it isn’t empty, or from a single support point otherwise.
+pub(crate) fn warm_start_or_reset<G1, G2>(
+    pos12: &Isometry<Real>,
+    g1: &G1,
+    g2: &G2,
+    cache: &GjkCache,
+    simplex: &mut VoronoiSimplex,
+) where
+    G1: ?Sized + SupportMap,
+    G2: ?Sized + SupportMap,
+{
+    if cache.warm_start(pos12, simplex) {
+        return;
+    }
+
+    let dir = cache
+        .separating_axis()
+        .or_else(|| Unit::try_new(pos12.translation.vector, crate::math::DEFAULT_EPSILON))
+        .unwrap_or_else(Vector::x_axis);
+    simplex.reset(CSOPoint::from_shapes(pos12, g1, g2, &dir));
+}
+
 /// Casts a ray on a support map using the GJK algorithm.
 pub fn cast_local_ray<G: ?Sized + SupportMap>(
     shape: &G,
@@ -269,8 +321,10 @@ where
     let mut dir;
     let mut niter = 0;
     let mut last_chance = false;
+    let mut iterations = IterationCounter::gjk();
 
     loop {
+        iterations.step();
         let old_max_bound = max_bound;
 
         if let Some((new_dir, dist)) = Unit::try_new_and_get(-proj.coords, _eps_tol) {
@@ -0,0 +1,104 @@
+use crate::math::{Isometry, Point, Real, Vector, DIM};
+use crate::query::gjk::{CSOPoint, GJKResult, VoronoiSimplex};
+use na::Unit;
+
+/// Data cached between successive runs of the GJK algorithm on the same pair of shapes.
+///
+/// Because shapes generally move only slightly between two consecutive frames, the simplex
+/// found by the GJK algorithm on one frame is a very good starting point for the next one. This
+/// cache stores the vertices of that simplex, expressed in the local-space of each shape so they
+/// remain valid after the shapes moved, as well as the last separating axis found. The GJK
+/// variants accepting a `GjkCache` (e.g. [`closest_points_with_cache`](super::closest_points_with_cache))
+/// then typically converge in one or two iterations.
+///
+/// A cache must only be used with the pair of shapes it was filled with, in the same order. It
+/// must be cleared with [`GjkCache::clear`] if any of these shapes is modified.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub struct GjkCache {
+    /// The simplex vertices on the first shape, in its local-space.
+    points1: [Point<Real>; DIM + 1],
+    /// The simplex vertices on the second shape, in its local-space.
+    points2: [Point<Real>; DIM + 1],
+    /// The number of cached simplex vertices.
+    len: usize,
+    /// The last separating axis (or contact normal), in the local-space of the first shape.
+    separating_axis: Option<Unit<Vector<Real>>>,
+}
+
+impl Default for GjkCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GjkCache {
+    /// Creates a new empty cache.
+    pub fn new() -> Self {
+        Self {
+            points1: [Point::origin(); DIM + 1],
+            points2: [Point::origin(); DIM + 1],
+            len: 0,
+            separating_axis: None,
+        }
+    }
+
+    /// Empties this cache so the next GJK run starts from scratch.
+    pub fn clear(&mut self) {
+        self.len = 0;
+        self.separating_axis = None;
+    }
+
+    /// Does this cache contain a simplex to start from?
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The last separating axis (or contact normal) found by the GJK algorithm, expressed in the
+    /// local-space of the first shape.
+    pub fn separating_axis(&self) -> Option<Unit<Vector<Real>>> {
+        self.separating_axis
+    }
+
+    /// Initializes `simplex` with the cached simplex vertices, with the second shape placed at
+    /// `pos12` relative to the first shape.
+    ///
+    /// Returns `false` (and leaves `simplex` untouched) if this cache is empty.
+    pub fn warm_start(&self, pos12: &Isometry<Real>, simplex: &mut VoronoiSimplex) -> bool {
+        if self.len == 0 {
+            return false;
+        }
+
+        let cso_point = |i: usize| CSOPoint::new(self.points1[i], pos12 * self.points2[i]);
+        simplex.reset(cso_point(0));
+
+        for i in 1..self.len {
+            // Vertices that became degenerate because of the shapes’ motion are just skipped.
+            let _ = simplex.add_point(cso_point(i));
+        }
+
+        true
+    }
+
+    /// Stores the vertices of `simplex`, and the separating axis from `result`, for the next GJK
+    /// run.
+    ///
+    /// The second shape is assumed to have been placed at `pos12` relative to the first shape
+    /// when `simplex` was computed.
+    pub fn update(&mut self, pos12: &Isometry<Real>, simplex: &VoronoiSimplex, result: &GJKResult) {
+        self.len = simplex.dimension() + 1;
+
+        for i in 0..self.len {
+            let pt = simplex.point(i);
+            self.points1[i] = pt.orig1;
+            self.points2[i] = pos12.inverse_transform_point(&pt.orig2);
+        }
+
+        self.separating_axis = match result {
+            GJKResult::ClosestPoints(_, _, dir)
+            | GJKResult::Proximity(dir)
+            | GJKResult::NoIntersection(dir) => Some(*dir),
+            GJKResult::Intersection => None,
+        };
+    }
+}
@@ -1,6 +1,7 @@
 //! The GJK algorithm for distance computation.
 
 pub use self::cso_point::CSOPoint;
+pub use self::gjk_cache::GjkCache;
 #[cfg(feature = "dim2")]
 pub use self::voronoi_simplex2::VoronoiSimplex;
 #[cfg(feature = "dim3")]
@@ -10,6 +11,7 @@ pub use special_support_maps::*;
 
 mod cso_point;
 mod gjk;
+mod gjk_cache;
 mod special_support_maps;
 #[cfg(feature = "dim2")]
 mod voronoi_simplex2;
@@ -0,0 +1,247 @@
+use crate::mass_properties::MassProperties;
+use crate::math::{Isometry, Point, Real, Vector};
+use crate::shape::{HalfSpace, Shape, TypedShape};
+use alloc::vec::Vec;
+
+#[cfg(feature = "dim2")]
+use {
+    crate::shape::TriangleOrientation,
+    na::{ComplexField, RealField},
+};
+#[cfg(feature = "dim3")]
+use {
+    crate::shape::{Ball, ConvexPolyhedron},
+    crate::transformation::{self, ConvexHullError},
+    alloc::borrow::Cow,
+};
+
+/// The number of subdivisions used to approximate curved shapes by polyhedra (or polygons).
+const CURVED_SHAPE_SUBDIVISIONS: u32 = 32;
+
+/// Error returned by [`intersection_volume`].
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum IntersectionVolumeError {
+    /// At least one of the shapes isn’t convex, or both shapes are half-spaces.
+    #[error("the intersection volume isn’t supported between these shapes")]
+    Unsupported,
+    /// The convex hull of the intersection, or of a shape converted to a convex polyhedron,
+    /// couldn’t be computed.
+    #[cfg(feature = "dim3")]
+    #[error("ConvexHullError: {0}")]
+    ConvexHullError(ConvexHullError),
+}
+
+#[cfg(feature = "dim3")]
+impl From<ConvexHullError> for IntersectionVolumeError {
+    fn from(value: ConvexHullError) -> Self {
+        IntersectionVolumeError::ConvexHullError(value)
+    }
+}
+
+/// Computes the volume (area in 2D) and centroid of the intersection of two convex shapes.
+///
+/// The result is given as the mass properties of the intersection with a unit density, so its
+/// [`mass`](MassProperties::mass) is the overlap volume and its `local_com` is the overlap
+/// centroid, both expressed in the local-space of `shape1`. Returns `Ok(None)` if the shapes
+/// don’t overlap, or if their overlap has no volume.
+///
+/// Any convex shape is supported, as well as half-spaces. Polyhedral shapes (cuboids, triangles,
+/// convex polyhedra, and convex polygons in 2D) are handled exactly. Curved and rounded shapes
+/// (balls, capsules, cylinders, cones, and round shapes) are first approximated by the convex
+/// hull of points sampled on their boundary, so the result is slightly smaller than the exact
+/// overlap. Returns [`IntersectionVolumeError::Unsupported`] if one of the shapes isn’t convex,
+/// or if both shapes are half-spaces.
+pub fn intersection_volume(
+    pos12: &Isometry<Real>,
+    shape1: &dyn Shape,
+    shape2: &dyn Shape,
+) -> Result<Option<MassProperties>, IntersectionVolumeError> {
+    if let Some(halfspace2) = shape2.as_shape::<HalfSpace>() {
+        return intersection_volume_convex_halfspace(pos12, shape1, halfspace2);
+    }
+
+    if let Some(halfspace1) = shape1.as_shape::<HalfSpace>() {
+        let mprops = intersection_volume_convex_halfspace(&pos12.inverse(), shape2, halfspace1)?;
+        return Ok(mprops.map(|mprops| mprops.transform_by(pos12)));
+    }
+
+    intersection_volume_convex_convex(pos12, shape1, shape2)
+}
+
+/// Samples the boundary of a convex shape by computing its support points in all the
+/// `directions`.
+fn support_points(
+    shape: &dyn Shape,
+    directions: impl Iterator<Item = Vector<Real>>,
+) -> Result<Vec<Point<Real>>, IntersectionVolumeError> {
+    let support_map = shape
+        .as_support_map()
+        .filter(|_| shape.is_convex())
+        .ok_or(IntersectionVolumeError::Unsupported)?;
+    Ok(directions
+        .map(|dir| support_map.local_support_point(&dir))
+        .collect())
+}
+
+#[cfg(feature = "dim3")]
+fn intersection_volume_convex_convex(
+    pos12: &Isometry<Real>,
+    shape1: &dyn Shape,
+    shape2: &dyn Shape,
+) -> Result<Option<MassProperties>, IntersectionVolumeError> {
+    let intersection = if let (Some(c1), Some(c2)) = (shape1.as_cuboid(), shape2.as_cuboid()) {
+        transformation::cuboids_intersection(pos12, c1, c2)?
+    } else {
+        let (Some(poly1), Some(poly2)) =
+            (to_convex_polyhedron(shape1)?, to_convex_polyhedron(shape2)?)
+        else {
+            return Ok(None);
+        };
+        transformation::convex_polyhedra_intersection(pos12, &poly1, &poly2)?
+    };
+
+    Ok(intersection.and_then(|poly| polyhedron_mass_properties(&poly)))
+}
+
+#[cfg(feature = "dim3")]
+fn intersection_volume_convex_halfspace(
+    pos12: &Isometry<Real>,
+    shape1: &dyn Shape,
+    halfspace2: &HalfSpace,
+) -> Result<Option<MassProperties>, IntersectionVolumeError> {
+    let Some(poly1) = to_convex_polyhedron(shape1)? else {
+        return Ok(None);
+    };
+    let intersection =
+        transformation::convex_polyhedron_halfspace_intersection(pos12, &poly1, halfspace2)?;
+    Ok(intersection.and_then(|poly| polyhedron_mass_properties(&poly)))
+}
+
+/// Converts a convex shape to a convex polyhedron.
+///
+/// Returns `Ok(None)` if the shape has no volume.
+#[cfg(feature = "dim3")]
+fn to_convex_polyhedron(
+    shape: &dyn Shape,
+) -> Result<Option<Cow<'_, ConvexPolyhedron>>, IntersectionVolumeError> {
+    let nsubdiv = CURVED_SHAPE_SUBDIVISIONS;
+    let points = match shape.as_typed_shape() {
+        TypedShape::ConvexPolyhedron(poly) => return Ok(Some(Cow::Borrowed(poly))),
+        TypedShape::Cuboid(cuboid) => cuboid.to_trimesh().0,
+        TypedShape::Triangle(tri) => tri.vertices().to_vec(),
+        TypedShape::Ball(ball) => ball.to_trimesh(nsubdiv, nsubdiv / 2).0,
+        TypedShape::Capsule(capsule) => capsule.to_trimesh(nsubdiv, nsubdiv / 2).0,
+        TypedShape::Cylinder(cylinder) => cylinder.to_trimesh(nsubdiv).0,
+        TypedShape::Cone(cone) => cone.to_trimesh(nsubdiv).0,
+        _ => {
+            // Rounded shapes are sampled along the directions of the vertices of a sphere.
+            let directions = Ball::new(1.0).to_trimesh(nsubdiv, nsubdiv / 2).0;
+            support_points(shape, directions.into_iter().map(|pt| pt.coords))?
+        }
+    };
+
+    Ok(transformation::convex_polyhedron_from_points(&points)?.map(Cow::Owned))
+}
+
+#[cfg(feature = "dim3")]
+fn polyhedron_mass_properties(poly: &ConvexPolyhedron) -> Option<MassProperties> {
+    let (vertices, indices) = poly.to_trimesh();
+    let mprops = MassProperties::from_convex_polyhedron(1.0, &vertices, &indices);
+    (mprops.mass() > 0.0).then_some(mprops)
+}
+
+#[cfg(feature = "dim2")]
+fn intersection_volume_convex_convex(
+    pos12: &Isometry<Real>,
+    shape1: &dyn Shape,
+    shape2: &dyn Shape,
+) -> Result<Option<MassProperties>, IntersectionVolumeError> {
+    let poly1 = to_convex_polygon(shape1)?;
+    let mut poly2 = to_convex_polygon(shape2)?;
+    poly2.iter_mut().for_each(|pt| *pt = pos12 * *pt);
+
+    if poly1.len() < 3 || poly2.len() < 3 {
+        return Ok(None);
+    }
+
+    let mut intersection = Vec::new();
+    crate::transformation::convex_polygons_intersection_points(&poly1, &poly2, &mut intersection);
+    Ok(polygon_mass_properties(&intersection))
+}
+
+#[cfg(feature = "dim2")]
+fn intersection_volume_convex_halfspace(
+    pos12: &Isometry<Real>,
+    shape1: &dyn Shape,
+    halfspace2: &HalfSpace,
+) -> Result<Option<MassProperties>, IntersectionVolumeError> {
+    let poly1 = to_convex_polygon(shape1)?;
+    let normal = pos12 * halfspace2.normal;
+    let bias = normal.dot(&pos12.translation.vector);
+
+    // Clip the polygon against the boundary of the half-space.
+    let mut clipped = Vec::new();
+    for (i, a) in poly1.iter().enumerate() {
+        let b = poly1[(i + 1) % poly1.len()];
+        let da = normal.dot(&a.coords) - bias;
+        let db = normal.dot(&b.coords) - bias;
+
+        if da <= 0.0 {
+            clipped.push(*a);
+        }
+
+        if (da < 0.0 && db > 0.0) || (da > 0.0 && db < 0.0) {
+            clipped.push(a + (b - a) * (da / (da - db)));
+        }
+    }
+
+    Ok(polygon_mass_properties(&clipped))
+}
+
+/// Converts a convex shape to a counterclockwise convex polygon.
+///
+/// The result has less than three vertices if the shape has no area.
+#[cfg(feature = "dim2")]
+fn to_convex_polygon(shape: &dyn Shape) -> Result<Vec<Point<Real>>, IntersectionVolumeError> {
+    match shape.as_typed_shape() {
+        TypedShape::ConvexPolygon(poly) => Ok(poly.points().to_vec()),
+        TypedShape::Cuboid(cuboid) => Ok(cuboid.to_polyline()),
+        TypedShape::Triangle(tri) => match tri.orientation(crate::math::DEFAULT_EPSILON) {
+            TriangleOrientation::CounterClockwise => Ok(alloc::vec![tri.a, tri.b, tri.c]),
+            TriangleOrientation::Clockwise => Ok(alloc::vec![tri.a, tri.c, tri.b]),
+            TriangleOrientation::Degenerate => Ok(Vec::new()),
+        },
+        _ => {
+            // Curved and rounded shapes are sampled along directions of increasing angle, so
+            // their support points are already sorted counterclockwise.
+            let n = CURVED_SHAPE_SUBDIVISIONS * 2;
+            let directions = (0..n).map(|i| {
+                let angle = Real::two_pi() * i as Real / n as Real;
+                Vector::new(ComplexField::cos(angle), ComplexField::sin(angle))
+            });
+            let mut points = support_points(shape, directions)?;
+            let eps = crate::math::DEFAULT_EPSILON
+                * points
+                    .iter()
+                    .map(|pt| pt.coords.amax())
+                    .fold(1.0, Real::max);
+            points.dedup_by(|a, b| na::distance(a, b) <= eps);
+
+            if points.len() > 1 && na::distance(&points[0], &points[points.len() - 1]) <= eps {
+                let _ = points.pop();
+            }
+
+            Ok(points)
+        }
+    }
+}
+
+#[cfg(feature = "dim2")]
+fn polygon_mass_properties(poly: &[Point<Real>]) -> Option<MassProperties> {
+    if poly.len() < 3 {
+        return None;
+    }
+
+    let mprops = MassProperties::from_convex_polygon(1.0, poly);
+    (mprops.mass() > 0.0).then_some(mprops)
+}
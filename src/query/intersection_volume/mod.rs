@@ -0,0 +1,13 @@
+//! Implementation details of the `intersection_volume` and `submerged_volume` functions.
+
+pub use self::intersection_volume::{intersection_volume, IntersectionVolumeError};
+#[cfg(feature = "dim3")]
+pub use self::submerged_volume::{
+    submerged_volume, submerged_volume_ball, submerged_volume_capsule, submerged_volume_compound,
+    submerged_volume_convex_polyhedron, submerged_volume_cuboid, submerged_volume_cylinder,
+    submerged_volume_trimesh, SubmergedVolume,
+};
+
+mod intersection_volume;
+#[cfg(feature = "dim3")]
+mod submerged_volume;
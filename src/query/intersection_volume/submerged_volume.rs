@@ -0,0 +1,658 @@
+use crate::math::{Isometry, Point, Real, Vector};
+use crate::query::Unsupported;
+use crate::shape::{
+    Ball, Capsule, Compound, ConvexPolyhedron, Cuboid, Cylinder, HalfSpace, Shape, TriMesh,
+    TypedShape,
+};
+use na::{RealField, Unit};
+
+#[cfg(not(feature = "std"))]
+use na::ComplexField; // for .sqrt(), .acos(), and .asin()
+
+/// The part of a shape submerged below the boundary of a half-space, computed by
+/// [`submerged_volume`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SubmergedVolume {
+    /// The volume of the part of the shape inside of the half-space.
+    pub volume: Real,
+    /// The centroid of the submerged part (i.e. the center of buoyancy), in the local-space of the
+    /// shape.
+    ///
+    /// This is set to the origin if `volume` is zero.
+    pub centroid: Point<Real>,
+    /// The area of the section of the shape by the boundary plane of the half-space.
+    pub waterline_area: Real,
+}
+
+impl SubmergedVolume {
+    /// A submerged volume with a zero volume and waterline area.
+    pub fn zero() -> Self {
+        Self {
+            volume: 0.0,
+            centroid: Point::origin(),
+            waterline_area: 0.0,
+        }
+    }
+
+    fn from_moments(volume: Real, moment: Vector<Real>, waterline_area: Real) -> Self {
+        if volume > 0.0 {
+            Self {
+                volume,
+                centroid: Point::from(moment / volume),
+                waterline_area,
+            }
+        } else {
+            Self {
+                waterline_area,
+                ..Self::zero()
+            }
+        }
+    }
+}
+
+/// The boundary plane of a half-space, in the local-space of the submerged shape.
+///
+/// The submerged points `p` are those verifying `normal.dot(p) <= bias`.
+#[derive(Copy, Clone, Debug)]
+struct Plane {
+    normal: Unit<Vector<Real>>,
+    bias: Real,
+}
+
+impl Plane {
+    fn new(pos12: &Isometry<Real>, halfspace2: &HalfSpace) -> Self {
+        let normal = pos12 * halfspace2.normal;
+        let bias = normal.dot(&pos12.translation.vector);
+        Self { normal, bias }
+    }
+
+    fn signed_dist(&self, pt: &Point<Real>) -> Real {
+        self.normal.dot(&pt.coords) - self.bias
+    }
+}
+
+/// Computes the volume, centroid and waterline area of the part of `shape1` lying inside of
+/// `halfspace2`.
+///
+/// The boundary of the half-space is seen as the water surface, its normal pointing out of the
+/// water. All the cases are computed analytically. The parts of a compound shape are assumed not
+/// to overlap each other.
+///
+/// Returns `Err(Unsupported)` if `shape1` is of any other type, or if it is (or contains) a
+/// triangle mesh without the [`TriMeshFlags::ORIENTED`](crate::shape::TriMeshFlags::ORIENTED)
+/// flag.
+pub fn submerged_volume(
+    pos12: &Isometry<Real>,
+    shape1: &dyn Shape,
+    halfspace2: &HalfSpace,
+) -> Result<SubmergedVolume, Unsupported> {
+    match shape1.as_typed_shape() {
+        TypedShape::Ball(ball) => Ok(submerged_volume_ball(pos12, ball, halfspace2)),
+        TypedShape::Cuboid(cuboid) => Ok(submerged_volume_cuboid(pos12, cuboid, halfspace2)),
+        TypedShape::Capsule(capsule) => Ok(submerged_volume_capsule(pos12, capsule, halfspace2)),
+        TypedShape::Cylinder(cylinder) => {
+            Ok(submerged_volume_cylinder(pos12, cylinder, halfspace2))
+        }
+        TypedShape::ConvexPolyhedron(poly) => {
+            Ok(submerged_volume_convex_polyhedron(pos12, poly, halfspace2))
+        }
+        TypedShape::TriMesh(mesh) => submerged_volume_trimesh(pos12, mesh, halfspace2),
+        TypedShape::Compound(compound) => submerged_volume_compound(pos12, compound, halfspace2),
+        _ => Err(Unsupported),
+    }
+}
+
+/// Computes the part of a ball lying inside of a half-space.
+pub fn submerged_volume_ball(
+    pos12: &Isometry<Real>,
+    ball1: &Ball,
+    halfspace2: &HalfSpace,
+) -> SubmergedVolume {
+    let plane = Plane::new(pos12, halfspace2);
+    let r = ball1.radius;
+    // The height of the submerged spherical cap.
+    let h = (r + plane.bias).clamp(0.0, 2.0 * r);
+
+    if h == 0.0 {
+        return SubmergedVolume::zero();
+    }
+
+    let (volume, cap_center_dist) = spherical_cap(r, h);
+    let waterline_area = Real::pi() * (r * r - plane.bias * plane.bias).max(0.0);
+
+    SubmergedVolume {
+        volume,
+        centroid: Point::from(*plane.normal * -cap_center_dist),
+        waterline_area,
+    }
+}
+
+/// Computes the part of a cuboid lying inside of a half-space.
+pub fn submerged_volume_cuboid(
+    pos12: &Isometry<Real>,
+    cuboid1: &Cuboid,
+    halfspace2: &HalfSpace,
+) -> SubmergedVolume {
+    let mut acc = PolyhedronAccumulator::new(Plane::new(pos12, halfspace2));
+    let he = cuboid1.half_extents;
+
+    for i in 0..3 {
+        let (j, k) = ((i + 1) % 3, (i + 2) % 3);
+        let ej = Vector::ith(j, he[j]);
+        let ek = Vector::ith(k, he[k]);
+
+        for sign in [-1.0, 1.0] {
+            let center = Point::from(Vector::ith(i, he[i] * sign));
+            let mut quad = [
+                center - ej - ek,
+                center + ej - ek,
+                center + ej + ek,
+                center - ej + ek,
+            ];
+
+            // Make the face’s orientation point outward.
+            if sign < 0.0 {
+                quad.reverse();
+            }
+
+            acc.add_triangle(&quad[0], &quad[1], &quad[2]);
+            acc.add_triangle(&quad[0], &quad[2], &quad[3]);
+        }
+    }
+
+    acc.finish()
+}
+
+/// Computes the part of a convex polyhedron lying inside of a half-space.
+pub fn submerged_volume_convex_polyhedron(
+    pos12: &Isometry<Real>,
+    poly1: &ConvexPolyhedron,
+    halfspace2: &HalfSpace,
+) -> SubmergedVolume {
+    let mut acc = PolyhedronAccumulator::new(Plane::new(pos12, halfspace2));
+    let points = poly1.points();
+
+    for face in poly1.faces() {
+        let i1 = face.first_vertex_or_edge as usize;
+        let i2 = i1 + face.num_vertices_or_edges as usize;
+        let face_vertices = &poly1.vertices_adj_to_face()[i1..i2];
+        let first = &points[face_vertices[0] as usize];
+
+        for idx in face_vertices[1..].windows(2) {
+            acc.add_triangle(first, &points[idx[0] as usize], &points[idx[1] as usize]);
+        }
+    }
+
+    acc.finish()
+}
+
+/// Computes the part of a closed triangle mesh lying inside of a half-space.
+///
+/// Returns `Err(Unsupported)` if the mesh wasn’t created with the
+/// [`TriMeshFlags::ORIENTED`](crate::shape::TriMeshFlags::ORIENTED) flag.
+pub fn submerged_volume_trimesh(
+    pos12: &Isometry<Real>,
+    mesh1: &TriMesh,
+    halfspace2: &HalfSpace,
+) -> Result<SubmergedVolume, Unsupported> {
+    if mesh1.pseudo_normals_if_oriented().is_none() {
+        return Err(Unsupported);
+    }
+
+    let mut acc = PolyhedronAccumulator::new(Plane::new(pos12, halfspace2));
+
+    for tri in mesh1.triangles() {
+        acc.add_triangle(&tri.a, &tri.b, &tri.c);
+    }
+
+    Ok(acc.finish())
+}
+
+/// Computes the part of a compound shape lying inside of a half-space.
+///
+/// The parts of the compound shape are assumed not to overlap each other.
+pub fn submerged_volume_compound(
+    pos12: &Isometry<Real>,
+    compound1: &Compound,
+    halfspace2: &HalfSpace,
+) -> Result<SubmergedVolume, Unsupported> {
+    let mut volume = 0.0;
+    let mut moment = Vector::zeros();
+    let mut waterline_area = 0.0;
+
+    for (part_pos, part) in compound1.shapes() {
+        let part = submerged_volume(&part_pos.inv_mul(pos12), &**part, halfspace2)?;
+        volume += part.volume;
+        moment += (part_pos * part.centroid).coords * part.volume;
+        waterline_area += part.waterline_area;
+    }
+
+    Ok(SubmergedVolume::from_moments(
+        volume,
+        moment,
+        waterline_area,
+    ))
+}
+
+/// Computes the part of a cylinder lying inside of a half-space.
+pub fn submerged_volume_cylinder(
+    pos12: &Isometry<Real>,
+    cylinder1: &Cylinder,
+    halfspace2: &HalfSpace,
+) -> SubmergedVolume {
+    let plane = Plane::new(pos12, halfspace2);
+    let axis = RevolutionAxis::new(&plane, Point::origin(), Vector::y_axis());
+    let mut acc = RevolutionAccumulator::default();
+    axis.add_cylinder(
+        cylinder1.radius,
+        -cylinder1.half_height,
+        cylinder1.half_height,
+        &mut acc,
+    );
+    acc.finish(&axis)
+}
+
+/// Computes the part of a capsule lying inside of a half-space.
+///
+/// The submerged parts of the cylindrical body and of the hemispherical ends of the capsule are
+/// computed separately, in closed form.
+pub fn submerged_volume_capsule(
+    pos12: &Isometry<Real>,
+    capsule1: &Capsule,
+    halfspace2: &HalfSpace,
+) -> SubmergedVolume {
+    let plane = Plane::new(pos12, halfspace2);
+    let half_height = capsule1.half_height();
+
+    if half_height == 0.0 {
+        let ball = Ball::new(capsule1.radius);
+        let pos = Isometry::from(-capsule1.center().coords) * pos12;
+        let mut result = submerged_volume_ball(&pos, &ball, halfspace2);
+        result.centroid += capsule1.center().coords;
+        return result;
+    }
+
+    let dir = Unit::new_normalize(capsule1.segment.scaled_direction());
+    let axis = RevolutionAxis::new(&plane, capsule1.center(), dir);
+    let mut acc = RevolutionAccumulator::default();
+    axis.add_cylinder(capsule1.radius, -half_height, half_height, &mut acc);
+    axis.add_hemisphere(capsule1.radius, half_height, 1.0, &mut acc);
+    axis.add_hemisphere(capsule1.radius, -half_height, -1.0, &mut acc);
+    acc.finish(&axis)
+}
+
+/// Accumulates the volume, first moment and vector area of the submerged part of a closed
+/// triangulated surface.
+struct PolyhedronAccumulator {
+    plane: Plane,
+    /// A point on the plane, used as the apex of the tetrahedra of the volume integration.
+    /// Because the cap of the submerged part lies on the plane, its own tetrahedra are flat and
+    /// don’t need to be accounted for.
+    apex: Point<Real>,
+    volume: Real,
+    moment: Vector<Real>,
+    vector_area: Vector<Real>,
+}
+
+impl PolyhedronAccumulator {
+    fn new(plane: Plane) -> Self {
+        Self {
+            plane,
+            apex: Point::from(*plane.normal * plane.bias),
+            volume: 0.0,
+            moment: Vector::zeros(),
+            vector_area: Vector::zeros(),
+        }
+    }
+
+    fn add_triangle(&mut self, a: &Point<Real>, b: &Point<Real>, c: &Point<Real>) {
+        let vertices = [*a, *b, *c];
+        let dists = vertices.map(|pt| self.plane.signed_dist(&pt));
+
+        // Clip the triangle, keeping its part inside of the half-space.
+        let mut clipped = [Point::origin(); 4];
+        let mut len = 0;
+
+        for i in 0..3 {
+            let j = (i + 1) % 3;
+
+            if dists[i] <= 0.0 {
+                clipped[len] = vertices[i];
+                len += 1;
+            }
+
+            if (dists[i] < 0.0 && dists[j] > 0.0) || (dists[i] > 0.0 && dists[j] < 0.0) {
+                let t = dists[i] / (dists[i] - dists[j]);
+                clipped[len] = vertices[i] + (vertices[j] - vertices[i]) * t;
+                len += 1;
+            }
+        }
+
+        for k in 1..len.saturating_sub(1) {
+            let (p0, p1, p2) = (clipped[0], clipped[k], clipped[k + 1]);
+            let e0 = p0 - self.apex;
+            let e1 = p1 - self.apex;
+            let e2 = p2 - self.apex;
+            let volume = e0.dot(&e1.cross(&e2)) / 6.0;
+
+            self.volume += volume;
+            self.moment += (self.apex.coords + p0.coords + p1.coords + p2.coords) * (volume / 4.0);
+            self.vector_area += (p1 - p0).cross(&(p2 - p0)) / 2.0;
+        }
+    }
+
+    fn finish(self) -> SubmergedVolume {
+        // The cap of the submerged part closes the clipped surface, so its vector area is the
+        // opposite of the clipped surface’s.
+        let waterline_area = self.vector_area.dot(&self.plane.normal).abs();
+
+        if self.volume == 0.0 {
+            return SubmergedVolume {
+                waterline_area,
+                ..SubmergedVolume::zero()
+            };
+        }
+
+        // Dividing by the signed volume makes the result independent from the orientation of
+        // the triangles.
+        SubmergedVolume {
+            volume: self.volume.abs(),
+            centroid: Point::from(self.moment / self.volume),
+            waterline_area,
+        }
+    }
+}
+
+/// The accumulated integrals of the submerged slices of a solid of revolution, expressed in the
+/// frame of its [`RevolutionAxis`].
+#[derive(Default)]
+struct RevolutionAccumulator {
+    volume: Real,
+    /// The first moment of the submerged volume along the axis.
+    axial_moment: Real,
+    /// The first moment of the submerged volume along the radial direction of the plane normal.
+    radial_moment: Real,
+    waterline_area: Real,
+}
+
+impl RevolutionAccumulator {
+    fn finish(self, axis: &RevolutionAxis) -> SubmergedVolume {
+        let moment = (axis.center.coords * self.volume)
+            + *axis.dir * self.axial_moment
+            + axis.radial_dir * self.radial_moment;
+        SubmergedVolume::from_moments(self.volume, moment, self.waterline_area)
+    }
+}
+
+/// The plane of a half-space, expressed in the frame of the axis of a solid of revolution.
+///
+/// With `y` the coordinate along the axis and `s` the coordinate along `radial_dir`, the
+/// submerged points verify `axial * y + radial * s <= bias`.
+struct RevolutionAxis {
+    center: Point<Real>,
+    dir: Unit<Vector<Real>>,
+    /// The unit direction perpendicular to the axis along which the plane normal is tilted.
+    radial_dir: Vector<Real>,
+    axial: Real,
+    radial: Real,
+    bias: Real,
+}
+
+impl RevolutionAxis {
+    fn new(plane: &Plane, center: Point<Real>, dir: Unit<Vector<Real>>) -> Self {
+        let axial = plane.normal.dot(&dir);
+        let radial_normal = *plane.normal - *dir * axial;
+        let (radial_dir, radial) =
+            Unit::try_new_and_get(radial_normal, crate::math::DEFAULT_EPSILON)
+                .map(|(radial_dir, radial)| (*radial_dir, radial))
+                .unwrap_or((Vector::zeros(), 0.0));
+
+        Self {
+            center,
+            dir,
+            radial_dir,
+            axial,
+            radial,
+            bias: -plane.signed_dist(&center),
+        }
+    }
+
+    /// Does the plane cross the axis perpendicularly?
+    fn is_perpendicular(&self) -> bool {
+        self.radial == 0.0
+    }
+
+    /// The position, along the radial direction, of the intersection of the plane with the
+    /// slice of the solid at the axial coordinate `y`.
+    fn cut(&self, y: Real) -> Real {
+        (self.bias - self.axial * y) / self.radial
+    }
+
+    /// Integrates the submerged part of the cylinder of radius `r` spanning the axial
+    /// coordinates `[y0, y1]`.
+    fn add_cylinder(&self, r: Real, y0: Real, y1: Real, acc: &mut RevolutionAccumulator) {
+        let disk_area = Real::pi() * r * r;
+
+        if self.is_perpendicular() {
+            // Each slice is either fully submerged or fully emerged.
+            let plane_y = self.bias / self.axial;
+            let (lo, hi) = if self.axial > 0.0 {
+                (y0, plane_y.min(y1))
+            } else {
+                (plane_y.max(y0), y1)
+            };
+
+            if hi > lo {
+                acc.volume += disk_area * (hi - lo);
+                acc.axial_moment += disk_area * (hi * hi - lo * lo) / 2.0;
+            }
+
+            if plane_y >= y0 && plane_y <= y1 {
+                acc.waterline_area += disk_area;
+            }
+
+            return;
+        }
+
+        let slice = DiskSlice { r };
+
+        if self.axial.abs() <= crate::math::DEFAULT_EPSILON {
+            // The plane is parallel to the axis: all the slices are cut at the same place.
+            let u = self.cut(0.0);
+            let area = slice.area(u);
+            acc.volume += area * (y1 - y0);
+            acc.axial_moment += area * (y1 * y1 - y0 * y0) / 2.0;
+            acc.radial_moment += slice.moment(u) * (y1 - y0);
+            acc.waterline_area += slice.chord(u) * (y1 - y0) / self.radial;
+            return;
+        }
+
+        // Integrate along the cut position `u` instead of `y`, where `dy = -radial / axial * du`.
+        let (ua, ub) = (self.cut(y0), self.cut(y1));
+        let jacobian = self.radial / self.axial;
+        let int_area = slice.integrated_area(ub, ua);
+        let int_u_area = slice.integrated_u_area(ub, ua);
+
+        acc.volume += jacobian * int_area;
+        acc.axial_moment +=
+            jacobian / self.axial * (self.bias * int_area - self.radial * int_u_area);
+        acc.radial_moment += jacobian * slice.integrated_moment(ub, ua);
+        acc.waterline_area += slice.integrated_chord(ub, ua) / self.axial;
+    }
+
+    /// Integrates the submerged part of the hemisphere of radius `r` centered at the axial
+    /// coordinate `y_center`, and extending toward the axial coordinates of sign `side`.
+    fn add_hemisphere(&self, r: Real, y_center: Real, side: Real, acc: &mut RevolutionAccumulator) {
+        // In the frame of the hemisphere, with the origin at its center and `a` its unit axis,
+        // the submerged points verify `axial * (x·a) + radial * (x·radial_dir) <= d`.
+        let axial = self.axial * side;
+        let radial = self.radial;
+        let d = self.bias - self.axial * y_center;
+        let hemisphere_volume = Real::two_pi() * r * r * r / 3.0;
+        let waterline_radius_sq = r * r - d * d;
+
+        // The volume and its first moment along `a` and `radial_dir`.
+        let (volume, moment_a, moment_s) = if d * d < r * r * radial * radial {
+            // The waterline crosses the flat face of the hemisphere. By the divergence theorem,
+            // the volume is `(r * |S| + d * |W|) / 3` and the first moment is
+            // `1/2 ∮ |x|² n dA`, where `S` is the submerged part of the spherical surface, `E`
+            // the submerged part of the flat face, and `W` the waterline section.
+            let waterline_radius = waterline_radius_sq.sqrt();
+            let flat = DiskSlice { r };
+            let waterline = DiskSlice {
+                r: waterline_radius,
+            };
+            let flat_cut = d / radial;
+            let waterline_cut = d * axial / radial;
+            let flat_area = flat.area(flat_cut);
+            let waterline_area = waterline.area(waterline_cut);
+            acc.waterline_area += waterline_area;
+
+            // The area of `S`, from the Gauss-Bonnet theorem: the waterline arc spans the angle
+            // `arc` around its center, and the two boundary arcs meet with the exterior angle
+            // `corner`.
+            let arc = 2.0 * (-waterline_cut / waterline_radius).clamp(-1.0, 1.0).acos();
+            let corner = (-axial * r / waterline_radius).clamp(-1.0, 1.0).acos();
+            let sphere_area = r * r * (Real::two_pi() + d / r * arc - 2.0 * corner);
+
+            let volume = (r * sphere_area + d * waterline_area) / 3.0;
+            // The vector area of `S` is `a * |E| - n * |W|` because the surface is closed.
+            let flat_term = r * r * flat_area - flat.polar_moment(flat_cut);
+            let waterline_term = -r * r * waterline_area
+                + d * d * waterline_area
+                + waterline.polar_moment(waterline_cut);
+            (
+                volume,
+                (flat_term + axial * waterline_term) / 2.0,
+                radial * waterline_term / 2.0,
+            )
+        } else if d * axial > 0.0 && waterline_radius_sq > 0.0 {
+            // The waterline is a full circle inside of the hemisphere.
+            acc.waterline_area += Real::pi() * waterline_radius_sq;
+            let (dry_volume, dry_dist) = spherical_cap(r, r - d);
+            let (wet_volume, wet_dist) = spherical_cap(r, r + d);
+
+            if -r * axial <= d {
+                // The hemisphere minus the dry cap.
+                (
+                    hemisphere_volume - dry_volume,
+                    hemisphere_volume * 3.0 * r / 8.0 - dry_volume * dry_dist * axial,
+                    -dry_volume * dry_dist * radial,
+                )
+            } else {
+                // Only the wet cap.
+                (
+                    wet_volume,
+                    -wet_volume * wet_dist * axial,
+                    -wet_volume * wet_dist * radial,
+                )
+            }
+        } else if r * axial < d {
+            // The whole hemisphere is submerged.
+            (hemisphere_volume, hemisphere_volume * 3.0 * r / 8.0, 0.0)
+        } else {
+            (0.0, 0.0, 0.0)
+        };
+
+        acc.volume += volume;
+        acc.axial_moment += volume * y_center + moment_a * side;
+        acc.radial_moment += moment_s;
+    }
+}
+
+/// The volume of the spherical cap of height `h` of a ball of radius `r`, and the distance from
+/// the center of the ball to the centroid of the cap.
+fn spherical_cap(r: Real, h: Real) -> (Real, Real) {
+    let volume = Real::pi() * h * h * (3.0 * r - h) / 3.0;
+    let center_dist = 3.0 * (2.0 * r - h) * (2.0 * r - h) / (4.0 * (3.0 * r - h));
+    (volume, center_dist)
+}
+
+/// A disk of radius `r` cut by a line at the coordinate `u` along some direction.
+///
+/// All the quantities below relate to the part of the disk with a coordinate smaller than `u`.
+struct DiskSlice {
+    r: Real,
+}
+
+impl DiskSlice {
+    fn clamp(&self, u: Real) -> Real {
+        u.clamp(-self.r, self.r)
+    }
+
+    fn half_chord(&self, u: Real) -> Real {
+        (self.r * self.r - u * u).max(0.0).sqrt()
+    }
+
+    /// The area of the part of the disk.
+    fn area(&self, u: Real) -> Real {
+        let u = self.clamp(u);
+        self.r * self.r * (-u / self.r).acos() + u * self.half_chord(u)
+    }
+
+    /// The first moment of the part of the disk, along the cut direction.
+    fn moment(&self, u: Real) -> Real {
+        let w = self.half_chord(self.clamp(u));
+        -2.0 / 3.0 * w * w * w
+    }
+
+    /// The second polar moment of the part of the disk, relative to the center of the disk.
+    fn polar_moment(&self, u: Real) -> Real {
+        let u = self.clamp(u);
+        let r2 = self.r * self.r;
+        u * self.half_chord(u) * (2.0 * u * u + r2) / 6.0
+            + r2 * r2 / 2.0 * self.asin(u)
+            + Real::pi() * r2 * r2 / 4.0
+    }
+
+    /// The length of the cut.
+    fn chord(&self, u: Real) -> Real {
+        2.0 * self.half_chord(self.clamp(u))
+    }
+
+    fn asin(&self, u: Real) -> Real {
+        (u / self.r).clamp(-1.0, 1.0).asin()
+    }
+
+    /// The integral of `self.area(u)` for `u` in `[lo, hi]`.
+    fn integrated_area(&self, lo: Real, hi: Real) -> Real {
+        let (r, r2) = (self.r, self.r * self.r);
+        let antiderivative = |u: Real| {
+            let w = self.half_chord(u);
+            r2 * u * (-u / r).acos() + r2 * w - w * w * w / 3.0
+        };
+        // Past `r`, the whole disk is submerged.
+        let full = Real::pi() * r2 * (hi.max(r) - lo.max(r));
+        antiderivative(self.clamp(hi)) - antiderivative(self.clamp(lo)) + full
+    }
+
+    /// The integral of `u * self.area(u)` for `u` in `[lo, hi]`.
+    fn integrated_u_area(&self, lo: Real, hi: Real) -> Real {
+        let (r, r2) = (self.r, self.r * self.r);
+        let antiderivative = |u: Real| {
+            let w = self.half_chord(u);
+            r2 * u * u / 2.0 * (-u / r).acos() - r2 * r2 / 8.0 * self.asin(u)
+                + u * w * (r2 / 8.0 + u * u / 4.0)
+        };
+        let (hi_full, lo_full) = (hi.max(r), lo.max(r));
+        let full = Real::pi() * r2 * (hi_full * hi_full - lo_full * lo_full) / 2.0;
+        antiderivative(self.clamp(hi)) - antiderivative(self.clamp(lo)) + full
+    }
+
+    /// The integral of `self.moment(u)` for `u` in `[lo, hi]`.
+    fn integrated_moment(&self, lo: Real, hi: Real) -> Real {
+        let r2 = self.r * self.r;
+        let antiderivative = |u: Real| {
+            let w = self.half_chord(u);
+            -2.0 / 3.0
+                * (u / 8.0 * (5.0 * r2 - 2.0 * u * u) * w + 3.0 * r2 * r2 / 8.0 * self.asin(u))
+        };
+        antiderivative(self.clamp(hi)) - antiderivative(self.clamp(lo))
+    }
+
+    /// The integral of `self.chord(u)` for `u` in `[lo, hi]`.
+    fn integrated_chord(&self, lo: Real, hi: Real) -> Real {
+        let antiderivative = |u: Real| u * self.half_chord(u) + self.r * self.r * self.asin(u);
+        antiderivative(self.clamp(hi)) - antiderivative(self.clamp(lo))
+    }
+}
@@ -4,9 +4,11 @@
 //! The most general methods provided by this module are:
 //!
 //! * [`closest_points()`] to compute the closest points between two shapes.
+//! * [`ClosestFeatureTracker`] to incrementally track the closest features between two convex polytopes.
 //! * [`distance()`] to compute the distance between two shapes.
 //! * [`contact()`] to compute one pair of contact points between two shapes, including penetrating contact.
 //! * [`intersection_test()`] to determine if two shapes are intersecting or not.
+//! * [`intersection_volume()`] to compute the volume and centroid of the overlap between two convex shapes.
 //! * [`cast_shapes()`] to determine when two shapes undergoing translational motions hit for the first time.
 //! * [`cast_shapes_nonlinear()`] to determine when two shapes undergoing continuous rigid motions hit for the first time.
 //!
@@ -25,6 +27,8 @@
 //! * `[shape1]` is the type of the first shape passed to the function, e.g., `ball`, or `halfspace`. Can also identify a trait implemented by supported shapes, e.g., `support_map`.
 //! * `[shape2]` is the type of the second shape passed to the function, e.g., `ball`, or `halfspace`. Can also identify a trait implemented by supported shapes, e.g., `support_map`.
 
+#[cfg(feature = "alloc")]
+pub use self::closest_features::{ClosestFeatureTracker, TrackedClosestPoints};
 pub use self::closest_points::{closest_points, ClosestPoints};
 pub use self::contact::{contact, Contact};
 #[cfg(feature = "alloc")]
@@ -35,6 +39,10 @@ pub use self::default_query_dispatcher::DefaultQueryDispatcher;
 pub use self::distance::distance;
 pub use self::error::Unsupported;
 pub use self::intersection_test::intersection_test;
+#[cfg(feature = "alloc")]
+pub use self::intersection_volume::{intersection_volume, IntersectionVolumeError};
+#[cfg(all(feature = "dim3", feature = "alloc"))]
+pub use self::intersection_volume::{submerged_volume, SubmergedVolume};
 pub use self::nonlinear_shape_cast::{cast_shapes_nonlinear, NonlinearRigidMotion};
 pub use self::point::{PointProjection, PointQuery, PointQueryWithLocation};
 #[cfg(feature = "alloc")]
@@ -48,17 +56,23 @@ pub use self::split::{IntersectResult, SplitResult};
 pub use self::ray::RayCullingMode;
 
 mod clip;
+#[cfg(feature = "alloc")]
+mod closest_features;
 pub mod closest_points;
 pub mod contact;
 #[cfg(feature = "alloc")]
 mod contact_manifolds;
 mod default_query_dispatcher;
+#[cfg(all(feature = "dim3", feature = "alloc"))]
+mod deforming_shape_cast;
 mod distance;
 #[cfg(feature = "alloc")]
 pub mod epa;
 mod error;
 pub mod gjk;
 mod intersection_test;
+#[cfg(feature = "alloc")]
+mod intersection_volume;
 mod nonlinear_shape_cast;
 pub mod point;
 mod query_dispatcher;
@@ -66,6 +80,10 @@ mod ray;
 pub mod sat;
 mod shape_cast;
 mod split;
+#[cfg(feature = "stats")]
+pub mod stats;
+#[cfg(not(feature = "stats"))]
+pub(crate) mod stats;
 
 /// Queries dedicated to specific pairs of shapes.
 pub mod details {
@@ -74,8 +92,12 @@ pub mod details {
     pub use super::contact::*;
     #[cfg(feature = "alloc")]
     pub use super::contact_manifolds::*;
+    #[cfg(all(feature = "dim3", feature = "alloc"))]
+    pub use super::deforming_shape_cast::*;
     pub use super::distance::*;
     pub use super::intersection_test::*;
+    #[cfg(feature = "alloc")]
+    pub use super::intersection_volume::*;
     pub use super::nonlinear_shape_cast::*;
     pub use super::point::*;
     pub use super::ray::*;
@@ -164,7 +164,15 @@ impl PointQuery for Polyline {
 impl PointQuery for TriMesh {
     #[inline]
     fn project_local_point(&self, point: &Point<Real>, solid: bool) -> PointProjection {
-        CompositeShapeRef(self).project_local_point(point, solid).1
+        #[allow(unused_mut)] // mut is needed in 3D.
+        let mut proj = CompositeShapeRef(self).project_local_point(point, solid).1;
+
+        #[cfg(feature = "dim3")]
+        if self.winding_numbers.is_some() {
+            proj.is_inside = self.contains_local_point_with_winding_number(point);
+        }
+
+        proj
     }
 
     #[inline]
@@ -189,6 +197,12 @@ impl PointQuery for TriMesh {
 
     #[inline]
     fn contains_local_point(&self, point: &Point<Real>) -> bool {
+        #[cfg(feature = "dim3")]
+        if self.winding_numbers.is_some() {
+            // If we can, in 3D, take the winding numbers into account.
+            return self.contains_local_point_with_winding_number(point);
+        }
+
         #[cfg(feature = "dim3")]
         if self.pseudo_normals.is_some() {
             // If we can, in 3D, take the pseudo-normals into account.
@@ -285,7 +299,9 @@ impl PointQueryWithLocation for TriMesh {
             CompositeShapeRef(self).project_local_point_and_get_location(point, max_dist, solid)
         {
             #[cfg(feature = "dim3")]
-            if let Some(pseudo_normals) = self.pseudo_normals_if_oriented() {
+            if self.winding_numbers.is_some() {
+                proj.is_inside = self.contains_local_point_with_winding_number(point);
+            } else if let Some(pseudo_normals) = self.pseudo_normals_if_oriented() {
                 let pseudo_normal = match location {
                     TrianglePointLocation::OnFace(..) | TrianglePointLocation::OnSolid => {
                         Some(self.triangle(part_id).scaled_normal())
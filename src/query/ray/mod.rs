@@ -6,7 +6,9 @@ pub use self::ray_ball::ray_toi_with_ball;
 pub use self::ray_halfspace::{line_toi_with_halfspace, ray_toi_with_halfspace};
 pub use self::ray_support_map::local_ray_intersection_with_support_map_with_params;
 #[cfg(feature = "dim3")]
-pub use self::ray_triangle::local_ray_intersection_with_triangle;
+pub use self::ray_triangle::{
+    local_ray_intersection_with_triangle, local_ray_toi_with_triangle_simd,
+};
 #[cfg(all(feature = "dim3", feature = "alloc"))]
 pub use self::ray_trimesh::RayCullingMode;
 pub use self::simd_ray::SimdRay;
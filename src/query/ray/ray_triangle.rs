@@ -4,7 +4,12 @@ use crate::math::Vector;
 use crate::query::{Ray, RayCast, RayIntersection};
 use crate::shape::{FeatureId, Triangle};
 #[cfg(feature = "dim3")]
-use {crate::math::Point, na::Vector3};
+use {
+    crate::math::{Point, SimdBool, SimdReal, Vector},
+    crate::query::SimdRay,
+    na::Vector3,
+    simba::simd::{SimdComplexField, SimdPartialOrd, SimdValue},
+};
 
 impl RayCast for Triangle {
     #[inline]
@@ -150,3 +155,59 @@ pub fn local_ray_intersection_with_triangle(
         Vector3::new(-v - w + 1.0, v, w),
     ))
 }
+
+/// Computes the time of impact of four rays on a triangle at once.
+///
+/// This computes exactly the same times of impact as [`local_ray_intersection_with_triangle`]
+/// would for each ray individually. Returns the mask of rays hitting the triangle before
+/// `max_time_of_impact`, and the associated times of impact.
+#[cfg(feature = "dim3")]
+pub fn local_ray_toi_with_triangle_simd(
+    a: &Point<Real>,
+    b: &Point<Real>,
+    c: &Point<Real>,
+    rays: &SimdRay,
+    max_time_of_impact: SimdReal,
+) -> (SimdBool, SimdReal) {
+    let zero = SimdReal::splat(0.0);
+    let ab = *b - *a;
+    let ac = *c - *a;
+
+    // normal
+    let n = ab.cross(&ac);
+    let simd_n = Vector::splat(n);
+    let d = simd_n.dot(&rays.dir);
+
+    // Discard rays parallel to the triangle.
+    let mut valid = d.simd_ne(zero);
+
+    let ap = rays.origin - Point::splat(*a);
+    let t = ap.dot(&simd_n);
+
+    // Discard rays that don’t intersect the halfspace defined by the triangle.
+    valid = valid & !((t.simd_lt(zero) & d.simd_lt(zero)) | (t.simd_gt(zero) & d.simd_gt(zero)));
+
+    let d = d.simd_abs();
+
+    //
+    // intersection: compute barycentric coordinates
+    //
+    let e = -rays.dir.cross(&ap);
+    let simd_ab = Vector::splat(ab);
+    let simd_ac = Vector::splat(ac);
+    let t_neg = t.simd_lt(zero);
+
+    let ac_e = simd_ac.dot(&e);
+    let v = (-ac_e).select(t_neg, ac_e);
+    valid = valid & v.simd_ge(zero) & v.simd_le(d);
+
+    let ab_e = simd_ab.dot(&e);
+    let w = ab_e.select(t_neg, -ab_e);
+    valid = valid & w.simd_ge(zero) & (v + w).simd_le(d);
+
+    let invd = SimdReal::splat(1.0) / d;
+    let time_of_impact = (-t * invd).select(t_neg, t * invd);
+    valid = valid & time_of_impact.simd_le(max_time_of_impact);
+
+    (valid, time_of_impact)
+}
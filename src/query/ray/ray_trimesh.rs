@@ -1,6 +1,13 @@
 use crate::math::Real;
 use crate::query::{Ray, RayCast, RayIntersection};
 use crate::shape::{CompositeShapeRef, FeatureId, TriMesh};
+#[cfg(feature = "dim3")]
+use {
+    crate::math::{Isometry, SimdReal, SIMD_WIDTH},
+    crate::query::{details::local_ray_toi_with_triangle_simd, SimdRay},
+    alloc::vec::Vec,
+    simba::simd::SimdValue,
+};
 
 #[cfg(feature = "dim3")]
 pub use ray_cast_with_culling::RayCullingMode;
@@ -35,6 +42,64 @@ impl RayCast for TriMesh {
     }
 }
 
+#[cfg(feature = "dim3")]
+impl TriMesh {
+    /// Casts a batch of rays on this triangle mesh transformed by `m`.
+    ///
+    /// This is the same as [`TriMesh::cast_local_rays`] except that the rays are given in
+    /// world-space.
+    pub fn cast_rays(
+        &self,
+        m: &Isometry<Real>,
+        rays: &[Ray],
+        max_time_of_impact: Real,
+    ) -> Vec<Option<(u32, Real)>> {
+        let ls_rays: Vec<_> = rays.iter().map(|ray| ray.inverse_transform_by(m)).collect();
+        self.cast_local_rays(&ls_rays, max_time_of_impact)
+    }
+
+    /// Casts a batch of rays on this triangle mesh.
+    ///
+    /// The rays are traced by packets of [`SIMD_WIDTH`] that traverse the BVH and are tested
+    /// against the triangles together (see [`Bvh::cast_ray_packet`](crate::partitioning::Bvh::cast_ray_packet)).
+    /// This is typically faster than casting each ray individually if consecutive rays are
+    /// coherent (i.e. have similar origins and directions).
+    ///
+    /// Returns, for each ray, the index of the closest triangle hit and the time of impact. The
+    /// time of impact is the same as the one returned by [`RayCast::cast_local_ray`].
+    pub fn cast_local_rays(
+        &self,
+        rays: &[Ray],
+        max_time_of_impact: Real,
+    ) -> Vec<Option<(u32, Real)>> {
+        let mut result = Vec::with_capacity(rays.len());
+
+        for chunk in rays.chunks(SIMD_WIDTH) {
+            // Lanes without a ray are given a negative max time of impact so they never hit.
+            let mut packet = SimdRay::splat(chunk[0]);
+            let mut max_toi = SimdReal::splat(-1.0);
+
+            for (lane, ray) in chunk.iter().enumerate() {
+                packet.origin.replace(lane, ray.origin);
+                packet.dir.replace(lane, ray.dir);
+                max_toi.replace(lane, max_time_of_impact);
+            }
+
+            let hits = self
+                .bvh()
+                .cast_ray_packet(&packet, max_toi, |i, _active, best_toi| {
+                    let tri = self.triangle(i);
+                    let (hit, toi) =
+                        local_ray_toi_with_triangle_simd(&tri.a, &tri.b, &tri.c, &packet, best_toi);
+                    toi.select(hit, SimdReal::splat(Real::MAX))
+                });
+            result.extend_from_slice(&hits[..chunk.len()]);
+        }
+
+        result
+    }
+}
+
 // NOTE: implement the ray-cast with culling on its own submodule to facilitate feature gating.
 #[cfg(feature = "dim3")]
 mod ray_cast_with_culling {
@@ -0,0 +1,318 @@
+//! Statistics collected while running geometric queries.
+//!
+//! When the `stats` feature is enabled, the [`DefaultQueryDispatcher`](crate::query::DefaultQueryDispatcher),
+//! the GJK and EPA algorithms, and the [`Bvh`](crate::partitioning::Bvh) traversals record how much
+//! work they do into a collector local to the current thread. Use `stats::collect` to gather the
+//! statistics of the queries run by a closure, for example to measure each frame of a simulation
+//! separately from any other simulation running on the same thread:
+//!
+//! ```ignore
+//! let (_, stats) = parry3d::query::stats::collect(|| {
+//!     // Run the queries of the frame…
+//! });
+//! println!("BVH nodes visited: {}", stats.bvh_nodes_visited);
+//! ```
+//!
+//! The statistics recorded on the current thread since it started (or since the last call to
+//! `stats::reset`) can also be read with `stats::snapshot`. Because the collector is local to
+//! each thread, queries running concurrently on other threads (including the parallel traversals
+//! enabled by the `parallel` feature) are not recorded, and don’t affect the statistics of the
+//! current thread. The `stats` feature requires the `std` feature. When it is disabled, this
+//! module isn’t public, none of this is compiled, and the queries don’t pay any cost.
+
+use crate::shape::ShapeType;
+
+/// The number of variants of [`ShapeType`].
+#[cfg(feature = "stats")]
+const NUM_SHAPE_TYPES: usize = ShapeType::Custom as usize + 1;
+/// The number of buckets of an [`IterationHistogram`].
+#[cfg(feature = "stats")]
+const NUM_BUCKETS: usize = 8;
+
+/// The queries of the [`QueryDispatcher`](crate::query::QueryDispatcher) and
+/// [`PersistentQueryDispatcher`](crate::query::PersistentQueryDispatcher) traits.
+#[derive(Copy, Clone)]
+#[cfg_attr(not(feature = "stats"), allow(dead_code))]
+pub(crate) enum Query {
+    IntersectionTest,
+    Distance,
+    Contact,
+    ClosestPoints,
+    CastShapes,
+    CastShapesNonlinear,
+    ContactManifolds,
+}
+
+/// A histogram of the number of iterations of an iterative algorithm.
+///
+/// The bucket `0` counts the runs that terminated without iterating, and the bucket `i > 0`
+/// counts the runs with a number of iterations in `[2^(i - 1), 2^i)`. The last bucket counts
+/// all the runs with 64 iterations or more.
+#[cfg(feature = "stats")]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct IterationHistogram {
+    /// The number of times the algorithm was run.
+    pub runs: usize,
+    /// The total number of iterations over all runs.
+    pub iterations: usize,
+    /// The number of runs in each bucket.
+    pub buckets: [usize; NUM_BUCKETS],
+}
+
+#[cfg(feature = "stats")]
+impl IterationHistogram {
+    /// The index of the bucket counting the runs with `iterations` iterations.
+    pub fn bucket(iterations: usize) -> usize {
+        let bits = (usize::BITS - iterations.leading_zeros()) as usize;
+        bits.min(NUM_BUCKETS - 1)
+    }
+
+    /// The average number of iterations per run.
+    pub fn mean(&self) -> f64 {
+        if self.runs == 0 {
+            0.0
+        } else {
+            self.iterations as f64 / self.runs as f64
+        }
+    }
+
+    /// Adds all the runs counted by `other` to `self`.
+    pub fn merge(&mut self, other: &IterationHistogram) {
+        self.runs += other.runs;
+        self.iterations += other.iterations;
+        self.buckets
+            .iter_mut()
+            .zip(other.buckets.iter())
+            .for_each(|(bucket, other)| *bucket += *other);
+    }
+
+    fn record(&mut self, iterations: usize) {
+        self.runs += 1;
+        self.iterations += iterations;
+        self.buckets[Self::bucket(iterations)] += 1;
+    }
+}
+
+/// The query statistics, returned by [`collect`] and [`snapshot`].
+#[cfg(feature = "stats")]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct QueryStats {
+    /// The number of intersection tests run by the default query dispatcher.
+    pub intersection_tests: usize,
+    /// The number of distance queries run by the default query dispatcher.
+    pub distances: usize,
+    /// The number of contact queries run by the default query dispatcher.
+    pub contacts: usize,
+    /// The number of closest-points queries run by the default query dispatcher.
+    pub closest_points: usize,
+    /// The number of shape-casts run by the default query dispatcher.
+    pub shape_casts: usize,
+    /// The number of nonlinear shape-casts run by the default query dispatcher.
+    pub nonlinear_shape_casts: usize,
+    /// The number of contact-manifolds computations run by the default query dispatcher.
+    pub contact_manifolds: usize,
+    /// The histogram of the number of iterations of each GJK run.
+    pub gjk_iterations: IterationHistogram,
+    /// The histogram of the number of iterations of each EPA run.
+    pub epa_iterations: IterationHistogram,
+    /// The number of BVH nodes (including leaves) visited by single-tree traversals.
+    pub bvh_nodes_visited: usize,
+    /// The number of BVH leaves visited by single-tree traversals.
+    pub bvh_leaves_visited: usize,
+    /// The number of pairs of BVH nodes visited by simultaneous traversals of two trees.
+    pub bvh_node_pairs_visited: usize,
+    contact_manifold_generators: [[usize; NUM_SHAPE_TYPES]; NUM_SHAPE_TYPES],
+}
+
+#[cfg(feature = "stats")]
+impl QueryStats {
+    /// The number of times a contact manifold generator was invoked for a pair of shapes with
+    /// the given types.
+    ///
+    /// This counts the generators for convex shapes, as well as those for composite shapes,
+    /// heightfields and voxels, invoked by the default query dispatcher.
+    pub fn contact_manifold_generators(&self, shape1: ShapeType, shape2: ShapeType) -> usize {
+        self.contact_manifold_generators[shape1 as usize][shape2 as usize]
+    }
+
+    /// The total number of times a contact manifold generator was invoked, for all pairs of
+    /// shape types.
+    pub fn total_contact_manifold_generators(&self) -> usize {
+        self.contact_manifold_generators.iter().flatten().sum()
+    }
+
+    /// Adds all the statistics of `other` to `self`.
+    pub fn merge(&mut self, other: &QueryStats) {
+        self.intersection_tests += other.intersection_tests;
+        self.distances += other.distances;
+        self.contacts += other.contacts;
+        self.closest_points += other.closest_points;
+        self.shape_casts += other.shape_casts;
+        self.nonlinear_shape_casts += other.nonlinear_shape_casts;
+        self.contact_manifolds += other.contact_manifolds;
+        self.gjk_iterations.merge(&other.gjk_iterations);
+        self.epa_iterations.merge(&other.epa_iterations);
+        self.bvh_nodes_visited += other.bvh_nodes_visited;
+        self.bvh_leaves_visited += other.bvh_leaves_visited;
+        self.bvh_node_pairs_visited += other.bvh_node_pairs_visited;
+        self.contact_manifold_generators
+            .iter_mut()
+            .flatten()
+            .zip(other.contact_manifold_generators.iter().flatten())
+            .for_each(|(count, other)| *count += *other);
+    }
+}
+
+/// Runs `f` and returns its result, together with the statistics of the queries it ran on the
+/// current thread.
+///
+/// The statistics of `f` are also added to the ones returned by [`snapshot`], so calls to
+/// `collect` can be nested.
+#[cfg(feature = "stats")]
+pub fn collect<R>(f: impl FnOnce() -> R) -> (R, QueryStats) {
+    /// Adds the statistics of `f` to the outer ones and restores them, even if `f` panics.
+    struct Scope(QueryStats);
+
+    impl Drop for Scope {
+        fn drop(&mut self) {
+            collector::with(|current| {
+                self.0.merge(current);
+                core::mem::swap(current, &mut self.0);
+            });
+        }
+    }
+
+    let scope = Scope(collector::with(core::mem::take));
+    let result = f();
+    let stats = snapshot();
+    drop(scope);
+    (result, stats)
+}
+
+/// Reads all the query statistics recorded on the current thread.
+#[cfg(feature = "stats")]
+pub fn snapshot() -> QueryStats {
+    collector::with(|current| current.clone())
+}
+
+/// Resets all the query statistics recorded on the current thread to zero.
+#[cfg(feature = "stats")]
+pub fn reset() {
+    collector::with(|current| *current = QueryStats::default())
+}
+
+#[cfg(feature = "stats")]
+mod collector {
+    use super::QueryStats;
+    use core::cell::RefCell;
+
+    std::thread_local! {
+        static CURRENT: RefCell<QueryStats> = RefCell::new(QueryStats::default());
+    }
+
+    pub fn with<R>(f: impl FnOnce(&mut QueryStats) -> R) -> R {
+        CURRENT.with_borrow_mut(f)
+    }
+}
+
+/*
+ * Recording hooks. They compile to nothing when the `stats` feature is disabled.
+ */
+/// Records one run of the given query by the default query dispatcher.
+#[inline(always)]
+pub(crate) fn record_query(_query: Query) {
+    #[cfg(feature = "stats")]
+    collector::with(|stats| {
+        let counter = match _query {
+            Query::IntersectionTest => &mut stats.intersection_tests,
+            Query::Distance => &mut stats.distances,
+            Query::Contact => &mut stats.contacts,
+            Query::ClosestPoints => &mut stats.closest_points,
+            Query::CastShapes => &mut stats.shape_casts,
+            Query::CastShapesNonlinear => &mut stats.nonlinear_shape_casts,
+            Query::ContactManifolds => &mut stats.contact_manifolds,
+        };
+        *counter += 1;
+    });
+}
+
+/// Records one invocation of a contact manifold generator for the given pair of shape types.
+#[cfg_attr(not(feature = "alloc"), allow(dead_code))]
+#[inline(always)]
+pub(crate) fn record_contact_manifold_generator(_shape1: ShapeType, _shape2: ShapeType) {
+    #[cfg(feature = "stats")]
+    collector::with(|stats| {
+        stats.contact_manifold_generators[_shape1 as usize][_shape2 as usize] += 1;
+    });
+}
+
+/// Records the visit of a BVH node by a single-tree traversal.
+#[cfg_attr(not(feature = "alloc"), allow(dead_code))]
+#[inline(always)]
+pub(crate) fn record_bvh_node_visit(_is_leaf: bool) {
+    #[cfg(feature = "stats")]
+    collector::with(|stats| {
+        stats.bvh_nodes_visited += 1;
+
+        if _is_leaf {
+            stats.bvh_leaves_visited += 1;
+        }
+    });
+}
+
+/// Records the visit of a pair of BVH nodes by a simultaneous traversal of two trees.
+#[cfg_attr(not(feature = "alloc"), allow(dead_code))]
+#[inline(always)]
+pub(crate) fn record_bvh_node_pair_visit() {
+    #[cfg(feature = "stats")]
+    collector::with(|stats| stats.bvh_node_pairs_visited += 1);
+}
+
+/// Counts the iterations of a single GJK or EPA run, and records them when dropped.
+pub(crate) struct IterationCounter {
+    #[cfg(feature = "stats")]
+    iterations: usize,
+    #[cfg(feature = "stats")]
+    histogram: fn(&mut QueryStats) -> &mut IterationHistogram,
+}
+
+impl IterationCounter {
+    /// Starts counting the iterations of a GJK run.
+    #[inline(always)]
+    pub fn gjk() -> Self {
+        Self {
+            #[cfg(feature = "stats")]
+            iterations: 0,
+            #[cfg(feature = "stats")]
+            histogram: |stats| &mut stats.gjk_iterations,
+        }
+    }
+
+    /// Starts counting the iterations of an EPA run.
+    #[cfg_attr(not(feature = "alloc"), allow(dead_code))]
+    #[inline(always)]
+    pub fn epa() -> Self {
+        Self {
+            #[cfg(feature = "stats")]
+            iterations: 0,
+            #[cfg(feature = "stats")]
+            histogram: |stats| &mut stats.epa_iterations,
+        }
+    }
+
+    /// Counts one more iteration.
+    #[inline(always)]
+    pub fn step(&mut self) {
+        #[cfg(feature = "stats")]
+        {
+            self.iterations += 1;
+        }
+    }
+}
+
+#[cfg(feature = "stats")]
+impl Drop for IterationCounter {
+    fn drop(&mut self) {
+        collector::with(|stats| (self.histogram)(stats).record(self.iterations));
+    }
+}
@@ -38,15 +38,13 @@ impl Compound {
             "A compound shape must contain at least one shape."
         );
         let mut aabbs = Vec::new();
-        let mut leaves = Vec::new();
         let mut aabb = Aabb::new_invalid();
 
-        for (i, (delta, shape)) in shapes.iter().enumerate() {
+        for (delta, shape) in shapes.iter() {
             let bv = shape.compute_aabb(delta);
 
             aabb.merge(&bv);
             aabbs.push(bv);
-            leaves.push((i, bv));
 
             if shape.as_composite_shape().is_some() {
                 panic!("Nested composite shapes are not allowed.");
@@ -55,7 +53,10 @@ impl Compound {
 
         // NOTE: we apply no dilation factor because we won't
         // update this tree dynamically.
-        let bvh = Bvh::from_iter(BvhBuildStrategy::Binned, leaves);
+        #[cfg(feature = "parallel")]
+        let bvh = Bvh::from_leaves_parallel(BvhBuildStrategy::Binned, &aabbs);
+        #[cfg(not(feature = "parallel"))]
+        let bvh = Bvh::from_leaves(BvhBuildStrategy::Binned, &aabbs);
 
         Compound {
             shapes,
@@ -437,6 +437,12 @@ impl ConvexPolyhedron {
         &self.faces_adj_to_vertex[..]
     }
 
+    /// The array containing the indices of the edges adjacent to each vertex.
+    #[inline]
+    pub fn edges_adj_to_vertex(&self) -> &[u32] {
+        &self.edges_adj_to_vertex[..]
+    }
+
     /// Computes a scaled version of this convex polygon.
     ///
     /// Returns `None` if the result had degenerate normals (for example if
@@ -129,7 +129,7 @@ impl HeightField {
         self.heights.ncols() - 1
     }
 
-    fn triangle_id(&self, i: usize, j: usize, left: bool) -> u32 {
+    pub(crate) fn triangle_id(&self, i: usize, j: usize, left: bool) -> u32 {
         let tid = j * (self.heights.nrows() - 1) + i;
         if left {
             tid as u32
@@ -116,6 +116,9 @@ mod polygonal_feature_map;
 mod tetrahedron;
 #[cfg(feature = "alloc")]
 pub(crate) mod trimesh;
+#[cfg(feature = "dim3")]
+#[cfg(feature = "alloc")]
+mod trimesh_winding_number;
 // TODO: move this elsewhere?
 mod feature_id;
 #[cfg(feature = "dim2")]
@@ -12,6 +12,8 @@ use crate::shape::{
 };
 #[cfg(feature = "dim3")]
 use crate::shape::{Cone, ConvexPolyhedron, Cylinder};
+#[cfg(all(feature = "dim3", feature = "spade"))]
+use crate::transformation::coacd::{self, CoACDParameters};
 use crate::transformation::vhacd::{VHACDParameters, VHACD};
 use crate::transformation::voxelization::{FillMode, VoxelSet};
 use alloc::sync::Arc;
@@ -368,6 +370,31 @@ impl SharedShape {
         Self::compound(parts)
     }
 
+    /// Initializes a compound shape obtained from the exact decomposition of the given closed
+    /// trimesh into convex parts, by recursive plane cuts.
+    ///
+    /// See [`coacd::decompose`] for details. Returns `None` if no convex part could be computed.
+    #[cfg(all(feature = "dim3", feature = "spade"))]
+    pub fn exact_convex_decomposition(
+        vertices: &[Point<Real>],
+        indices: &[[u32; 3]],
+    ) -> Option<Self> {
+        Self::exact_convex_decomposition_with_params(vertices, indices, &CoACDParameters::default())
+    }
+
+    /// Initializes a compound shape obtained from the exact decomposition of the given closed
+    /// trimesh into convex parts, by recursive plane cuts.
+    ///
+    /// See [`coacd::decompose`] for details. Returns `None` if no convex part could be computed.
+    #[cfg(all(feature = "dim3", feature = "spade"))]
+    pub fn exact_convex_decomposition_with_params(
+        vertices: &[Point<Real>],
+        indices: &[[u32; 3]],
+        params: &CoACDParameters,
+    ) -> Option<Self> {
+        coacd::decompose(vertices, indices, params).map(|compound| SharedShape(Arc::new(compound)))
+    }
+
     /// Creates a new shared shape that is the convex-hull of the given points.
     pub fn convex_hull(points: &[Point<Real>]) -> Option<Self> {
         #[cfg(feature = "dim2")]
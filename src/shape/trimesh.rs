@@ -6,7 +6,10 @@ use crate::utils::HashablePartialEq;
 use alloc::{vec, vec::Vec};
 use core::fmt;
 #[cfg(feature = "dim3")]
-use {crate::shape::Cuboid, crate::utils::SortedPair, na::Unit};
+use {
+    crate::shape::trimesh_winding_number::TriMeshWindingNumbers, crate::shape::Cuboid,
+    crate::utils::SortedPair, na::Unit,
+};
 
 use {
     crate::shape::composite_shape::CompositeShape,
@@ -271,6 +274,12 @@ bitflags::bitflags! {
         /// This is achieved by taking into account adjacent triangle normals when computing contact
         /// points for a given triangle.
         const FIX_INTERNAL_EDGES = (1 << 7) | Self::MERGE_DUPLICATE_VERTICES.bits();
+        /// If set, the data needed to compute fast generalized winding numbers will be computed.
+        ///
+        /// The point-containment test of the trimesh will then be based on its winding number
+        /// instead of its pseudo-normals, which is more robust on meshes with holes,
+        /// self-intersections, or inconsistent orientations. This is only supported in 3D.
+        const WINDING_NUMBERS = 1 << 8;
     }
 }
 
@@ -289,6 +298,8 @@ pub struct TriMesh {
     indices: Vec<[u32; 3]>,
     #[cfg(feature = "dim3")]
     pub(crate) pseudo_normals: Option<TriMeshPseudoNormals>,
+    #[cfg(feature = "dim3")]
+    pub(crate) winding_numbers: Option<TriMeshWindingNumbers>,
     topology: Option<TriMeshTopology>,
     connected_components: Option<TriMeshConnectedComponents>,
     flags: TriMeshFlags,
@@ -325,6 +336,8 @@ impl TriMesh {
             indices,
             #[cfg(feature = "dim3")]
             pseudo_normals: None,
+            #[cfg(feature = "dim3")]
+            winding_numbers: None,
             topology: None,
             connected_components: None,
             flags: TriMeshFlags::empty(),
@@ -354,6 +367,11 @@ impl TriMesh {
             self.pseudo_normals = None;
         }
 
+        #[cfg(feature = "dim3")]
+        if !flags.contains(TriMeshFlags::WINDING_NUMBERS) {
+            self.winding_numbers = None;
+        }
+
         if !flags.contains(TriMeshFlags::CONNECTED_COMPONENTS) {
             self.connected_components = None;
         }
@@ -392,6 +410,14 @@ impl TriMesh {
             self.rebuild_bvh();
         }
 
+        #[cfg(feature = "dim3")]
+        if difference.contains(TriMeshFlags::WINDING_NUMBERS) {
+            if self.bvh.is_empty() {
+                self.rebuild_bvh();
+            }
+            self.winding_numbers = Some(TriMeshWindingNumbers::new(self));
+        }
+
         self.flags = flags;
         result
     }
@@ -415,6 +441,8 @@ impl TriMesh {
             flags: _,
             #[cfg(feature = "dim3")]
             pseudo_normals,
+            #[cfg(feature = "dim3")]
+            winding_numbers,
         } = self;
         let sz_bvh = bvh.heap_memory_size();
         let sz_vertices = vertices.capacity() * size_of::<Point<Real>>();
@@ -429,6 +457,13 @@ impl TriMesh {
             .unwrap_or(0);
         #[cfg(feature = "dim2")]
         let sz_pseudo_normals = 0;
+        #[cfg(feature = "dim3")]
+        let sz_winding_numbers = winding_numbers
+            .as_ref()
+            .map(|wn| wn.heap_memory_size())
+            .unwrap_or(0);
+        #[cfg(feature = "dim2")]
+        let sz_winding_numbers = 0;
         let sz_topology = topology
             .as_ref()
             .map(|t| {
@@ -450,6 +485,7 @@ impl TriMesh {
             + sz_vertices
             + sz_indices
             + sz_pseudo_normals
+            + sz_winding_numbers
             + sz_topology
             + sz_connected_components
     }
@@ -502,16 +538,27 @@ impl TriMesh {
         let mut bvh = self.bvh.clone();
         bvh.scale(scale);
 
-        Self {
+        #[allow(unused_mut)] // mut is needed in 3D.
+        let mut result = Self {
             bvh,
             vertices: self.vertices,
             indices: self.indices,
             #[cfg(feature = "dim3")]
             pseudo_normals: self.pseudo_normals,
+            #[cfg(feature = "dim3")]
+            winding_numbers: None,
             topology: self.topology,
             connected_components: self.connected_components,
             flags: self.flags,
+        };
+
+        // The dipoles don’t scale uniformly: they must be recomputed.
+        #[cfg(feature = "dim3")]
+        if self.winding_numbers.is_some() {
+            result.winding_numbers = Some(TriMeshWindingNumbers::new(&result));
         }
+
+        result
     }
 
     /// Appends a second triangle mesh to this triangle mesh.
@@ -532,6 +579,8 @@ impl TriMesh {
     /// Create a `TriMesh` from a set of points assumed to describe a counter-clockwise non-convex polygon.
     ///
     /// This operation may fail if the input polygon is invalid, e.g. it is non-simple or has zero surface area.
+    /// See [`constrained_delaunay_triangulation`](crate::transformation::constrained_delaunay_triangulation)
+    /// for triangulating polygons with holes.
     #[cfg(feature = "dim2")]
     pub fn from_polygon(vertices: Vec<Point<Real>>) -> Option<Self> {
         triangulate_ear_clipping(&vertices).map(|indices| Self::new(vertices, indices).unwrap())
@@ -547,17 +596,35 @@ impl TriMesh {
     }
 
     fn rebuild_bvh(&mut self) {
-        let leaves = self.indices.iter().enumerate().map(|(i, idx)| {
+        let vertices = &self.vertices;
+        let leaf = |(i, idx): (usize, &[u32; 3])| {
             let aabb = Triangle::new(
-                self.vertices[idx[0] as usize],
-                self.vertices[idx[1] as usize],
-                self.vertices[idx[2] as usize],
+                vertices[idx[0] as usize],
+                vertices[idx[1] as usize],
+                vertices[idx[2] as usize],
             )
             .local_aabb();
             (i, aabb)
-        });
+        };
 
-        self.bvh = Bvh::from_iter(BvhBuildStrategy::Binned, leaves)
+        #[cfg(feature = "parallel")]
+        {
+            use rayon::prelude::*;
+            let leaves = self.indices.par_iter().enumerate().map(leaf);
+            self.bvh = Bvh::from_iter_parallel(BvhBuildStrategy::Binned, leaves)
+        }
+
+        #[cfg(not(feature = "parallel"))]
+        {
+            let leaves = self.indices.iter().enumerate().map(leaf);
+            self.bvh = Bvh::from_iter(BvhBuildStrategy::Binned, leaves)
+        }
+
+        // The dipoles are stored per BVH node so they must be recomputed too.
+        #[cfg(feature = "dim3")]
+        if self.winding_numbers.is_some() {
+            self.winding_numbers = Some(TriMeshWindingNumbers::new(self));
+        }
     }
 
     /// Reverse the orientation of the triangle mesh.
@@ -565,7 +632,7 @@ impl TriMesh {
         self.indices.iter_mut().for_each(|idx| idx.swap(0, 1));
 
         // NOTE: the BVH, and connected components are not changed by this operation.
-        //       The pseudo-normals just have to be flipped.
+        //       The pseudo-normals and winding number dipoles just have to be flipped.
         //       The topology must be recomputed.
 
         #[cfg(feature = "dim3")]
@@ -581,6 +648,11 @@ impl TriMesh {
             }
         }
 
+        #[cfg(feature = "dim3")]
+        if let Some(winding_numbers) = &mut self.winding_numbers {
+            winding_numbers.reverse();
+        }
+
         if self.flags.contains(TriMeshFlags::HALF_EDGE_TOPOLOGY) {
             // TODO: this could be done more efficiently.
             let _ = self.compute_topology(false);
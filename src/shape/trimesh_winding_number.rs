@@ -0,0 +1,205 @@
+//! Fast generalized winding numbers, as described in:
+//! "Fast Winding Numbers for Soups and Clouds", Barill, et al.
+//! DOI: 10.1145/3197517.3201337
+
+use crate::math::{Point, Real, Vector};
+use crate::partitioning::{Bvh, BvhNode};
+use crate::shape::{TriMesh, Triangle};
+use alloc::vec::Vec;
+use na::RealField;
+
+/// The far-field approximation of a subtree is used for points further than this factor times
+/// the radius of the subtree from its center.
+const WINDING_NUMBER_ACCURACY: Real = 3.0;
+
+/// The dipole approximating the contribution of all the triangles of a BVH subtree to the
+/// winding number.
+#[derive(Copy, Clone, Debug, Default)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Deserialize, rkyv::Serialize),
+    archive(check_bytes)
+)]
+struct WindingNumberDipole {
+    /// The area-weighted center of the subtree’s triangles.
+    center: Point<Real>,
+    /// The total area of the subtree’s triangles.
+    area: Real,
+    /// The sum of the subtree’s triangle normals, scaled by their areas.
+    area_normal: Vector<Real>,
+    /// The radius of a ball centered at `center` containing all the subtree’s triangles.
+    radius: Real,
+    /// The number of BVH nodes in the subtree (including its root).
+    subtree_len: u32,
+}
+
+/// The data needed to compute fast generalized winding numbers of a [`TriMesh`].
+///
+/// This stores one dipole per node of the mesh’s BVH, in depth-first order.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Deserialize, rkyv::Serialize),
+    archive(check_bytes)
+)]
+pub(crate) struct TriMeshWindingNumbers {
+    dipoles: Vec<WindingNumberDipole>,
+}
+
+impl TriMeshWindingNumbers {
+    pub(crate) fn new(mesh: &TriMesh) -> Self {
+        let mut result = Self::default();
+        for root in mesh.bvh().root_nodes() {
+            let _ = result.push_subtree(mesh, root);
+        }
+        result
+    }
+
+    pub(crate) fn heap_memory_size(&self) -> usize {
+        self.dipoles.capacity() * size_of::<WindingNumberDipole>()
+    }
+
+    /// Flips the orientation of all the dipoles, after the orientation of all the triangles
+    /// was reversed.
+    pub(crate) fn reverse(&mut self) {
+        self.dipoles
+            .iter_mut()
+            .for_each(|dipole| dipole.area_normal = -dipole.area_normal);
+    }
+
+    /// Pushes the dipoles of the subtree rooted at `node` and returns the subtree’s dipole.
+    fn push_subtree(&mut self, mesh: &TriMesh, node: &BvhNode) -> WindingNumberDipole {
+        let id = self.dipoles.len();
+        self.dipoles.push(WindingNumberDipole::default());
+
+        let dipole = if let Some(tri_id) = node.leaf_data() {
+            let tri = mesh.triangle(tri_id);
+            let center = tri.center();
+            let radius = tri
+                .vertices()
+                .iter()
+                .map(|pt| na::distance(pt, &center))
+                .fold(0.0, Real::max);
+
+            WindingNumberDipole {
+                center,
+                area: tri.area(),
+                area_normal: tri.scaled_normal() / 2.0,
+                radius,
+                subtree_len: 1,
+            }
+        } else {
+            let [left, right] = mesh.bvh().node_children(node).unwrap();
+            let left = self.push_subtree(mesh, left);
+            let right = self.push_subtree(mesh, right);
+            let area = left.area + right.area;
+            let center = if area > 0.0 {
+                (left.center * left.area + right.center.coords * right.area) / area
+            } else {
+                na::center(&left.center, &right.center)
+            };
+            let radius = (na::distance(&center, &left.center) + left.radius)
+                .max(na::distance(&center, &right.center) + right.radius);
+
+            WindingNumberDipole {
+                center,
+                area,
+                area_normal: left.area_normal + right.area_normal,
+                radius,
+                subtree_len: (self.dipoles.len() - id) as u32,
+            }
+        };
+
+        self.dipoles[id] = dipole;
+        dipole
+    }
+
+    /// Computes the winding number of `pt` with respect to the subtree rooted at `node`, whose
+    /// dipole is at index `*id`, and advances `*id` past that subtree.
+    fn subtree_winding_number(
+        &self,
+        mesh: &TriMesh,
+        bvh: &Bvh,
+        node: &BvhNode,
+        pt: &Point<Real>,
+        id: &mut usize,
+    ) -> Real {
+        let dipole = &self.dipoles[*id];
+
+        if let Some(tri_id) = node.leaf_data() {
+            *id += 1;
+            return triangle_solid_angle(&mesh.triangle(tri_id), pt);
+        }
+
+        let dpt = dipole.center - pt;
+        let dist = dpt.norm();
+
+        if dist > dipole.radius * WINDING_NUMBER_ACCURACY {
+            *id += dipole.subtree_len as usize;
+            return dipole.area_normal.dot(&dpt) / (dist * dist * dist);
+        }
+
+        *id += 1;
+        let [left, right] = bvh.node_children(node).unwrap();
+        self.subtree_winding_number(mesh, bvh, left, pt, id)
+            + self.subtree_winding_number(mesh, bvh, right, pt, id)
+    }
+}
+
+/// The signed solid angle subtended by a triangle seen from `pt`.
+///
+/// This uses the formula from "The Solid Angle of a Plane Triangle", Van Oosterom, et al.
+/// DOI: 10.1109/TBME.1983.325207
+fn triangle_solid_angle(tri: &Triangle, pt: &Point<Real>) -> Real {
+    let a = tri.a - pt;
+    let b = tri.b - pt;
+    let c = tri.c - pt;
+    let (la, lb, lc) = (a.norm(), b.norm(), c.norm());
+    let numerator = a.dot(&b.cross(&c));
+    let denominator = la * lb * lc + a.dot(&b) * lc + a.dot(&c) * lb + b.dot(&c) * la;
+    2.0 * numerator.atan2(denominator)
+}
+
+impl TriMesh {
+    /// Computes the generalized winding number of a point with respect to this mesh.
+    ///
+    /// The winding number is close to `1.0` for points inside of a closed mesh with outward
+    /// normals, and close to `0.0` for points outside of it. Unlike the containment test based on
+    /// pseudo-normals, it degrades gracefully on meshes with holes, self-intersections, or
+    /// duplicate triangles, taking fractional values near the defects.
+    ///
+    /// If this mesh has the [`TriMeshFlags::WINDING_NUMBERS`] flag, the winding number is
+    /// approximated hierarchically using dipoles precomputed for each node of the mesh’s BVH,
+    /// which runs in logarithmic time. Otherwise, the exact winding number is computed by
+    /// summing the solid angles of all the triangles.
+    ///
+    /// [`TriMeshFlags::WINDING_NUMBERS`]: crate::shape::TriMeshFlags::WINDING_NUMBERS
+    pub fn winding_number(&self, pt: &Point<Real>) -> Real {
+        let total_solid_angle = if let Some(winding_numbers) = &self.winding_numbers {
+            let mut id = 0;
+            self.bvh()
+                .root_nodes()
+                .map(|root| {
+                    winding_numbers.subtree_winding_number(self, self.bvh(), root, pt, &mut id)
+                })
+                .sum::<Real>()
+        } else {
+            self.triangles()
+                .map(|tri| triangle_solid_angle(&tri, pt))
+                .sum::<Real>()
+        };
+
+        total_solid_angle / (4.0 * Real::pi())
+    }
+
+    /// Checks if a point is inside of this mesh based on its generalized winding number.
+    ///
+    /// A point is considered inside if its [`TriMesh::winding_number`] is at least `0.5`. This
+    /// is robust to holes and to inconsistent triangle orientations as long as they remain
+    /// small compared to the whole mesh.
+    pub fn contains_local_point_with_winding_number(&self, pt: &Point<Real>) -> bool {
+        self.winding_number(pt) >= 0.5
+    }
+}
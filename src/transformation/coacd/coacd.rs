@@ -0,0 +1,381 @@
+//! Exact convex decomposition by recursive plane cuts, following the approach of:
+//! "Approximate Convex Decomposition for 3D Meshes with Collision-Aware Concavity and Tree
+//! Search", Wei, et al. DOI: 10.1145/3528223.3530103
+
+use crate::bounding_volume::{Aabb, BoundingVolume};
+use crate::math::{Isometry, Point, Real};
+use crate::query::details::{hausdorff_distance_trimesh_shape, hausdorff_distance_trimesh_trimesh};
+use crate::query::SplitResult;
+use crate::shape::{Compound, ConvexPolyhedron, SharedShape, TriMesh, TriMeshFlags};
+use crate::transformation::coacd::CoACDParameters;
+use crate::transformation::try_convex_hull;
+use crate::utils::hashmap::HashMap;
+use alloc::vec::Vec;
+
+type ConvexHull = (Vec<Point<Real>>, Vec<[u32; 3]>);
+
+/// A convex part of the decomposition, with the piece of the input mesh it covers.
+struct Part {
+    mesh: TriMesh,
+    hull: ConvexHull,
+}
+
+impl Part {
+    fn new(mesh: TriMesh) -> Option<Self> {
+        let hull = try_convex_hull(mesh.vertices()).ok()?;
+        (hull.1.len() >= 4).then_some(Self { mesh, hull })
+    }
+
+    /// The symmetric Hausdorff distance between the boundaries of this part and of its hull.
+    ///
+    /// Returns `Real::MAX` if the hull can’t be turned into a triangle mesh.
+    fn concavity(&self, sample_spacing: Real) -> Real {
+        let Ok(hull) = TriMesh::new(self.hull.0.clone(), self.hull.1.clone()) else {
+            return Real::MAX;
+        };
+        hausdorff_distance_trimesh_trimesh(
+            &Isometry::identity(),
+            &hull,
+            &Isometry::identity(),
+            &self.mesh,
+            sample_spacing,
+        )
+        .max
+    }
+}
+
+/// Decomposes a closed triangle mesh into convex parts, by cutting it recursively with planes.
+///
+/// Unlike [`VHACD`](crate::transformation::vhacd::VHACD), which operates on a voxelization of
+/// the input, the mesh is cut exactly and each cut is closed by triangulating the cross-section,
+/// so the quality of the decomposition doesn’t depend on a resolution and thin features are
+/// preserved. A part is cut into two halves as long as its concavity, measured as the Hausdorff
+/// distance between its boundary and the boundary of its convex hull, exceeds
+/// [`CoACDParameters::concavity`]. The cutting plane minimizing the volume added by the convex
+/// hulls of the halves is selected among axis-aligned candidates. Finally, the hulls of nearby
+/// parts are merged if the merged hull remains within the concavity bound.
+///
+/// The input mesh must be closed and consistently oriented. Returns `None` if no convex part
+/// could be computed, e.g., if the mesh is empty or flat.
+///
+/// # Panics
+///
+/// Panics if [`CoACDParameters::concavity`] isn’t in `[0.0, 1.0]`.
+pub fn decompose(
+    vertices: &[Point<Real>],
+    indices: &[[u32; 3]],
+    params: &CoACDParameters,
+) -> Option<Compound> {
+    assert!(
+        (0.0..=1.0).contains(&params.concavity),
+        "The concavity must be in [0.0, 1.0]."
+    );
+
+    let mesh = TriMesh::with_flags(
+        vertices.to_vec(),
+        indices.to_vec(),
+        TriMeshFlags::MERGE_DUPLICATE_VERTICES | TriMeshFlags::ORIENTED,
+    )
+    .ok()?;
+    let diagonal = mesh.local_aabb().extents().norm();
+    let max_concavity = params.concavity * diagonal;
+    // Sample the surfaces finely enough to estimate concavities with a precision
+    // proportional to the concavity bound.
+    let sample_spacing = max_concavity.max(diagonal * 1.0e-3);
+    let epsilon = diagonal * 1.0e-6;
+
+    let mut stack: Vec<_> = connected_components(&mesh)
+        .into_iter()
+        .map(|mesh| (mesh, 0))
+        .collect();
+    let mut parts = Vec::new();
+
+    while let Some((mesh, depth)) = stack.pop() {
+        let Some(part) = Part::new(mesh) else {
+            // The part is flat.
+            continue;
+        };
+
+        if depth >= params.max_depth || part.concavity(sample_spacing) <= max_concavity {
+            parts.push(part);
+            continue;
+        }
+
+        match best_cut(&part.mesh, params.planes_per_axis.max(1), epsilon) {
+            Some((lhs, rhs)) => {
+                for piece in [lhs, rhs] {
+                    stack.extend(
+                        connected_components(&piece)
+                            .into_iter()
+                            .map(|mesh| (mesh, depth + 1)),
+                    );
+                }
+            }
+            None => parts.push(part),
+        }
+    }
+
+    let hulls = merge_parts(parts, params, max_concavity, sample_spacing);
+    let shapes: Vec<_> = hulls
+        .into_iter()
+        .filter_map(|(vertices, indices)| {
+            let convex = ConvexPolyhedron::from_convex_mesh(vertices, &indices)?;
+            Some((Isometry::identity(), SharedShape::new(convex)))
+        })
+        .collect();
+
+    (!shapes.is_empty()).then(|| Compound::new(shapes))
+}
+
+/// Finds the axis-aligned plane cutting `mesh` into two halves with the smallest total
+/// difference between the volumes of their convex hulls and their own volumes.
+fn best_cut(mesh: &TriMesh, planes_per_axis: u32, epsilon: Real) -> Option<(TriMesh, TriMesh)> {
+    let aabb = mesh.local_aabb();
+    let mut best: Option<(Real, TriMesh, TriMesh)> = None;
+    let mut best_plane = None;
+
+    let mut evaluate = |axis: usize, bias: Real, best_plane: &mut Option<(usize, Real)>| {
+        let SplitResult::Pair(lhs, rhs) = mesh.canonical_split(axis, bias, epsilon) else {
+            return;
+        };
+        // Skip the cuts producing a half whose convex hull can’t be computed.
+        let (Some(lhs_cost), Some(rhs_cost)) = (hull_volume_excess(&lhs), hull_volume_excess(&rhs))
+        else {
+            return;
+        };
+        let cost = lhs_cost + rhs_cost;
+
+        if best
+            .as_ref()
+            .is_none_or(|(best_cost, _, _)| cost < *best_cost)
+        {
+            best = Some((cost, lhs, rhs));
+            *best_plane = Some((axis, bias));
+        }
+    };
+
+    let steps = planes_per_axis as Real + 1.0;
+
+    for axis in 0..3 {
+        let step = aabb.extents()[axis] / steps;
+
+        for k in 1..=planes_per_axis {
+            evaluate(axis, aabb.mins[axis] + step * k as Real, &mut best_plane);
+        }
+    }
+
+    // Refine the best plane by sampling the interval between its neighbors.
+    if let Some((axis, bias)) = best_plane {
+        let step = aabb.extents()[axis] / steps;
+        let fine_step = step * 2.0 / steps;
+
+        for k in 1..=planes_per_axis {
+            let fine_bias = bias - step + fine_step * k as Real;
+
+            if fine_bias != bias {
+                evaluate(axis, fine_bias, &mut best_plane);
+            }
+        }
+    }
+
+    best.map(|(_, lhs, rhs)| (lhs, rhs))
+}
+
+/// The volume of the convex hull of a closed mesh, minus the volume of the mesh.
+///
+/// Returns `None` if the convex hull can’t be computed.
+fn hull_volume_excess(mesh: &TriMesh) -> Option<Real> {
+    let (hull_vertices, hull_indices) = try_convex_hull(mesh.vertices()).ok()?;
+    Some(
+        signed_volume(&hull_vertices, &hull_indices)
+            - signed_volume(mesh.vertices(), mesh.indices()),
+    )
+}
+
+fn signed_volume(vertices: &[Point<Real>], indices: &[[u32; 3]]) -> Real {
+    indices
+        .iter()
+        .map(|idx| {
+            let [a, b, c] = idx.map(|i| vertices[i as usize].coords);
+            a.dot(&b.cross(&c)) / 6.0
+        })
+        .sum()
+}
+
+/// Splits a mesh into its connected components, each with pseudo-normals so it can be cut
+/// further with capping.
+fn connected_components(mesh: &TriMesh) -> Vec<TriMesh> {
+    let vertices = mesh.vertices();
+    let indices = mesh.indices();
+    let mut parents: Vec<u32> = (0..vertices.len() as u32).collect();
+
+    fn find(parents: &mut [u32], mut i: u32) -> u32 {
+        while parents[i as usize] != i {
+            parents[i as usize] = parents[parents[i as usize] as usize];
+            i = parents[i as usize];
+        }
+        i
+    }
+
+    for idx in indices {
+        let root = find(&mut parents, idx[0]);
+        for i in &idx[1..] {
+            let other = find(&mut parents, *i);
+            parents[other as usize] = root;
+        }
+    }
+
+    // Triangles and remapped vertices of each component, by root vertex.
+    let mut components: Vec<(Vec<Point<Real>>, Vec<[u32; 3]>)> = Vec::new();
+    let mut component_ids = HashMap::default();
+    let mut vertex_ids = alloc::vec![u32::MAX; vertices.len()];
+
+    for idx in indices {
+        let root = find(&mut parents, idx[0]);
+        let component_id = *component_ids.entry(root).or_insert_with(|| {
+            components.push((Vec::new(), Vec::new()));
+            components.len() - 1
+        });
+        let (component_vertices, component_indices) = &mut components[component_id];
+        let new_idx = idx.map(|i| {
+            if vertex_ids[i as usize] == u32::MAX {
+                vertex_ids[i as usize] = component_vertices.len() as u32;
+                component_vertices.push(vertices[i as usize]);
+            }
+            vertex_ids[i as usize]
+        });
+        component_indices.push(new_idx);
+    }
+
+    components
+        .into_iter()
+        .filter_map(|(vertices, indices)| {
+            TriMesh::with_flags(vertices, indices, TriMeshFlags::ORIENTED).ok()
+        })
+        .collect()
+}
+
+/// A set of merged parts.
+struct MergedPart {
+    /// The concatenated meshes of the merged parts.
+    mesh: TriMesh,
+    hull: ConvexHull,
+    aabb: Aabb,
+}
+
+impl MergedPart {
+    fn merge(&self, other: &Self) -> Option<Self> {
+        let mut points = self.hull.0.clone();
+        points.extend_from_slice(&other.hull.0);
+        let hull = try_convex_hull(&points).ok()?;
+
+        let mut vertices = self.mesh.vertices().to_vec();
+        let base = vertices.len() as u32;
+        vertices.extend_from_slice(other.mesh.vertices());
+        let mut indices = self.mesh.indices().to_vec();
+        indices.extend(other.mesh.indices().iter().map(|idx| idx.map(|i| i + base)));
+
+        Some(Self {
+            mesh: TriMesh::new(vertices, indices).ok()?,
+            hull,
+            aabb: self.aabb.merged(&other.aabb),
+        })
+    }
+
+    /// The Hausdorff distance from the boundary of the hull to the boundary of the merged parts.
+    ///
+    /// The opposite direction isn’t measured since the boundaries shared by adjacent parts lie
+    /// inside of the merged hull. Returns `Real::MAX` if the hull can’t be turned into a
+    /// triangle mesh.
+    fn concavity(&self, sample_spacing: Real) -> Real {
+        let Ok(hull) = TriMesh::new(self.hull.0.clone(), self.hull.1.clone()) else {
+            return Real::MAX;
+        };
+        hausdorff_distance_trimesh_shape(
+            &Isometry::identity(),
+            &hull,
+            &Isometry::identity(),
+            &self.mesh,
+            sample_spacing,
+        )
+        .max
+    }
+}
+
+/// Greedily merges the pairs of parts with the smallest merged concavity.
+fn merge_parts(
+    parts: Vec<Part>,
+    params: &CoACDParameters,
+    max_concavity: Real,
+    sample_spacing: Real,
+) -> Vec<ConvexHull> {
+    let mut merged: Vec<Option<MergedPart>> = parts
+        .into_iter()
+        .map(|part| {
+            let aabb = part.mesh.local_aabb();
+            Some(MergedPart {
+                mesh: part.mesh,
+                hull: part.hull,
+                aabb,
+            })
+        })
+        .collect();
+    let mut num_parts = merged.len();
+    let max_parts = params.max_convex_hulls.max(1) as usize;
+    // The merged concavity of each pair of parts, computed lazily. Merged parts are given new
+    // ids so these values never need to be invalidated.
+    let mut costs: HashMap<(usize, usize), Option<Real>> = HashMap::default();
+
+    loop {
+        let must_merge = num_parts > max_parts;
+
+        if !params.merge && !must_merge {
+            break;
+        }
+
+        let alive: Vec<usize> = (0..merged.len()).filter(|i| merged[*i].is_some()).collect();
+        let mut best: Option<(Real, usize, usize)> = None;
+
+        for (k, i) in alive.iter().enumerate() {
+            for j in &alive[k + 1..] {
+                let (part1, part2) = (merged[*i].as_ref().unwrap(), merged[*j].as_ref().unwrap());
+
+                // Only parts close to each other can be merged without exceeding the concavity.
+                if !must_merge && !part1.aabb.loosened(max_concavity).intersects(&part2.aabb) {
+                    continue;
+                }
+
+                let cost = *costs.entry((*i, *j)).or_insert_with(|| {
+                    part1
+                        .merge(part2)
+                        .map(|merged| merged.concavity(sample_spacing))
+                });
+
+                if let Some(cost) = cost {
+                    if (must_merge || cost <= max_concavity)
+                        && best.is_none_or(|(best_cost, _, _)| cost < best_cost)
+                    {
+                        best = Some((cost, *i, *j));
+                    }
+                }
+            }
+        }
+
+        let Some((_, i, j)) = best else {
+            break;
+        };
+
+        let Some(new_part) = merged[i]
+            .as_ref()
+            .and_then(|part1| part1.merge(merged[j].as_ref()?))
+        else {
+            break;
+        };
+        merged[i] = None;
+        merged[j] = None;
+        merged.push(Some(new_part));
+        num_parts -= 1;
+    }
+
+    merged.into_iter().flatten().map(|part| part.hull).collect()
+}
@@ -0,0 +1,5 @@
+pub use self::coacd::decompose;
+pub use self::parameters::CoACDParameters;
+
+mod coacd;
+mod parameters;
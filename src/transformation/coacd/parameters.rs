@@ -0,0 +1,48 @@
+use crate::math::Real;
+
+/// Parameters controlling the exact convex decomposition computed by [`decompose`](super::decompose).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CoACDParameters {
+    /// Maximum concavity of each part, relative to the diagonal of the input mesh’s bounding box.
+    ///
+    /// The concavity of a part is the Hausdorff distance between its boundary and the boundary
+    /// of its convex hull.
+    ///
+    /// Default: 0.02.
+    /// Valid range `[0.0, 1.0]`.
+    pub concavity: Real,
+    /// The number of candidate cutting planes evaluated along each axis when splitting a part.
+    ///
+    /// The best candidate is then refined by evaluating as many planes around it.
+    ///
+    /// Default: 8
+    pub planes_per_axis: u32,
+    /// The maximum number of recursive cuts applied to each part of the input mesh.
+    ///
+    /// Default: 10
+    pub max_depth: u32,
+    /// Controls whether the convex hulls of adjacent parts are merged after the decomposition,
+    /// as long as the concavity of the merged hull doesn’t exceed [`Self::concavity`].
+    ///
+    /// Default: true
+    pub merge: bool,
+    /// Controls the max number of convex-hull generated by the convex decomposition.
+    ///
+    /// If the decomposition generates more parts, those with the smallest merged concavity are
+    /// merged, regardless of [`Self::concavity`].
+    ///
+    /// Default: 1024
+    pub max_convex_hulls: u32,
+}
+
+impl Default for CoACDParameters {
+    fn default() -> Self {
+        Self {
+            concavity: 0.02,
+            planes_per_axis: 8,
+            max_depth: 10,
+            merge: true,
+            max_convex_hulls: 1024,
+        }
+    }
+}
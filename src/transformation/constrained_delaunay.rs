@@ -0,0 +1,200 @@
+//! Constrained Delaunay triangulation of 2D polygons with holes.
+
+use crate::math::{Point, Real};
+use crate::shape::{TriMesh, TriMeshBuilderError};
+use crate::utils;
+use alloc::{vec, vec::Vec};
+use spade::{
+    AngleLimit, ConstrainedDelaunayTriangulation, RefinementParameters, Triangulation as _,
+};
+
+/// Parameters controlling the triangulation of polygons with [`constrained_delaunay_triangulation`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ConstrainedTriangulationParameters {
+    /// Whether the triangles outside of the polygons delimited by the constraint edges are
+    /// removed.
+    ///
+    /// A triangle is inside of the polygons if any path from it to the outside of the
+    /// triangulation crosses an odd number of constraint edges. This makes the loops nested
+    /// inside of a polygon its holes. If this is `false`, the whole convex hull of the vertices
+    /// is triangulated.
+    ///
+    /// Default: `true`
+    pub remove_outside: bool,
+    /// The minimum inner angle, in radians, of the triangles.
+    ///
+    /// If non-zero, additional vertices are inserted until this limit is reached, or until
+    /// `max_added_vertices` is exceeded. Limits above 34 degrees may prevent the refinement from
+    /// completing. Constraint edges may be split, but the shape of the polygons is preserved.
+    ///
+    /// Default: 0.0
+    pub min_angle: Real,
+    /// The maximum area of the triangles.
+    ///
+    /// If finite, additional vertices are inserted until all the triangles are smaller than this
+    /// area, or until `max_added_vertices` is exceeded.
+    ///
+    /// Default: `Real::MAX`
+    pub max_area: Real,
+    /// The maximum number of vertices inserted to satisfy `min_angle` and `max_area`.
+    ///
+    /// Default: `None` (ten times the number of vertices of the input)
+    pub max_added_vertices: Option<usize>,
+}
+
+impl Default for ConstrainedTriangulationParameters {
+    fn default() -> Self {
+        Self {
+            remove_outside: true,
+            min_angle: 0.0,
+            max_area: Real::MAX,
+            max_added_vertices: None,
+        }
+    }
+}
+
+/// Error returned by [`constrained_delaunay_triangulation`].
+#[derive(thiserror::Error, Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ConstrainedTriangulationError {
+    /// The vertex with the given index has invalid (NaN or infinite) coordinates.
+    #[error("the vertex {0} has invalid coordinates")]
+    InvalidVertex(u32),
+    /// The constraint edge with the given index refers to a vertex that doesn’t exist.
+    #[error("the constraint edge {0} refers to a vertex that doesn’t exist")]
+    InvalidEdge(u32),
+    /// See [`TriMeshBuilderError`]
+    #[error("TriMeshBuilderError: {0}")]
+    TriMeshBuilderError(TriMeshBuilderError),
+}
+
+impl From<TriMeshBuilderError> for ConstrainedTriangulationError {
+    fn from(value: TriMeshBuilderError) -> Self {
+        ConstrainedTriangulationError::TriMeshBuilderError(value)
+    }
+}
+
+/// Computes the constrained Delaunay triangulation of a set of vertices and edges.
+///
+/// The triangulation contains all the constraint `edges`, given as pairs of indices into
+/// `vertices`. Constraint edges can intersect each other, in which case they are split at their
+/// intersection. For example, the outline of a polygon with holes can be given as the vertices
+/// and indices of a [`Polyline`](crate::shape::Polyline) made of closed loops, the orientation of
+/// the loops being irrelevant.
+///
+/// The resulting mesh only contains the vertices used by its triangles, including the vertices
+/// inserted at the intersections of constraint edges and by the refinement, so its vertex
+/// indices don’t match the input indices. Its triangles are counterclockwise. It can be
+/// decomposed into convex polygons with
+/// [`Compound::decompose_trimesh`](crate::shape::Compound::decompose_trimesh).
+pub fn constrained_delaunay_triangulation(
+    vertices: &[Point<Real>],
+    edges: &[[u32; 2]],
+    params: &ConstrainedTriangulationParameters,
+) -> Result<TriMesh, ConstrainedTriangulationError> {
+    let mut cdt = ConstrainedDelaunayTriangulation::<spade::Point2<Real>>::new();
+    let mut handles = Vec::with_capacity(vertices.len());
+
+    for (i, pt) in vertices.iter().enumerate() {
+        let handle = cdt
+            .insert(utils::sanitize_spade_point(spade::Point2::new(pt.x, pt.y)))
+            .map_err(|_| ConstrainedTriangulationError::InvalidVertex(i as u32))?;
+        handles.push(handle);
+    }
+
+    for (i, [a, b]) in edges.iter().enumerate() {
+        let (Some(a), Some(b)) = (handles.get(*a as usize), handles.get(*b as usize)) else {
+            return Err(ConstrainedTriangulationError::InvalidEdge(i as u32));
+        };
+
+        let _ = cdt.add_constraint_and_split(*a, *b, |pt| pt);
+    }
+
+    if params.min_angle > 0.0 || params.max_area < Real::MAX {
+        #[allow(clippy::unnecessary_cast)]
+        let mut refinement = RefinementParameters::new()
+            .with_angle_limit(AngleLimit::from_rad(params.min_angle as f64))
+            .exclude_outer_faces(params.remove_outside);
+
+        if params.max_area < Real::MAX {
+            refinement = refinement.with_max_allowed_area(params.max_area);
+        }
+
+        if let Some(max_added_vertices) = params.max_added_vertices {
+            refinement = refinement.with_max_additional_vertices(max_added_vertices);
+        }
+
+        let _ = cdt.refine(refinement);
+    }
+
+    let inside = if params.remove_outside {
+        inner_faces(&cdt)
+    } else {
+        vec![true; cdt.num_all_faces()]
+    };
+
+    let mut points: Vec<_> = cdt
+        .vertices()
+        .map(|v| Point::new(v.position().x, v.position().y))
+        .collect();
+    let mut indices: Vec<_> = cdt
+        .inner_faces()
+        .filter(|face| inside[face.fix().index()])
+        .map(|face| face.vertices().map(|v| v.fix().index() as u32))
+        .collect();
+
+    utils::remove_unused_points(&mut points, &mut indices);
+    Ok(TriMesh::new(points, indices)?)
+}
+
+/// Flags the faces of the triangulation separated from its outside by an odd number of
+/// constraint edges.
+fn inner_faces(cdt: &ConstrainedDelaunayTriangulation<spade::Point2<Real>>) -> Vec<bool> {
+    let mut depths = vec![usize::MAX; cdt.num_all_faces()];
+    let mut current = Vec::new();
+    let mut next = Vec::new();
+    let mut depth = 0;
+
+    // Peel the triangulation layer by layer, each layer being bounded by constraint edges. The
+    // edges pushed to the stacks are oriented toward the face to visit.
+    for edge in cdt.convex_hull() {
+        if edge.is_constraint_edge() {
+            next.push(edge.rev());
+        } else {
+            current.push(edge.rev());
+        }
+    }
+
+    loop {
+        while let Some(edge) = current.pop() {
+            let Some(face) = edge.face().as_inner() else {
+                continue;
+            };
+
+            if depths[face.fix().index()] != usize::MAX {
+                continue;
+            }
+
+            depths[face.fix().index()] = depth;
+
+            for neighbor in [edge.next(), edge.prev()] {
+                if neighbor.is_constraint_edge() {
+                    next.push(neighbor.rev());
+                } else {
+                    current.push(neighbor.rev());
+                }
+            }
+        }
+
+        if next.is_empty() {
+            break;
+        }
+
+        core::mem::swap(&mut current, &mut next);
+        depth += 1;
+    }
+
+    depths
+        .into_iter()
+        .map(|depth| depth != usize::MAX && depth % 2 == 1)
+        .collect()
+}
@@ -26,14 +26,12 @@ pub fn try_convex_hull(
     let _ = normalize(&mut normalized_points[..]);
 
     let mut undecidable_points = Vec::new();
-    let mut silhouette_loop_facets_and_idx = Vec::new();
-    let mut removed_facets = Vec::new();
 
     let mut triangles;
 
     match super::try_get_initial_mesh(points, &mut normalized_points[..], &mut undecidable_points)?
     {
-        InitialMesh::Facets(facets) => {
+        InitialMesh::Facets { facets, .. } => {
             triangles = facets;
         }
         InitialMesh::ResultMesh(vertices, indices) => {
@@ -41,7 +39,47 @@ pub fn try_convex_hull(
         }
     }
 
-    let mut i = 0;
+    update_facets(
+        0,
+        &normalized_points,
+        &mut triangles,
+        &mut undecidable_points,
+        &mut Vec::new(),
+    )?;
+
+    let mut idx = Vec::new();
+
+    for facet in triangles.iter() {
+        if facet.valid {
+            idx.push([
+                facet.pts[0] as u32,
+                facet.pts[1] as u32,
+                facet.pts[2] as u32,
+            ]);
+        }
+    }
+
+    let mut points = points.to_vec();
+    utils::remove_unused_points(&mut points, &mut idx[..]);
+    // super::check_convex_hull(&points, &idx);
+
+    Ok((points, idx))
+}
+
+/// Processes the facets with index `first` or greater until none of the valid facets can be seen
+/// by any point.
+///
+/// The facets removed from the hull are appended to `all_removed_facets`.
+pub(super) fn update_facets(
+    first: usize,
+    points: &[Point3<Real>],
+    triangles: &mut Vec<TriangleFacet>,
+    undecidable: &mut Vec<usize>,
+    all_removed_facets: &mut Vec<usize>,
+) -> Result<(), ConvexHullError> {
+    let mut silhouette_loop_facets_and_idx = Vec::new();
+    let mut removed_facets = Vec::new();
+    let mut i = first;
     while i != triangles.len() {
         silhouette_loop_facets_and_idx.clear();
 
@@ -53,7 +91,7 @@ pub fn try_convex_hull(
         // TODO: use triangles[i].furthest_point instead.
         let pt_id = indexed_support_point_id(
             &triangles[i].normal,
-            &normalized_points[..],
+            points,
             triangles[i].visible_points[..].iter().copied(),
         );
 
@@ -70,7 +108,7 @@ pub fn try_convex_hull(
                     triangles[i].indirect_adj_id[j],
                     point,
                     &mut silhouette_loop_facets_and_idx,
-                    &normalized_points[..],
+                    points,
                     &mut removed_facets,
                     &mut triangles[..],
                 );
@@ -80,7 +118,7 @@ pub fn try_convex_hull(
             // 1. Contain self-intersections (i.e. a single vertex is used by more than two edges).
             // 2. Contain multiple disjoint (but nested) loops.
             fix_silhouette_topology(
-                &normalized_points,
+                points,
                 &mut silhouette_loop_facets_and_idx,
                 &mut removed_facets,
                 &mut triangles[..],
@@ -113,17 +151,19 @@ pub fn try_convex_hull(
 
                 // TODO: this is very harsh.
                 triangles[i].valid = true;
+                all_removed_facets.extend_from_slice(&removed_facets);
                 break;
             }
 
             attach_and_push_facets(
                 &silhouette_loop_facets_and_idx[..],
                 point,
-                &normalized_points[..],
-                &mut triangles,
+                points,
+                triangles,
                 &removed_facets[..],
-                &mut undecidable_points,
+                undecidable,
             );
+            all_removed_facets.extend_from_slice(&removed_facets);
 
             // println!("Verifying facets at iteration: {}, k: {}", i, k);
             // for i in 0..triangles.len() {
@@ -136,23 +176,7 @@ pub fn try_convex_hull(
         i += 1;
     }
 
-    let mut idx = Vec::new();
-
-    for facet in triangles.iter() {
-        if facet.valid {
-            idx.push([
-                facet.pts[0] as u32,
-                facet.pts[1] as u32,
-                facet.pts[2] as u32,
-            ]);
-        }
-    }
-
-    let mut points = points.to_vec();
-    utils::remove_unused_points(&mut points, &mut idx[..]);
-    // super::check_convex_hull(&points, &idx);
-
-    Ok((points, idx))
+    Ok(())
 }
 
 fn compute_silhouette(
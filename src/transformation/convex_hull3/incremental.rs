@@ -0,0 +1,253 @@
+use super::convex_hull::update_facets;
+use super::{try_get_initial_mesh, ConvexHullError, InitialMesh, TriangleFacet};
+use crate::math::Real;
+use crate::shape::ConvexPolyhedron;
+use crate::transformation::convex_hull_utils::normalize;
+use crate::utils;
+use alloc::{vec, vec::Vec};
+use na::Point3;
+
+/// The facets created and destroyed by an insertion into an [`IncrementalConvexHull`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ConvexHullUpdate {
+    /// The identifiers of the facets added to the hull.
+    pub created: Vec<u32>,
+    /// The identifiers of the facets removed from the hull.
+    ///
+    /// Facets created and destroyed by the same insertion are not reported.
+    pub destroyed: Vec<u32>,
+}
+
+impl ConvexHullUpdate {
+    /// Returns `true` if the insertion didn’t modify the hull.
+    pub fn is_empty(&self) -> bool {
+        self.created.is_empty() && self.destroyed.is_empty()
+    }
+}
+
+/// A 3D convex hull that can be updated as points are added to it.
+///
+/// Each point is identified by its insertion order, and each triangular facet of the hull by an
+/// identifier that remains valid until the facet is reported as destroyed by an insertion.
+/// Identifiers of destroyed facets are never reused.
+///
+/// As long as the inserted points are coplanar, collinear, or identical, the hull is degenerate:
+/// it has no facets, but [`Self::vertices_and_indices`] still returns the same flat mesh
+/// as [`convex_hull`](crate::transformation::convex_hull). The facets are created once the first
+/// point making the hull non-degenerate is inserted.
+///
+/// The points are normalized with a scaling computed when the hull stops being degenerate, so
+/// the tolerances used to decide whether a point lies outside of the hull are relative to the
+/// size of the hull at that time.
+#[derive(Clone, Debug, Default)]
+pub struct IncrementalConvexHull {
+    points: Vec<Point3<Real>>,
+    normalized_points: Vec<Point3<Real>>,
+    // The center and scale of the normalization, set once the hull has facets.
+    normalization: Option<(Point3<Real>, Real)>,
+    facets: Vec<TriangleFacet>,
+    undecidable: Vec<usize>,
+    degenerate_hull: (Vec<Point3<Real>>, Vec<[u32; 3]>),
+}
+
+impl IncrementalConvexHull {
+    /// Creates an empty convex hull.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates the convex hull of a set of points.
+    pub fn from_points(points: &[Point3<Real>]) -> Result<Self, ConvexHullError> {
+        let mut result = Self::new();
+        let _ = result.add_points(points)?;
+        Ok(result)
+    }
+
+    /// All the points inserted so far, including the ones inside of the hull.
+    pub fn points(&self) -> &[Point3<Real>] {
+        &self.points
+    }
+
+    /// Returns `true` if the hull doesn’t have any volume yet.
+    pub fn is_degenerate(&self) -> bool {
+        self.normalization.is_none()
+    }
+
+    /// The indices of the points forming the facet with the given identifier, if it is part
+    /// of the hull.
+    ///
+    /// The vertices of the facet are in counterclockwise order when seen from outside of the
+    /// hull.
+    pub fn facet(&self, id: u32) -> Option<[u32; 3]> {
+        self.facets
+            .get(id as usize)
+            .filter(|facet| facet.valid)
+            .map(|facet| facet.pts.map(|i| i as u32))
+    }
+
+    /// The identifiers and point indices of all the facets of the hull.
+    pub fn facets(&self) -> impl Iterator<Item = (u32, [u32; 3])> + '_ {
+        self.facets
+            .iter()
+            .enumerate()
+            .filter(|(_, facet)| facet.valid)
+            .map(|(id, facet)| (id as u32, facet.pts.map(|i| i as u32)))
+    }
+
+    /// The vertices and triangles of the hull.
+    ///
+    /// Only the points on the hull are returned, so the indices of this mesh don’t match the
+    /// insertion order of the points.
+    pub fn vertices_and_indices(&self) -> (Vec<Point3<Real>>, Vec<[u32; 3]>) {
+        if self.is_degenerate() {
+            return self.degenerate_hull.clone();
+        }
+
+        let mut vertices = self.points.clone();
+        let mut indices: Vec<_> = self.facets().map(|(_, idx)| idx).collect();
+        utils::remove_unused_points(&mut vertices, &mut indices);
+        (vertices, indices)
+    }
+
+    /// The hull as a convex polyhedron.
+    ///
+    /// Returns `None` if the hull is degenerate.
+    pub fn to_convex_polyhedron(&self) -> Option<ConvexPolyhedron> {
+        if self.is_degenerate() {
+            return None;
+        }
+
+        let (vertices, indices) = self.vertices_and_indices();
+        ConvexPolyhedron::from_convex_mesh(vertices, &indices)
+    }
+
+    /// Adds a point to the hull.
+    ///
+    /// Returns the facets created and destroyed by this insertion, which are both empty if the
+    /// point lies inside of the hull. If an error is returned, the hull may be left in an
+    /// inconsistent state.
+    pub fn add_point(&mut self, point: Point3<Real>) -> Result<ConvexHullUpdate, ConvexHullError> {
+        self.add_points(&[point])
+    }
+
+    /// Adds a set of points to the hull.
+    ///
+    /// This is faster than adding the points one by one since the facets that would only exist
+    /// between two of these insertions are never created. Returns the facets created and
+    /// destroyed by this insertion. If an error is returned, the hull may be left in an
+    /// inconsistent state.
+    pub fn add_points(
+        &mut self,
+        points: &[Point3<Real>],
+    ) -> Result<ConvexHullUpdate, ConvexHullError> {
+        let first_new_point = self.points.len();
+        self.points.extend_from_slice(points);
+
+        let Some((center, scale)) = self.normalization else {
+            return self.try_initialize();
+        };
+
+        self.normalized_points
+            .extend(points.iter().map(|pt| Point3::from((pt - center) / scale)));
+
+        // Assign each new point to the facet it is the furthest from.
+        let num_facets = self.facets.len();
+        let mut first_facet = usize::MAX;
+
+        for point in first_new_point..self.points.len() {
+            let mut furthest = usize::MAX;
+            let mut furthest_dist = 0.0;
+
+            for (i, facet) in self.facets.iter().enumerate() {
+                if facet.valid && facet.can_see_point(point, &self.normalized_points) {
+                    let distance = facet.distance_to_point(point, &self.normalized_points);
+
+                    if distance > furthest_dist {
+                        furthest = i;
+                        furthest_dist = distance;
+                    }
+                }
+            }
+
+            // If none of the facets can be seen from the point, it is inside of the hull.
+            if furthest != usize::MAX {
+                self.facets[furthest].add_visible_point(point, &self.normalized_points);
+                first_facet = first_facet.min(furthest);
+            }
+        }
+
+        if first_facet == usize::MAX {
+            return Ok(ConvexHullUpdate::default());
+        }
+
+        let mut removed_facets = Vec::new();
+        update_facets(
+            first_facet,
+            &self.normalized_points,
+            &mut self.facets,
+            &mut self.undecidable,
+            &mut removed_facets,
+        )?;
+
+        Ok(ConvexHullUpdate {
+            created: (num_facets..self.facets.len())
+                .filter(|i| self.facets[*i].valid)
+                .map(|i| i as u32)
+                .collect(),
+            destroyed: removed_facets
+                .into_iter()
+                .filter(|i| *i < num_facets && !self.facets[*i].valid)
+                .map(|i| i as u32)
+                .collect(),
+        })
+    }
+
+    /// Attempts to build the initial facets of the hull from all the points inserted so far.
+    fn try_initialize(&mut self) -> Result<ConvexHullUpdate, ConvexHullError> {
+        if self.points.is_empty() {
+            return Ok(ConvexHullUpdate::default());
+        }
+
+        let mut normalized_points = self.points.clone();
+        let (aabb_center, diag) = normalize(&mut normalized_points);
+
+        if diag == 0.0 {
+            // All the points are identical.
+            self.degenerate_hull = (vec![self.points[0]], vec![[0; 3]; 2]);
+            return Ok(ConvexHullUpdate::default());
+        }
+
+        let mut undecidable = Vec::new();
+
+        match try_get_initial_mesh(&self.points, &mut normalized_points, &mut undecidable)? {
+            InitialMesh::ResultMesh(vertices, indices) => {
+                self.degenerate_hull = (vertices, indices);
+                Ok(ConvexHullUpdate::default())
+            }
+            InitialMesh::Facets {
+                mut facets,
+                center,
+                scale,
+            } => {
+                update_facets(
+                    0,
+                    &normalized_points,
+                    &mut facets,
+                    &mut undecidable,
+                    &mut Vec::new(),
+                )?;
+
+                self.normalization = Some((aabb_center + center.coords * diag, diag * scale));
+                self.normalized_points = normalized_points;
+                self.facets = facets;
+                self.undecidable = undecidable;
+                self.degenerate_hull = Default::default();
+
+                Ok(ConvexHullUpdate {
+                    created: self.facets().map(|(id, _)| id).collect(),
+                    destroyed: Vec::new(),
+                })
+            }
+        }
+    }
+}
@@ -10,7 +10,13 @@ use na::{Point2, Point3, Vector3};
 
 #[derive(Debug)]
 pub enum InitialMesh {
-    Facets(Vec<TriangleFacet>),
+    /// The initial facets, and the translation and scaling applied to the normalized points
+    /// (the points were replaced by `(point - center) / scale`).
+    Facets {
+        facets: Vec<TriangleFacet>,
+        center: Point3<Real>,
+        scale: Real,
+    },
     ResultMesh(Vec<Point3<Real>>, Vec<[u32; 3]>),
 }
 
@@ -142,9 +148,10 @@ pub fn try_get_initial_mesh(
             // The hull is a polyhedron.
             // Find a initial triangle lying on the principal halfspace…
             let center = utils::center(normalized_points);
+            let scale = eigval.amax();
 
             for point in normalized_points.iter_mut() {
-                *point = Point3::from((*point - center) / eigval.amax());
+                *point = Point3::from((*point - center) / scale);
             }
 
             let p1 = support_point_id(&eigpairs[0].0, normalized_points)
@@ -214,7 +221,11 @@ pub fn try_get_initial_mesh(
                 super::check_facet_links(0, &facets[..]);
                 super::check_facet_links(1, &facets[..]);
 
-                Ok(InitialMesh::Facets(facets))
+                Ok(InitialMesh::Facets {
+                    facets,
+                    center,
+                    scale,
+                })
             }
         }
         _ => Err(ConvexHullError::Unreachable),
@@ -4,7 +4,7 @@ use alloc::vec::Vec;
 use na::{Point3, Vector3};
 use num::Bounded;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct TriangleFacet {
     pub valid: bool,
     pub affinely_dependent: bool,
@@ -0,0 +1,364 @@
+//! Simplification of convex hulls to a maximum number of vertices and faces.
+
+use crate::bounding_volume;
+#[cfg(feature = "dim3")]
+use crate::math::Isometry;
+use crate::math::{Point, Real, Vector, DIM};
+#[cfg(feature = "dim2")]
+use crate::shape::ConvexPolygon;
+#[cfg(feature = "dim3")]
+use crate::shape::{ConvexPolyhedron, HalfSpace, Triangle};
+#[cfg(feature = "dim2")]
+use crate::utils;
+use alloc::vec::Vec;
+use na::{SMatrix, Unit};
+
+/// The maximum number of candidate refinements tried at each step of the simplification.
+///
+/// The candidates are tried by decreasing distance to the simplified hull. Bounding their number
+/// keeps the simplification of dense hulls tractable, since each try rebuilds a hull.
+const MAX_CANDIDATES: usize = 8;
+
+#[cfg(feature = "dim2")]
+type ConvexShape = ConvexPolygon;
+#[cfg(feature = "dim3")]
+type ConvexShape = ConvexPolyhedron;
+
+/// Constrains the position of a simplified convex hull relative to the original one.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ConvexHullSimplificationBound {
+    /// The simplified hull contains the original hull.
+    ///
+    /// It is the intersection of a simplex, whose faces are pushed outward until it contains the
+    /// original hull, with some of the half-spaces bounding the original hull. These half-spaces
+    /// are selected greedily to cut off the parts furthest away from the original hull first.
+    #[default]
+    Outside,
+    /// The simplified hull is contained in the original hull.
+    ///
+    /// It is the convex hull of some of the vertices of the original hull. These vertices are
+    /// selected greedily to add the parts of the original hull furthest away from the simplified
+    /// hull first.
+    Inside,
+}
+
+/// Parameters controlling the simplification of a convex hull with [`simplify_convex_hull`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ConvexHullSimplificationParameters {
+    /// The maximum number of vertices of the simplified hull.
+    ///
+    /// Default: 64
+    pub max_vertices: u32,
+    /// The maximum number of faces of the simplified hull.
+    ///
+    /// In 3D, coplanar triangles are counted as a single face. In 2D, the number of faces (edges)
+    /// of a polygon is equal to its number of vertices.
+    ///
+    /// Default: `u32::MAX`
+    pub max_faces: u32,
+    /// Whether the simplified hull should contain the original hull, or be contained by it.
+    ///
+    /// Default: `ConvexHullSimplificationBound::Outside`
+    pub bound: ConvexHullSimplificationBound,
+}
+
+impl Default for ConvexHullSimplificationParameters {
+    fn default() -> Self {
+        Self {
+            max_vertices: 64,
+            max_faces: u32::MAX,
+            bound: ConvexHullSimplificationBound::Outside,
+        }
+    }
+}
+
+/// Computes the convex hull of a set of points, simplified to a maximum number of vertices and
+/// faces.
+///
+/// The simplification stops as soon as the simplified hull matches the original one, or when
+/// refining it further would exceed the budget given by `params`. If the convex hull of `points`
+/// already fits in the budget, it is returned unchanged.
+///
+/// Returns `None` if the convex hull of `points` is degenerate, or if the budget is too small
+/// for a simplex (less than `DIM + 1` vertices or faces).
+#[cfg(feature = "dim2")]
+pub fn simplify_convex_hull(
+    points: &[Point<Real>],
+    params: &ConvexHullSimplificationParameters,
+) -> Option<ConvexPolygon> {
+    simplify(points, params)
+}
+
+/// Computes the convex hull of a set of points, simplified to a maximum number of vertices and
+/// faces.
+///
+/// The simplification stops as soon as the simplified hull matches the original one, or when
+/// refining it further would exceed the budget given by `params`. If the convex hull of `points`
+/// already fits in the budget, it is returned unchanged.
+///
+/// Returns `None` if the convex hull of `points` is degenerate, or if the budget is too small
+/// for a simplex (less than `DIM + 1` vertices or faces).
+#[cfg(feature = "dim3")]
+pub fn simplify_convex_hull(
+    points: &[Point<Real>],
+    params: &ConvexHullSimplificationParameters,
+) -> Option<ConvexPolyhedron> {
+    simplify(points, params)
+}
+
+fn simplify(
+    points: &[Point<Real>],
+    params: &ConvexHullSimplificationParameters,
+) -> Option<ConvexShape> {
+    let original = ConvexShape::from_convex_hull(points)?;
+
+    if fits_in_budget(&original, params) {
+        return Some(original);
+    }
+
+    let vertices = original.points();
+    let aabb = bounding_volume::details::local_point_cloud_aabb_ref(vertices);
+    let eps = aabb.extents().norm() * 1.0e-5;
+    let simplex = simplex_vertices(vertices, eps)?;
+
+    let mut result = match params.bound {
+        ConvexHullSimplificationBound::Outside => pushed_simplex(vertices, &simplex)?,
+        ConvexHullSimplificationBound::Inside => {
+            ConvexShape::from_convex_hull(&simplex.map(|i| vertices[i]))?
+        }
+    };
+
+    if !fits_in_budget(&result, params) {
+        return None;
+    }
+
+    match params.bound {
+        ConvexHullSimplificationBound::Outside => {
+            let planes = hull_planes(vertices);
+
+            loop {
+                // The distance from each plane of the original hull to the furthest vertex of
+                // the simplified hull beyond it.
+                let mut candidates: Vec<_> = planes
+                    .iter()
+                    .map(|(normal, bias)| {
+                        result
+                            .points()
+                            .iter()
+                            .map(|pt| normal.dot(&pt.coords) - bias)
+                            .fold(-Real::MAX, Real::max)
+                    })
+                    .enumerate()
+                    .filter(|(_, dist)| *dist > eps)
+                    .collect();
+                candidates.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+                let next = candidates.iter().take(MAX_CANDIDATES).find_map(|(i, _)| {
+                    clip(&result, &planes[*i]).filter(|clipped| fits_in_budget(clipped, params))
+                });
+
+                match next {
+                    Some(clipped) => result = clipped,
+                    None => break,
+                }
+            }
+        }
+        ConvexHullSimplificationBound::Inside => {
+            let mut selected: Vec<_> = simplex.iter().map(|i| vertices[*i]).collect();
+
+            // Adding a point always adds a vertex to the hull.
+            while result.points().len() < params.max_vertices as usize {
+                // The distance from each vertex of the original hull to the simplified hull.
+                let planes = hull_planes(&selected);
+                let mut candidates: Vec<_> = vertices
+                    .iter()
+                    .map(|pt| {
+                        planes
+                            .iter()
+                            .map(|(normal, bias)| normal.dot(&pt.coords) - bias)
+                            .fold(-Real::MAX, Real::max)
+                    })
+                    .enumerate()
+                    .filter(|(_, dist)| *dist > eps)
+                    .collect();
+                candidates.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+                let next = candidates.iter().take(MAX_CANDIDATES).find_map(|(i, _)| {
+                    selected.push(vertices[*i]);
+                    let hull = ConvexShape::from_convex_hull(&selected);
+                    let _ = selected.pop();
+                    hull.filter(|hull| fits_in_budget(hull, params))
+                        .map(|hull| (hull, *i))
+                });
+
+                match next {
+                    Some((hull, i)) => {
+                        selected.push(vertices[i]);
+                        result = hull;
+                    }
+                    None => break,
+                }
+            }
+        }
+    }
+
+    Some(result)
+}
+
+#[cfg(feature = "dim2")]
+fn fits_in_budget(polygon: &ConvexPolygon, params: &ConvexHullSimplificationParameters) -> bool {
+    let num_vertices = polygon.points().len();
+    num_vertices <= params.max_vertices as usize && num_vertices <= params.max_faces as usize
+}
+
+#[cfg(feature = "dim3")]
+fn fits_in_budget(poly: &ConvexPolyhedron, params: &ConvexHullSimplificationParameters) -> bool {
+    poly.points().len() <= params.max_vertices as usize
+        && poly.faces().len() <= params.max_faces as usize
+}
+
+/// The outward normal and offset `n.dot(p) = bias` of the plane of each face of the convex hull
+/// of the given points.
+///
+/// The planes are computed from the exact convex hull, without merging nearly collinear edges
+/// like `ConvexPolygon` does.
+#[cfg(feature = "dim2")]
+fn hull_planes(points: &[Point<Real>]) -> Vec<(Unit<Vector<Real>>, Real)> {
+    let hull = crate::transformation::convex_hull(points);
+    (0..hull.len())
+        .filter_map(|i| {
+            let a = &hull[i];
+            let normal = utils::ccw_face_normal([a, &hull[(i + 1) % hull.len()]])?;
+            Some((normal, normal.dot(&a.coords)))
+        })
+        .collect()
+}
+
+/// The outward normal and offset `n.dot(p) = bias` of the plane of each face of the convex hull
+/// of the given points.
+///
+/// The planes are computed from the exact convex hull, without merging nearly coplanar faces
+/// like `ConvexPolyhedron` does.
+#[cfg(feature = "dim3")]
+fn hull_planes(points: &[Point<Real>]) -> Vec<(Unit<Vector<Real>>, Real)> {
+    let (vertices, indices) = crate::transformation::try_convex_hull(points).unwrap_or_default();
+    indices
+        .iter()
+        .filter_map(|idx| {
+            let [a, b, c] = idx.map(|i| vertices[i as usize]);
+            let normal = Triangle::new(a, b, c).normal()?;
+            Some((normal, normal.dot(&a.coords)))
+        })
+        .collect()
+}
+
+/// The part of the polygon located behind the given plane.
+#[cfg(feature = "dim2")]
+fn clip(
+    polygon: &ConvexPolygon,
+    (normal, bias): &(Unit<Vector<Real>>, Real),
+) -> Option<ConvexPolygon> {
+    let pts = polygon.points();
+    let mut clipped = Vec::with_capacity(pts.len() + 1);
+
+    for (i, a) in pts.iter().enumerate() {
+        let b = pts[(i + 1) % pts.len()];
+        let da = normal.dot(&a.coords) - bias;
+        let db = normal.dot(&b.coords) - bias;
+
+        if da <= 0.0 {
+            clipped.push(*a);
+        }
+
+        if (da < 0.0 && db > 0.0) || (da > 0.0 && db < 0.0) {
+            clipped.push(a + (b - a) * (da / (da - db)));
+        }
+    }
+
+    ConvexPolygon::from_convex_polyline(clipped)
+}
+
+/// The part of the polyhedron located behind the given plane.
+#[cfg(feature = "dim3")]
+fn clip(
+    poly: &ConvexPolyhedron,
+    (normal, bias): &(Unit<Vector<Real>>, Real),
+) -> Option<ConvexPolyhedron> {
+    let pos = Isometry::new(normal.into_inner() * *bias, na::zero());
+    crate::transformation::convex_polyhedron_halfspace_intersection(
+        &pos,
+        poly,
+        &HalfSpace::new(*normal),
+    )
+    .ok()
+    .flatten()
+}
+
+/// Selects `DIM + 1` vertices forming a large simplex.
+///
+/// Returns `None` if the points are (almost) affinely dependent.
+fn simplex_vertices(points: &[Point<Real>], eps: Real) -> Option<[usize; DIM + 1]> {
+    let furthest = |dist: &dyn Fn(&Point<Real>) -> Real| {
+        points
+            .iter()
+            .enumerate()
+            .map(|(i, pt)| (i, dist(pt)))
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .filter(|(_, dist)| *dist > eps)
+            .map(|(i, _)| i)
+    };
+
+    let aabb = bounding_volume::details::local_point_cloud_aabb_ref(points);
+    let axis = Vector::ith_axis(aabb.extents().imax());
+    let p0 = (0..points.len()).min_by(|a, b| {
+        let da = points[*a].coords.dot(&axis);
+        let db = points[*b].coords.dot(&axis);
+        da.total_cmp(&db)
+    })?;
+    let p1 = furthest(&|pt| na::distance(pt, &points[p0]))?;
+    let dir0 = (points[p1] - points[p0]).normalize();
+    let p2 = furthest(&|pt| {
+        let dpt = pt - points[p0];
+        (dpt - dir0 * dpt.dot(&dir0)).norm()
+    })?;
+
+    #[cfg(feature = "dim2")]
+    let simplex = [p0, p1, p2];
+    #[cfg(feature = "dim3")]
+    let simplex = {
+        let normal = (points[p1] - points[p0])
+            .cross(&(points[p2] - points[p0]))
+            .normalize();
+        let p3 = furthest(&|pt| normal.dot(&(pt - points[p0])).abs())?;
+        [p0, p1, p2, p3]
+    };
+
+    Some(simplex)
+}
+
+/// The simplex with faces parallel to the faces of the given simplex, and pushed outward until
+/// it contains all the `points`.
+fn pushed_simplex(points: &[Point<Real>], simplex: &[usize; DIM + 1]) -> Option<ConvexShape> {
+    let planes: Vec<_> = hull_planes(&simplex.map(|i| points[i]))
+        .into_iter()
+        .map(|(normal, _)| {
+            let bias = points
+                .iter()
+                .map(|pt| normal.dot(&pt.coords))
+                .fold(-Real::MAX, Real::max);
+            (normal, bias)
+        })
+        .collect();
+
+    // Each vertex of the pushed simplex is at the intersection of all its planes but one.
+    let mut vertices = Vec::with_capacity(DIM + 1);
+
+    for excluded in 0..planes.len() {
+        let others: Vec<_> = (0..planes.len()).filter(|i| *i != excluded).collect();
+        let mat = SMatrix::<Real, DIM, DIM>::from_fn(|r, c| planes[others[r]].0[c]);
+        let rhs = Vector::from_fn(|r, _| planes[others[r]].1);
+        vertices.push(Point::from(mat.try_inverse()? * rhs));
+    }
+
+    ConvexShape::from_convex_hull(&vertices)
+}
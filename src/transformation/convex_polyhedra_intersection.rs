@@ -0,0 +1,230 @@
+use crate::bounding_volume::Aabb;
+use crate::math::{Isometry, Point, Real, Vector};
+use crate::shape::{ConvexPolyhedron, Cuboid, HalfSpace};
+use crate::transformation::ConvexHullError;
+use alloc::vec::Vec;
+use na::{ComplexField, Unit};
+
+/// A convex polyhedron described by its vertices, edges, and the planes supporting its faces.
+struct ClippedPolyhedron {
+    points: Vec<Point<Real>>,
+    edges: Vec<[u32; 2]>,
+    /// The outward normal and offset `n.dot(p) = bias` of the plane of each face.
+    planes: Vec<(Unit<Vector<Real>>, Real)>,
+}
+
+impl ClippedPolyhedron {
+    fn from_convex_polyhedron(poly: &ConvexPolyhedron) -> Self {
+        let points = poly.points().to_vec();
+        let edges = poly
+            .edges()
+            .iter()
+            .map(|e| [e.vertices.x, e.vertices.y])
+            .collect();
+        let planes = poly
+            .faces()
+            .iter()
+            .map(|face| {
+                let vid = poly.vertices_adj_to_face()[face.first_vertex_or_edge as usize];
+                let bias = face.normal.dot(&points[vid as usize].coords);
+                (face.normal, bias)
+            })
+            .collect();
+
+        Self {
+            points,
+            edges,
+            planes,
+        }
+    }
+
+    fn from_cuboid(cuboid: &Cuboid) -> Self {
+        let he = cuboid.half_extents;
+        let points = (0..8)
+            .map(|i| {
+                Point::new(
+                    if i & 1 == 0 { -he.x } else { he.x },
+                    if i & 2 == 0 { -he.y } else { he.y },
+                    if i & 4 == 0 { -he.z } else { he.z },
+                )
+            })
+            .collect();
+        let edges = (0..8u32)
+            .flat_map(|i| {
+                [1, 2, 4]
+                    .into_iter()
+                    .filter(move |bit| i & bit == 0)
+                    .map(move |bit| [i, i | bit])
+            })
+            .collect();
+        let planes = (0..3)
+            .flat_map(|i| {
+                let axis = Vector::ith_axis(i);
+                [(axis, he[i]), (-axis, he[i])]
+            })
+            .collect();
+
+        Self {
+            points,
+            edges,
+            planes,
+        }
+    }
+
+    fn transform_by(&mut self, pos: &Isometry<Real>) {
+        for pt in &mut self.points {
+            *pt = pos * *pt;
+        }
+
+        for (normal, bias) in &mut self.planes {
+            *normal = pos * *normal;
+            *bias += normal.dot(&pos.translation.vector);
+        }
+    }
+
+    fn contains_point(&self, pt: &Point<Real>, eps: Real) -> bool {
+        self.planes
+            .iter()
+            .all(|(normal, bias)| normal.dot(&pt.coords) <= *bias + eps)
+    }
+
+    /// Pushes to `out` the points of the boundary of `self` located inside of `other`.
+    ///
+    /// Those are the vertices of `self` inside of `other`, and the intersections of the edges of
+    /// `self` with the faces of `other`.
+    fn clip_against(&self, other: &Self, eps: Real, out: &mut Vec<Point<Real>>) {
+        out.extend(
+            self.points
+                .iter()
+                .filter(|pt| other.contains_point(pt, eps)),
+        );
+
+        for [a, b] in &self.edges {
+            let a = self.points[*a as usize];
+            let b = self.points[*b as usize];
+
+            for (normal, bias) in &other.planes {
+                let da = normal.dot(&a.coords) - bias;
+                let db = normal.dot(&b.coords) - bias;
+
+                if (da < 0.0) != (db < 0.0) {
+                    let pt = a + (b - a) * (da / (da - db));
+
+                    if other.contains_point(&pt, eps) {
+                        out.push(pt);
+                    }
+                }
+            }
+        }
+    }
+
+    fn intersection(&self, other: &Self) -> Result<Option<ConvexPolyhedron>, ConvexHullError> {
+        let eps = ComplexField::sqrt(crate::math::DEFAULT_EPSILON);
+        let mut points = Vec::new();
+        self.clip_against(other, eps, &mut points);
+        other.clip_against(self, eps, &mut points);
+        convex_polyhedron_from_points(&points)
+    }
+}
+
+/// Computes the convex hull of `points` as a convex polyhedron.
+///
+/// Returns `Ok(None)` if the points are too few, or too degenerate, to enclose any volume.
+pub(crate) fn convex_polyhedron_from_points(
+    points: &[Point<Real>],
+) -> Result<Option<ConvexPolyhedron>, ConvexHullError> {
+    if points
+        .iter()
+        .any(|pt| !pt.coords.iter().all(|x| x.is_finite()))
+    {
+        return Err(ConvexHullError::MissingSupportPoint);
+    }
+
+    if points.len() < 4 {
+        return Ok(None);
+    }
+
+    match crate::transformation::try_convex_hull(points) {
+        Ok((vertices, indices)) => ConvexPolyhedron::from_convex_mesh(vertices, &indices)
+            .map(Some)
+            .ok_or(ConvexHullError::InternalError(
+                "the convex hull isn’t a valid convex polyhedron",
+            )),
+        Err(ConvexHullError::IncompleteInput | ConvexHullError::MissingSupportPoint) => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+/// Computes the intersection of two convex polyhedra.
+///
+/// The resulting polyhedron is expressed in the local-space of `poly1`, `pos12` being the position
+/// of `poly2` relative to `poly1`. Returns `Ok(None)` if the polyhedra don’t intersect. If they are
+/// only touching, the result may be flat. Returns an error if the convex hull of the intersection
+/// couldn’t be computed.
+///
+/// Use [`MassProperties::from_convex_polyhedron`](crate::mass_properties::MassProperties::from_convex_polyhedron)
+/// on the result to compute the volume and centroid of the overlap.
+pub fn convex_polyhedra_intersection(
+    pos12: &Isometry<Real>,
+    poly1: &ConvexPolyhedron,
+    poly2: &ConvexPolyhedron,
+) -> Result<Option<ConvexPolyhedron>, ConvexHullError> {
+    let clipped1 = ClippedPolyhedron::from_convex_polyhedron(poly1);
+    let mut clipped2 = ClippedPolyhedron::from_convex_polyhedron(poly2);
+    clipped2.transform_by(pos12);
+    clipped1.intersection(&clipped2)
+}
+
+/// Computes the intersection of two cuboids.
+///
+/// This is the same as [`convex_polyhedra_intersection`], but doesn’t require the cuboids to be
+/// converted to convex polyhedra first. If the cuboids have the same orientation, the
+/// intersection is computed directly from their overlapping AABBs.
+pub fn cuboids_intersection(
+    pos12: &Isometry<Real>,
+    cuboid1: &Cuboid,
+    cuboid2: &Cuboid,
+) -> Result<Option<ConvexPolyhedron>, ConvexHullError> {
+    if pos12.rotation.angle() <= crate::math::DEFAULT_EPSILON {
+        let aabb1 = Aabb::from_half_extents(Point::origin(), cuboid1.half_extents);
+        let aabb2 = Aabb::from_half_extents(pos12.translation.vector.into(), cuboid2.half_extents);
+        let Some(aabb) = aabb1.intersection(&aabb2) else {
+            return Ok(None);
+        };
+        let (vertices, indices) = aabb.to_trimesh();
+        return Ok(ConvexPolyhedron::from_convex_mesh(vertices, &indices));
+    }
+
+    let clipped1 = ClippedPolyhedron::from_cuboid(cuboid1);
+    let mut clipped2 = ClippedPolyhedron::from_cuboid(cuboid2);
+    clipped2.transform_by(pos12);
+    clipped1.intersection(&clipped2)
+}
+
+/// Computes the part of a convex polyhedron located inside of a half-space.
+///
+/// The resulting polyhedron is expressed in the local-space of `poly1`, `pos12` being the position
+/// of `halfspace2` relative to `poly1`. Returns `Ok(None)` if the polyhedron is completely outside
+/// of the half-space. Returns an error if the convex hull of the clipped polyhedron couldn’t be
+/// computed.
+pub fn convex_polyhedron_halfspace_intersection(
+    pos12: &Isometry<Real>,
+    poly1: &ConvexPolyhedron,
+    halfspace2: &HalfSpace,
+) -> Result<Option<ConvexPolyhedron>, ConvexHullError> {
+    let clipped1 = ClippedPolyhedron::from_convex_polyhedron(poly1);
+    let normal = pos12 * halfspace2.normal;
+    let plane = ClippedPolyhedron {
+        points: Vec::new(),
+        edges: Vec::new(),
+        planes: alloc::vec![(normal, normal.dot(&pos12.translation.vector))],
+    };
+
+    let mut points = Vec::new();
+    clipped1.clip_against(
+        &plane,
+        ComplexField::sqrt(crate::math::DEFAULT_EPSILON),
+        &mut points,
+    );
+    convex_polyhedron_from_points(&points)
+}
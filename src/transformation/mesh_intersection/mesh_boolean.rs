@@ -0,0 +1,170 @@
+use super::mesh_intersection::intersect_meshes_with_provenance;
+use super::{MeshIntersectionError, MeshIntersectionTolerances};
+use crate::math::{Isometry, Real};
+use crate::shape::{TriMesh, TriMeshFlags};
+use alloc::vec::Vec;
+use na::Point3;
+
+/// A boolean operation between two solid meshes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum MeshBooleanOp {
+    /// The volume covered by at least one of the meshes.
+    Union,
+    /// The volume covered by the first mesh but not by the second.
+    Difference,
+    /// The volume covered by both meshes.
+    Intersection,
+    /// The volume covered by exactly one of the meshes.
+    ///
+    /// The result is made of the closed shells of both differences, which touch along the
+    /// intersection curves of the meshes without sharing any vertex or edge there.
+    Xor,
+}
+
+/// The triangle of the input meshes an output triangle of a [`mesh_boolean`] was generated from.
+///
+/// An output triangle is either a copy of its input triangle, or a part of it if the input
+/// triangle was split by the surface of the other mesh. Its orientation is reversed if it comes
+/// from the second mesh of a [`MeshBooleanOp::Difference`], or from the part of a mesh lying
+/// inside of the other one for a [`MeshBooleanOp::Xor`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TriangleProvenance {
+    /// The output triangle comes from the triangle with this index in the first mesh.
+    Mesh1(u32),
+    /// The output triangle comes from the triangle with this index in the second mesh.
+    Mesh2(u32),
+}
+
+impl TriangleProvenance {
+    /// The index of the input triangle, in its mesh.
+    pub fn triangle(self) -> u32 {
+        match self {
+            Self::Mesh1(tid) | Self::Mesh2(tid) => tid,
+        }
+    }
+
+    fn swapped(self) -> Self {
+        match self {
+            Self::Mesh1(tid) => Self::Mesh2(tid),
+            Self::Mesh2(tid) => Self::Mesh1(tid),
+        }
+    }
+}
+
+/// The result of a [`mesh_boolean`].
+#[derive(Clone)]
+pub struct MeshBoolean {
+    /// The resulting mesh.
+    ///
+    /// It has its half-edge topology, pseudo-normals and connected components computed (i.e.,
+    /// [`TriMeshFlags::ORIENTED`], [`TriMeshFlags::HALF_EDGE_TOPOLOGY`] and
+    /// [`TriMeshFlags::CONNECTED_COMPONENTS`] are set) so it can be used as the input of another
+    /// boolean operation.
+    pub mesh: TriMesh,
+    /// The provenance of each triangle of `mesh`, i.e., `provenance[i]` is the input triangle
+    /// `mesh.indices()[i]` was generated from.
+    pub provenance: Vec<TriangleProvenance>,
+}
+
+/// Computes a boolean operation between two solid meshes.
+///
+/// The meshes must be oriented, have their half-edge topology computed, and must not be
+/// self-intersecting. The vertices of the resulting mesh are expressed in the same coordinate
+/// frame as `pos1` and `pos2`. Returns `None` if the result is empty.
+pub fn mesh_boolean(
+    op: MeshBooleanOp,
+    pos1: &Isometry<Real>,
+    mesh1: &TriMesh,
+    pos2: &Isometry<Real>,
+    mesh2: &TriMesh,
+) -> Result<Option<MeshBoolean>, MeshIntersectionError> {
+    mesh_boolean_with_tolerances(
+        op,
+        pos1,
+        mesh1,
+        pos2,
+        mesh2,
+        MeshIntersectionTolerances::default(),
+    )
+}
+
+/// Similar to [`mesh_boolean`].
+///
+/// It allows to specify epsilons for how the algorithm will behave.
+/// See [`MeshIntersectionTolerances`] for details.
+pub fn mesh_boolean_with_tolerances(
+    op: MeshBooleanOp,
+    pos1: &Isometry<Real>,
+    mesh1: &TriMesh,
+    pos2: &Isometry<Real>,
+    mesh2: &TriMesh,
+    tolerances: MeshIntersectionTolerances,
+) -> Result<Option<MeshBoolean>, MeshIntersectionError> {
+    let (vertices, mut indices, provenance) = match op {
+        MeshBooleanOp::Intersection => {
+            intersect_meshes_with_provenance(pos1, mesh1, false, pos2, mesh2, false, tolerances)?
+        }
+        MeshBooleanOp::Difference => {
+            intersect_meshes_with_provenance(pos1, mesh1, false, pos2, mesh2, true, tolerances)?
+        }
+        MeshBooleanOp::Union => {
+            // A ∪ B is the complement of ¬A ∩ ¬B.
+            let (vertices, mut indices, provenance) =
+                intersect_meshes_with_provenance(pos1, mesh1, true, pos2, mesh2, true, tolerances)?;
+            indices.iter_mut().for_each(|idx| idx.swap(0, 1));
+            (vertices, indices, provenance)
+        }
+        MeshBooleanOp::Xor => {
+            let (mut vertices, mut indices, mut provenance) = intersect_meshes_with_provenance(
+                pos1, mesh1, false, pos2, mesh2, true, tolerances,
+            )?;
+            let (vertices21, indices21, provenance21) = intersect_meshes_with_provenance(
+                pos2, mesh2, false, pos1, mesh1, true, tolerances,
+            )?;
+
+            // NOTE: the vertices of both differences aren’t merged. Otherwise, the edges along
+            //       the intersection curves would be shared by four triangles, preventing the
+            //       computation of the half-edge topology. Each difference is a closed shell, so
+            //       every edge of the result still has a twin.
+            let base = vertices.len() as u32;
+            vertices.extend_from_slice(&vertices21);
+            indices.extend(indices21.iter().map(|idx| idx.map(|i| i + base)));
+            provenance.extend(provenance21.iter().map(|p| p.swapped()));
+            (vertices, indices, provenance)
+        }
+    };
+
+    if indices.is_empty() {
+        return Ok(None);
+    }
+
+    let vertices = remove_unused_vertices(&vertices, &mut indices);
+    let mesh = TriMesh::with_flags(
+        vertices,
+        indices,
+        TriMeshFlags::ORIENTED
+            | TriMeshFlags::HALF_EDGE_TOPOLOGY
+            | TriMeshFlags::CONNECTED_COMPONENTS,
+    )?;
+
+    Ok(Some(MeshBoolean { mesh, provenance }))
+}
+
+/// Removes the vertices not referenced by any triangle, and remaps the indices accordingly.
+fn remove_unused_vertices(
+    vertices: &[Point3<Real>],
+    indices: &mut [[u32; 3]],
+) -> Vec<Point3<Real>> {
+    let mut remap = alloc::vec![u32::MAX; vertices.len()];
+    let mut new_vertices = Vec::new();
+
+    for idx in indices.iter_mut().flatten() {
+        if remap[*idx as usize] == u32::MAX {
+            remap[*idx as usize] = new_vertices.len() as u32;
+            new_vertices.push(vertices[*idx as usize]);
+        }
+        *idx = remap[*idx as usize];
+    }
+
+    new_vertices
+}
@@ -1,4 +1,4 @@
-use super::{MeshIntersectionError, TriangleTriangleIntersection};
+use super::{MeshIntersectionError, TriangleProvenance, TriangleTriangleIntersection};
 use crate::bounding_volume::BoundingVolume;
 use crate::math::{Isometry, Real};
 use crate::partitioning::BvhNode;
@@ -102,6 +102,30 @@ pub fn intersect_meshes_with_tolerances(
     flip2: bool,
     tolerances: MeshIntersectionTolerances,
 ) -> Result<Option<TriMesh>, MeshIntersectionError> {
+    let (vertices, indices, _) =
+        intersect_meshes_with_provenance(pos1, mesh1, flip1, pos2, mesh2, flip2, tolerances)?;
+
+    if !indices.is_empty() {
+        Ok(Some(TriMesh::new(vertices, indices)?))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Computes the intersection of two meshes, as well as the triangle of the input meshes each
+/// output triangle was generated from.
+///
+/// Returns the vertices and indices of the output triangles, and their provenance. The
+/// vertices are expressed in the same coordinate frame as `pos1` and `pos2`.
+pub(super) fn intersect_meshes_with_provenance(
+    pos1: &Isometry<Real>,
+    mesh1: &TriMesh,
+    flip1: bool,
+    pos2: &Isometry<Real>,
+    mesh2: &TriMesh,
+    flip2: bool,
+    tolerances: MeshIntersectionTolerances,
+) -> Result<(Vec<Point3<Real>>, Vec<[u32; 3]>, Vec<TriangleProvenance>), MeshIntersectionError> {
     if cfg!(debug_assertions) {
         mesh1.assert_half_edge_topology_is_valid();
         mesh2.assert_half_edge_topology_is_valid();
@@ -130,8 +154,8 @@ pub fn intersect_meshes_with_tolerances(
 
     let mut deleted_faces1: HashSet<u32> = HashSet::default();
     let mut deleted_faces2: HashSet<u32> = HashSet::default();
-    let mut new_indices1 = vec![];
-    let mut new_indices2 = vec![];
+    let mut new_faces1 = vec![];
+    let mut new_faces2 = vec![];
 
     // 2: Identify all triangles that do actually intersect.
     let mut intersections = vec![];
@@ -155,7 +179,7 @@ pub fn intersect_meshes_with_tolerances(
         mesh2,
         flip2,
         &deleted_faces1,
-        &mut new_indices1,
+        &mut new_faces1,
     );
     extract_connected_components(
         &pos12.inverse(),
@@ -163,7 +187,7 @@ pub fn intersect_meshes_with_tolerances(
         mesh1,
         flip1,
         &deleted_faces2,
-        &mut new_indices2,
+        &mut new_faces2,
     );
 
     // 4: Initialize a new mesh by inserting points into a set. Duplicate points should
@@ -179,7 +203,8 @@ pub fn intersect_meshes_with_tolerances(
             ) as u32
         };
         // Add the inside vertices and triangles from mesh1
-        for mut face in new_indices1 {
+        for fid in new_faces1 {
+            let mut face = mesh1.indices()[fid as usize];
             if flip1 {
                 face.swap(0, 1);
             }
@@ -191,12 +216,13 @@ pub fn intersect_meshes_with_tolerances(
             ];
 
             if !is_topologically_degenerate(idx) {
-                insert_topology_indices(&mut topology_indices, idx);
+                insert_topology_indices(&mut topology_indices, idx, TriangleProvenance::Mesh1(fid));
             }
         }
 
         // Add the inside vertices and triangles from mesh2
-        for mut face in new_indices2 {
+        for fid in new_faces2 {
+            let mut face = mesh2.indices()[fid as usize];
             if flip2 {
                 face.swap(0, 1);
             }
@@ -207,7 +233,7 @@ pub fn intersect_meshes_with_tolerances(
             ];
 
             if !is_topologically_degenerate(idx) {
-                insert_topology_indices(&mut topology_indices, idx);
+                insert_topology_indices(&mut topology_indices, idx, TriangleProvenance::Mesh2(fid));
             }
         }
     }
@@ -259,6 +285,7 @@ pub fn intersect_meshes_with_tolerances(
         pos2,
         flip1,
         flip2,
+        TriangleProvenance::Mesh1,
         &tolerances,
         &mut point_set,
         &mut topology_indices,
@@ -272,6 +299,7 @@ pub fn intersect_meshes_with_tolerances(
         pos1,
         flip2,
         flip1,
+        TriangleProvenance::Mesh2,
         &tolerances,
         &mut point_set,
         &mut topology_indices,
@@ -281,15 +309,9 @@ pub fn intersect_meshes_with_tolerances(
     let mut vertices: Vec<_> = point_set.iter().copied().collect();
     vertices.sort_by(|a, b| a.id.cmp(&b.id));
     let vertices: Vec<_> = vertices.iter().map(|p| Point3::from(p.point)).collect();
+    let (indices, provenance) = topology_indices.into_values().unzip();
 
-    if !topology_indices.is_empty() {
-        Ok(Some(TriMesh::new(
-            vertices,
-            topology_indices.into_values().collect(),
-        )?))
-    } else {
-        Ok(None)
-    }
+    Ok((vertices, indices, provenance))
 }
 
 fn extract_connected_components(
@@ -298,7 +320,7 @@ fn extract_connected_components(
     mesh2: &TriMesh,
     flip2: bool,
     deleted_faces1: &HashSet<u32>,
-    new_indices1: &mut Vec<[u32; 3]>,
+    new_faces1: &mut Vec<u32>,
 ) {
     let topo1 = mesh1.topology().unwrap();
     let mut visited: HashSet<u32> = HashSet::default();
@@ -341,7 +363,7 @@ fn extract_connected_components(
             continue; // Already visited.
         }
 
-        new_indices1.push(mesh1.indices()[face as usize]);
+        new_faces1.push(face);
 
         let eid = topo1.faces[face as usize].half_edge;
         let edge_a = &topo1.half_edges[eid as usize];
@@ -370,14 +392,9 @@ fn extract_connected_components(
                 // if the whole thing is inside or outside.
                 let repr_face = cc.grouped_faces[range[0]];
                 let repr_pt = mesh1.triangle(repr_face).center();
-                let indices = mesh1.indices();
 
                 if flip2 ^ mesh2.contains_local_point(&pos12.inverse_transform_point(&repr_pt)) {
-                    new_indices1.extend(
-                        cc.grouped_faces[range[0]..range[1]]
-                            .iter()
-                            .map(|fid| indices[*fid as usize]),
-                    )
+                    new_faces1.extend_from_slice(&cc.grouped_faces[range[0]..range[1]])
                 }
             }
         }
@@ -386,7 +403,7 @@ fn extract_connected_components(
         let repr_pt = mesh1.triangle(0).center();
 
         if flip2 ^ mesh2.contains_local_point(&pos12.inverse_transform_point(&repr_pt)) {
-            new_indices1.extend_from_slice(mesh1.indices());
+            new_faces1.extend(0..mesh1.indices().len() as u32);
         }
     }
 }
@@ -439,7 +456,10 @@ fn triangulate_constraints_and_merge_duplicates(
         let p1_id = insert_into_set(point_pair[0], &mut point_set, epsilon);
         let p2_id = insert_into_set(point_pair[1], &mut point_set, epsilon);
 
-        edges.push([p1_id, p2_id]);
+        // Both endpoints might have been merged if the constraint is very short.
+        if p1_id != p2_id {
+            edges.push([p1_id, p2_id]);
+        }
     }
 
     let mut points: Vec<_> = point_set.iter().cloned().collect();
@@ -625,9 +645,10 @@ fn merge_triangle_sets(
     pos2: &Isometry<Real>,
     flip1: bool,
     flip2: bool,
+    provenance1: fn(u32) -> TriangleProvenance,
     metadata: &MeshIntersectionTolerances,
     point_set: &mut RTree<TreePoint>,
-    topology_indices: &mut HashMap<HashableTriangleIndices, [u32; 3]>,
+    topology_indices: &mut HashMap<HashableTriangleIndices, ([u32; 3], TriangleProvenance)>,
 ) -> Result<(), MeshIntersectionError> {
     // For each triangle, and each constraint edge associated to that triangle,
     // make a triangulation of the face and sort whether each generated
@@ -690,7 +711,7 @@ fn merge_triangle_sets(
                     return Err(MeshIntersectionError::DuplicateVertices);
                 }
 
-                insert_topology_indices(topology_indices, new_tri_idx);
+                insert_topology_indices(topology_indices, new_tri_idx, provenance1(**triangle_id));
             }
         }
     }
@@ -704,12 +725,13 @@ fn merge_triangle_sets(
 // set of indices but opposite orientations. If this happens, both the new triangle, and the one it
 // matched with are removed (because they describe a degenerate piece of volume).
 fn insert_topology_indices(
-    topology_indices: &mut HashMap<HashableTriangleIndices, [u32; 3]>,
+    topology_indices: &mut HashMap<HashableTriangleIndices, ([u32; 3], TriangleProvenance)>,
     new_tri_idx: [u32; 3],
+    provenance: TriangleProvenance,
 ) {
     match topology_indices.entry(new_tri_idx.into()) {
         Entry::Vacant(e) => {
-            let _ = e.insert(new_tri_idx);
+            let _ = e.insert((new_tri_idx, provenance));
         }
         Entry::Occupied(e) => {
             fn same_orientation(a: &[u32; 3], b: &[u32; 3]) -> bool {
@@ -723,7 +745,7 @@ fn insert_topology_indices(
                 a[1] == b[(ib + 1) % 3]
             }
 
-            if !same_orientation(e.get(), &new_tri_idx) {
+            if !same_orientation(&e.get().0, &new_tri_idx) {
                 // If we are inserting two identical triangles but with mismatching
                 // orientations, we can just ignore both because they cover a degenerate
                 // 2D plane.
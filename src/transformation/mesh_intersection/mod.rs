@@ -1,13 +1,21 @@
+pub use self::mesh_boolean::{
+    mesh_boolean, mesh_boolean_with_tolerances, MeshBoolean, MeshBooleanOp, TriangleProvenance,
+};
 pub use self::mesh_intersection::{
     intersect_meshes, intersect_meshes_with_tolerances, MeshIntersectionTolerances,
 };
 pub use self::mesh_intersection_error::MeshIntersectionError;
+pub use self::triangle_pairs_intersection::{
+    intersecting_triangle_pairs, self_intersecting_triangle_pairs,
+};
 use triangle_triangle_intersection::*;
 
 use crate::math::Real;
 
+mod mesh_boolean;
 mod mesh_intersection;
 mod mesh_intersection_error;
+mod triangle_pairs_intersection;
 mod triangle_triangle_intersection;
 
 const EPS: Real = 1.0e-6;
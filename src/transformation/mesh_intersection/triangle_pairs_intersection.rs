@@ -0,0 +1,105 @@
+use super::{MeshIntersectionTolerances, TriangleTriangleIntersection};
+use crate::bounding_volume::BoundingVolume;
+use crate::math::{Isometry, Real};
+use crate::partitioning::{BvhWorkspace, TraversalAction};
+use crate::shape::{Segment, TriMesh, Triangle};
+use alloc::vec::Vec;
+
+/// Computes all the pairs of intersecting triangles between two meshes.
+///
+/// Each element of the result is a tuple `(tri1, tri2, segment)` where `tri1` is the index of a
+/// triangle of `mesh1`, `tri2` is the index of a triangle of `mesh2`, and `segment` is a piece of
+/// their intersection. Two non-coplanar triangles intersect along a single segment. If two
+/// triangles are coplanar and overlap, one segment is reported for each edge of the polygon
+/// resulting from their intersection.
+///
+/// The segments are expressed in the local-space of `mesh1`.
+pub fn intersecting_triangle_pairs(
+    pos1: &Isometry<Real>,
+    mesh1: &TriMesh,
+    pos2: &Isometry<Real>,
+    mesh2: &TriMesh,
+    tolerances: MeshIntersectionTolerances,
+) -> Vec<(u32, u32, Segment)> {
+    let pos12 = pos1.inv_mul(pos2);
+    let mut result = Vec::new();
+
+    mesh1
+        .bvh()
+        .traverse_bvtt(mesh2.bvh(), &pos12, |node1, node2, aabb2| {
+            if !node1.aabb().intersects(aabb2) {
+                return TraversalAction::Prune;
+            }
+
+            if let (Some(fid1), Some(fid2)) = (node1.leaf_data(), node2.leaf_data()) {
+                let tri1 = mesh1.triangle(fid1);
+                let tri2 = mesh2.triangle(fid2).transformed(&pos12);
+                push_intersection_segments(fid1, &tri1, fid2, &tri2, &tolerances, &mut result);
+            }
+
+            TraversalAction::Continue
+        });
+
+    result
+}
+
+/// Computes all the pairs of intersecting triangles within a single mesh.
+///
+/// This is useful for detecting self-intersections. Pairs of triangles sharing at least one
+/// vertex index are ignored since adjacent triangles always touch each other. Each element of the
+/// result is a tuple `(tri1, tri2, segment)` with `tri1 < tri2`. See
+/// [`intersecting_triangle_pairs`] for details on the reported segments.
+///
+/// The segments are expressed in the local-space of `mesh`.
+pub fn self_intersecting_triangle_pairs(
+    mesh: &TriMesh,
+    tolerances: MeshIntersectionTolerances,
+) -> Vec<(u32, u32, Segment)> {
+    let indices = mesh.indices();
+    let mut workspace = BvhWorkspace::default();
+    let mut result = Vec::new();
+
+    mesh.bvh()
+        .traverse_bvtt_single_tree::<false>(&mut workspace, &mut |leaf1, leaf2| {
+            let (fid1, fid2) = (leaf1.min(leaf2), leaf1.max(leaf2));
+            let idx1 = indices[fid1 as usize];
+            let idx2 = indices[fid2 as usize];
+
+            if idx1.iter().any(|i| idx2.contains(i)) {
+                // Adjacent triangles.
+                return;
+            }
+
+            let tri1 = mesh.triangle(fid1);
+            let tri2 = mesh.triangle(fid2);
+            push_intersection_segments(fid1, &tri1, fid2, &tri2, &tolerances, &mut result);
+        });
+
+    result
+}
+
+fn push_intersection_segments(
+    fid1: u32,
+    tri1: &Triangle,
+    fid2: u32,
+    tri2: &Triangle,
+    tolerances: &MeshIntersectionTolerances,
+    out: &mut Vec<(u32, u32, Segment)>,
+) {
+    match super::triangle_triangle_intersection(tri1, tri2, tolerances.collinearity_epsilon) {
+        Some(TriangleTriangleIntersection::Segment { a, b }) => {
+            out.push((fid1, fid2, Segment::new(a.p1, b.p1)))
+        }
+        Some(TriangleTriangleIntersection::Polygon(polygon)) if polygon.len() == 2 => {
+            out.push((fid1, fid2, Segment::new(polygon[0].p1, polygon[1].p1)))
+        }
+        Some(TriangleTriangleIntersection::Polygon(polygon)) if polygon.len() > 2 => {
+            for i in 0..polygon.len() {
+                let a = polygon[i];
+                let b = polygon[(i + 1) % polygon.len()];
+                out.push((fid1, fid2, Segment::new(a.p1, b.p1)));
+            }
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,535 @@
+//! Repair of triangle meshes with inconsistent orientations, holes, or T-junctions.
+
+use crate::math::{Point, Real};
+use crate::utils::hashmap::{Entry, HashMap};
+use crate::utils::hashset::HashSet;
+use crate::utils::{HashablePartialEq, SortedPair};
+use alloc::vec::Vec;
+use smallvec::SmallVec;
+
+/// Parameters controlling the repair of a triangle mesh with [`repair_trimesh`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct MeshRepairParameters {
+    /// If `true`, vertices with the exact same coordinates are merged.
+    ///
+    /// Default: `true`
+    pub merge_duplicate_vertices: bool,
+    /// If `true`, triangles with a vertex lying on one of their boundary edges are split at that
+    /// vertex.
+    ///
+    /// Default: `true`
+    pub split_t_junctions: bool,
+    /// The maximum distance between a vertex and a boundary edge for that vertex to be considered
+    /// a T-junction on that edge.
+    ///
+    /// Default: `1.0e-5`
+    pub t_junction_tolerance: Real,
+    /// If `true`, the triangles of each connected component are oriented consistently, with
+    /// their normals pointing outward.
+    ///
+    /// Default: `true`
+    pub orient: bool,
+    /// The maximum number of edges of a boundary loop for the hole it delimits to be filled.
+    ///
+    /// Set this to 0 to disable hole filling.
+    ///
+    /// Default: 256
+    pub max_hole_edges: usize,
+}
+
+impl Default for MeshRepairParameters {
+    fn default() -> Self {
+        Self {
+            merge_duplicate_vertices: true,
+            split_t_junctions: true,
+            t_junction_tolerance: 1.0e-5,
+            orient: true,
+            max_hole_edges: 256,
+        }
+    }
+}
+
+/// A summary of the changes applied by [`repair_trimesh`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct MeshRepairReport {
+    /// The number of input vertices removed because they were duplicates of another vertex or
+    /// weren’t referenced by any triangle.
+    pub removed_vertices: usize,
+    /// The number of input triangles removed because they had two identical vertices, or had
+    /// the same vertices as another triangle.
+    pub removed_triangles: usize,
+    /// The number of vertices at which a triangle edge was split to remove a T-junction.
+    pub split_t_junctions: usize,
+    /// The number of triangles which had their orientation reversed.
+    pub flipped_triangles: usize,
+    /// The number of holes that were filled.
+    pub filled_holes: usize,
+    /// The number of triangles added to fill the holes.
+    pub added_triangles: usize,
+    /// The number of boundary loops left open, either because they have more than
+    /// [`MeshRepairParameters::max_hole_edges`] edges or because they couldn’t be closed.
+    pub unfilled_holes: usize,
+}
+
+impl MeshRepairReport {
+    /// Returns `true` if the repair modified the mesh.
+    pub fn has_changes(&self) -> bool {
+        self.removed_vertices
+            + self.removed_triangles
+            + self.split_t_junctions
+            + self.flipped_triangles
+            + self.added_triangles
+            > 0
+    }
+}
+
+/// Repairs a triangle mesh so it can be used with [`TriMeshFlags::HALF_EDGE_TOPOLOGY`] and
+/// [`TriMeshFlags::ORIENTED`].
+///
+/// The following steps are applied in order, depending on `params`:
+/// 1. Duplicate vertices are merged, then degenerate and duplicate triangles are removed.
+/// 2. T-junctions are removed by splitting the boundary edges passing through a vertex of
+///    another boundary edge.
+/// 3. The triangles of each connected component are oriented consistently by flood-filling the
+///    adjacency graph of the triangles across their manifold edges.
+/// 4. The holes delimited by the boundary loops of the mesh are filled with a minimal-area
+///    triangulation.
+/// 5. Each connected component is flipped if its signed volume is negative, so its triangles
+///    point outward.
+///
+/// Returns the vertices and indices of the repaired mesh, and a report of what was changed.
+///
+/// [`TriMeshFlags::HALF_EDGE_TOPOLOGY`]: crate::shape::TriMeshFlags::HALF_EDGE_TOPOLOGY
+/// [`TriMeshFlags::ORIENTED`]: crate::shape::TriMeshFlags::ORIENTED
+pub fn repair_trimesh(
+    vertices: &[Point<Real>],
+    indices: &[[u32; 3]],
+    params: &MeshRepairParameters,
+) -> (Vec<Point<Real>>, Vec<[u32; 3]>, MeshRepairReport) {
+    let mut report = MeshRepairReport::default();
+    let (vertices, mut indices) = cleanup(
+        vertices,
+        indices,
+        params.merge_duplicate_vertices,
+        &mut report,
+    );
+
+    if params.split_t_junctions {
+        report.split_t_junctions =
+            split_t_junctions(&vertices, &mut indices, params.t_junction_tolerance);
+    }
+
+    let mut flipped = alloc::vec![false; indices.len()];
+    let num_faces_before_filling = indices.len();
+
+    if params.orient {
+        let _ = orient_consistently(&mut indices, &mut flipped);
+    }
+
+    if params.max_hole_edges > 0 {
+        fill_holes(&vertices, &mut indices, params.max_hole_edges, &mut report);
+        flipped.resize(indices.len(), false);
+    }
+
+    if params.orient {
+        for component in orient_consistently(&mut indices, &mut flipped) {
+            if signed_volume(&vertices, &indices, &component) < 0.0 {
+                for fid in component {
+                    indices[fid as usize].swap(1, 2);
+                    flipped[fid as usize] = !flipped[fid as usize];
+                }
+            }
+        }
+
+        report.flipped_triangles = flipped[..num_faces_before_filling]
+            .iter()
+            .filter(|f| **f)
+            .count();
+    }
+
+    (vertices, indices, report)
+}
+
+/// Merges the duplicate vertices, removes the degenerate and duplicate triangles, and removes
+/// the unreferenced vertices.
+fn cleanup(
+    vertices: &[Point<Real>],
+    indices: &[[u32; 3]],
+    merge_duplicate_vertices: bool,
+    report: &mut MeshRepairReport,
+) -> (Vec<Point<Real>>, Vec<[u32; 3]>) {
+    let mut vtx_to_id = HashMap::default();
+    let mut remap = alloc::vec![u32::MAX; vertices.len()];
+    let mut new_vertices = Vec::with_capacity(vertices.len());
+    let mut new_indices = Vec::with_capacity(indices.len());
+    let mut triangle_set = HashSet::default();
+
+    for idx in indices {
+        let new_idx = idx.map(|vid| {
+            if remap[vid as usize] == u32::MAX {
+                let pt = vertices[vid as usize];
+                remap[vid as usize] = if merge_duplicate_vertices {
+                    match vtx_to_id.entry(HashablePartialEq::new(pt)) {
+                        Entry::Occupied(entry) => *entry.get(),
+                        Entry::Vacant(entry) => {
+                            new_vertices.push(pt);
+                            *entry.insert(new_vertices.len() as u32 - 1)
+                        }
+                    }
+                } else {
+                    new_vertices.push(pt);
+                    new_vertices.len() as u32 - 1
+                };
+            }
+
+            remap[vid as usize]
+        });
+
+        let [a, b, c] = new_idx;
+        let is_degenerate = a == b || b == c || c == a;
+        let (c, b, a) = crate::utils::sort3(&a, &b, &c);
+
+        if !is_degenerate && triangle_set.insert((*a, *b, *c)) {
+            new_indices.push(new_idx);
+        }
+    }
+
+    report.removed_vertices = vertices.len() - new_vertices.len();
+    report.removed_triangles = indices.len() - new_indices.len();
+    (new_vertices, new_indices)
+}
+
+/// Maps each edge of the mesh to the triangles it is adjacent to.
+fn edge_faces(indices: &[[u32; 3]]) -> HashMap<SortedPair<u32>, SmallVec<[u32; 2]>> {
+    let mut result: HashMap<_, SmallVec<_>> = HashMap::default();
+
+    for (fid, idx) in indices.iter().enumerate() {
+        for k in 0..3 {
+            result
+                .entry(SortedPair::new(idx[k], idx[(k + 1) % 3]))
+                .or_default()
+                .push(fid as u32);
+        }
+    }
+
+    result
+}
+
+/// Lists the boundary edges of the mesh, as pairs of a triangle and the index of the edge in
+/// that triangle.
+fn boundary_edges(indices: &[[u32; 3]]) -> Vec<(u32, usize)> {
+    let edges = edge_faces(indices);
+    let mut result = Vec::new();
+
+    for (fid, idx) in indices.iter().enumerate() {
+        for k in 0..3 {
+            if edges[&SortedPair::new(idx[k], idx[(k + 1) % 3])].len() == 1 {
+                result.push((fid as u32, k));
+            }
+        }
+    }
+
+    result
+}
+
+/// Splits the triangles with a boundary edge passing through the endpoint of another boundary
+/// edge.
+///
+/// Returns the number of splits.
+fn split_t_junctions(
+    vertices: &[Point<Real>],
+    indices: &mut Vec<[u32; 3]>,
+    tolerance: Real,
+) -> usize {
+    // NOTE: each pass splits at most one edge per triangle so new triangles can simply be
+    //       fanned from the vertex opposite to the split edge. Three passes are sufficient if
+    //       all the edges of a triangle need to be split, but adding vertices can expose new
+    //       T-junctions.
+    const MAX_PASSES: usize = 8;
+    let mut num_splits = 0;
+
+    for _ in 0..MAX_PASSES {
+        let boundary = boundary_edges(indices);
+
+        // The endpoints of the boundary edges, sorted along the `x` axis.
+        let mut candidates: Vec<u32> = boundary
+            .iter()
+            .flat_map(|(fid, k)| {
+                let idx = indices[*fid as usize];
+                [idx[*k], idx[(*k + 1) % 3]]
+            })
+            .collect();
+        candidates.sort_unstable();
+        candidates.dedup();
+        candidates.sort_by(|a, b| vertices[*a as usize].x.total_cmp(&vertices[*b as usize].x));
+
+        let mut new_indices = Vec::with_capacity(indices.len());
+        let mut split_faces = alloc::vec![false; indices.len()];
+        let mut on_edge = Vec::new();
+
+        for (fid, k) in boundary {
+            if split_faces[fid as usize] {
+                continue;
+            }
+
+            let idx = indices[fid as usize];
+            let [a, b, c] = [idx[k], idx[(k + 1) % 3], idx[(k + 2) % 3]];
+            let pa = vertices[a as usize];
+            let pb = vertices[b as usize];
+            let ab = pb - pa;
+            let sq_len = ab.norm_squared();
+
+            if sq_len == 0.0 {
+                continue;
+            }
+
+            let min_x = pa.x.min(pb.x) - tolerance;
+            let max_x = pa.x.max(pb.x) + tolerance;
+            let first = candidates.partition_point(|vid| vertices[*vid as usize].x < min_x);
+
+            on_edge.clear();
+
+            for vid in &candidates[first..] {
+                let pt = vertices[*vid as usize];
+
+                if pt.x > max_x {
+                    break;
+                }
+
+                if *vid == a || *vid == b || *vid == c {
+                    continue;
+                }
+
+                let t = (pt - pa).dot(&ab) / sq_len;
+                if t > 0.0 && t < 1.0 && (pa + ab * t - pt).norm() <= tolerance {
+                    on_edge.push((t, *vid));
+                }
+            }
+
+            if on_edge.is_empty() {
+                continue;
+            }
+
+            on_edge.sort_by(|a, b| a.0.total_cmp(&b.0));
+            split_faces[fid as usize] = true;
+            num_splits += on_edge.len();
+
+            let mut prev = a;
+            for (_, vid) in &on_edge {
+                new_indices.push([prev, *vid, c]);
+                prev = *vid;
+            }
+            new_indices.push([prev, b, c]);
+        }
+
+        if new_indices.is_empty() {
+            break;
+        }
+
+        new_indices.extend(
+            indices
+                .iter()
+                .zip(&split_faces)
+                .filter(|(_, split)| !**split)
+                .map(|(idx, _)| *idx),
+        );
+        *indices = new_indices;
+    }
+
+    num_splits
+}
+
+/// Orients the triangles of each connected component consistently.
+///
+/// The orientation of the first triangle of each component is propagated to its neighbors
+/// across manifold edges (i.e. edges shared by exactly two triangles). The triangles that were
+/// flipped are toggled in `flipped`.
+///
+/// Returns the triangles of each connected component.
+fn orient_consistently(indices: &mut [[u32; 3]], flipped: &mut [bool]) -> Vec<Vec<u32>> {
+    let edges = edge_faces(indices);
+    let mut visited = alloc::vec![false; indices.len()];
+    let mut components = Vec::new();
+    let mut stack = Vec::new();
+
+    for seed in 0..indices.len() {
+        if visited[seed] {
+            continue;
+        }
+
+        let mut component = Vec::new();
+        visited[seed] = true;
+        stack.push(seed as u32);
+
+        while let Some(fid) = stack.pop() {
+            component.push(fid);
+            let idx = indices[fid as usize];
+
+            for k in 0..3 {
+                let (u, v) = (idx[k], idx[(k + 1) % 3]);
+                let adjacent = &edges[&SortedPair::new(u, v)];
+
+                if adjacent.len() != 2 {
+                    continue;
+                }
+
+                let neighbor = if adjacent[0] == fid {
+                    adjacent[1]
+                } else {
+                    adjacent[0]
+                };
+
+                if visited[neighbor as usize] {
+                    continue;
+                }
+
+                // The neighbor must traverse the shared edge from `v` to `u`.
+                let nbh_idx = &mut indices[neighbor as usize];
+                if (0..3).any(|l| nbh_idx[l] == u && nbh_idx[(l + 1) % 3] == v) {
+                    nbh_idx.swap(1, 2);
+                    flipped[neighbor as usize] = !flipped[neighbor as usize];
+                }
+
+                visited[neighbor as usize] = true;
+                stack.push(neighbor);
+            }
+        }
+
+        components.push(component);
+    }
+
+    components
+}
+
+/// Fills the holes delimited by the boundary loops of the mesh.
+fn fill_holes(
+    vertices: &[Point<Real>],
+    indices: &mut Vec<[u32; 3]>,
+    max_hole_edges: usize,
+    report: &mut MeshRepairReport,
+) {
+    let boundary: Vec<_> = boundary_edges(indices)
+        .into_iter()
+        .map(|(fid, k)| {
+            let idx = indices[fid as usize];
+            [idx[k], idx[(k + 1) % 3]]
+        })
+        .collect();
+    let mut outgoing: HashMap<u32, SmallVec<[u32; 2]>> = HashMap::default();
+
+    for (eid, edge) in boundary.iter().enumerate() {
+        outgoing.entry(edge[0]).or_default().push(eid as u32);
+    }
+
+    let mut used = alloc::vec![false; boundary.len()];
+    let mut hole = Vec::new();
+
+    for first in 0..boundary.len() {
+        if used[first] {
+            continue;
+        }
+
+        // Follow the boundary edges until we get back to the first vertex.
+        let start = boundary[first][0];
+        let mut curr = first;
+        let mut closed = false;
+        hole.clear();
+
+        loop {
+            used[curr] = true;
+            hole.push(boundary[curr][0]);
+            let end = boundary[curr][1];
+
+            if end == start {
+                closed = true;
+                break;
+            }
+
+            match outgoing
+                .get(&end)
+                .and_then(|edges| edges.iter().find(|eid| !used[**eid as usize]))
+            {
+                Some(next) => curr = *next as usize,
+                None => break,
+            }
+        }
+
+        if closed && hole.len() >= 3 && hole.len() <= max_hole_edges {
+            let num_faces = indices.len();
+            triangulate_hole(vertices, &hole, indices);
+            report.filled_holes += 1;
+            report.added_triangles += indices.len() - num_faces;
+        } else {
+            report.unfilled_holes += 1;
+        }
+    }
+}
+
+/// Triangulates a hole with the triangulation of minimal area.
+///
+/// The hole is given by its boundary loop, following the orientation of the triangles around
+/// it. The new triangles are oriented consistently with these triangles.
+fn triangulate_hole(vertices: &[Point<Real>], hole: &[u32], indices: &mut Vec<[u32; 3]>) {
+    let n = hole.len();
+    let area = |i: usize, j: usize, k: usize| {
+        let [a, b, c] = [i, j, k].map(|l| vertices[hole[l] as usize]);
+        (b - a).cross(&(c - a)).norm()
+    };
+
+    // weights[i * n + j] is the minimal area of the triangulation of the polygon
+    // `hole[i..=j]`, and splits[i * n + j] the vertex forming a triangle with `i` and `j`.
+    let mut weights = alloc::vec![0.0; n * n];
+    let mut splits = alloc::vec![0; n * n];
+
+    for len in 2..n {
+        for i in 0..n - len {
+            let j = i + len;
+            let mut best = (Real::MAX, i + 1);
+
+            for k in i + 1..j {
+                let weight = weights[i * n + k] + weights[k * n + j] + area(i, j, k);
+                if weight < best.0 {
+                    best = (weight, k);
+                }
+            }
+
+            weights[i * n + j] = best.0;
+            splits[i * n + j] = best.1;
+        }
+    }
+
+    let mut stack = alloc::vec![(0, n - 1)];
+
+    while let Some((i, j)) = stack.pop() {
+        if j - i < 2 {
+            continue;
+        }
+
+        let k = splits[i * n + j];
+        indices.push([hole[i], hole[j], hole[k]]);
+        stack.push((i, k));
+        stack.push((k, j));
+    }
+}
+
+/// The signed volume enclosed by a set of triangles of the mesh.
+fn signed_volume(vertices: &[Point<Real>], indices: &[[u32; 3]], faces: &[u32]) -> Real {
+    // Use the center of the triangles as reference to limit numerical errors, and have a
+    // meaningful sign for open meshes.
+    let mut center = Point::origin();
+    for fid in faces {
+        for vid in indices[*fid as usize] {
+            center += vertices[vid as usize].coords;
+        }
+    }
+    center /= (faces.len() * 3) as Real;
+
+    faces
+        .iter()
+        .map(|fid| {
+            let [a, b, c] = indices[*fid as usize].map(|vid| vertices[vid as usize] - center);
+            a.dot(&b.cross(&c))
+        })
+        .sum::<Real>()
+        / 6.0
+}
@@ -0,0 +1,569 @@
+//! Triangle mesh simplification based on the quadric error metric.
+
+use crate::bounding_volume::Aabb;
+use crate::math::{Point, Real, Vector};
+use crate::utils::hashmap::HashMap;
+use alloc::collections::BinaryHeap;
+use alloc::vec::Vec;
+use core::cmp::{Ordering, Reverse};
+use na::{ComplexField, Matrix4, RealField, Vector4};
+use ordered_float::OrderedFloat;
+use smallvec::SmallVec;
+
+/// Constrains the position of the simplified mesh relative to the original one.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum MeshSimplificationBound {
+    /// The simplified mesh may cross the original surface.
+    #[default]
+    None,
+    /// The simplified mesh is fully contained inside of the original mesh.
+    Inside,
+    /// The simplified mesh fully encloses the original mesh.
+    Outside,
+}
+
+/// Parameters controlling the simplification of a triangle mesh with [`simplify_trimesh`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct MeshSimplificationParameters {
+    /// The number of triangles the simplification stops at.
+    ///
+    /// The simplified mesh can have more triangles than this if `max_error` is reached first,
+    /// or if no more edges can be collapsed without changing the topology of the mesh.
+    ///
+    /// Default: 0
+    pub target_triangle_count: usize,
+    /// The maximum error introduced by an edge collapse.
+    ///
+    /// The error of a vertex is the square root of the sum of its squared distances to the
+    /// supporting planes of the original triangles merged into it.
+    ///
+    /// Default: `Real::MAX`
+    pub max_error: Real,
+    /// The weight of the penalty preventing the boundaries and sharp features of the mesh from
+    /// being eroded.
+    ///
+    /// Default: 1000.0
+    pub feature_weight: Real,
+    /// The minimum angle (in radians) between the normals of two adjacent triangles for their
+    /// shared edge to be considered a sharp feature.
+    ///
+    /// Default: π / 6
+    pub sharp_angle: Real,
+    /// Whether the simplified mesh should be kept inside or outside of the original surface.
+    ///
+    /// This requires the input mesh to be consistently oriented, with counterclockwise triangles
+    /// when seen from the outside.
+    ///
+    /// Default: `MeshSimplificationBound::None`
+    pub bound: MeshSimplificationBound,
+}
+
+impl Default for MeshSimplificationParameters {
+    fn default() -> Self {
+        Self {
+            target_triangle_count: 0,
+            max_error: Real::MAX,
+            feature_weight: 1000.0,
+            sharp_angle: Real::pi() / 6.0,
+            bound: MeshSimplificationBound::None,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+struct EdgeCollapse {
+    cost: OrderedFloat<Real>,
+    vertices: [u32; 2],
+    stamps: [u32; 2],
+    target: Point<Real>,
+}
+
+impl PartialOrd for EdgeCollapse {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for EdgeCollapse {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.cost.cmp(&other.cost)
+    }
+}
+
+impl PartialEq for EdgeCollapse {
+    fn eq(&self, other: &Self) -> bool {
+        self.cost == other.cost
+    }
+}
+
+impl Eq for EdgeCollapse {}
+
+/// Simplifies a triangle mesh by iteratively collapsing its edges.
+///
+/// This implements the quadric error metric from "Surface Simplification Using Quadric Error
+/// Metrics", Garland, Heckbert. Edges are collapsed by increasing error until the mesh reaches
+/// `params.target_triangle_count` triangles or the next collapse would exceed `params.max_error`.
+///
+/// The boundaries and sharp features of the mesh are preserved by penalty quadrics. An edge
+/// collapse is never performed if it would change the topology of the mesh (e.g. by closing a
+/// handle or merging two boundaries), make it non-manifold, or flip one of its triangles.
+/// Vertices adjacent to non-manifold edges are never moved.
+///
+/// If `params.bound` is set, the new vertex of each collapse is constrained to lie on the inner
+/// (resp. outer) side of all the triangles adjacent to the collapsed edge, following
+/// "Progressive Hulls", Sander et al. This guarantees that the simplified mesh stays inside
+/// (resp. outside) of the original closed surface.
+///
+/// Returns the vertices and indices of the simplified mesh. Unreferenced input vertices are
+/// removed.
+pub fn simplify_trimesh(
+    vertices: &[Point<Real>],
+    indices: &[[u32; 3]],
+    params: &MeshSimplificationParameters,
+) -> (Vec<Point<Real>>, Vec<[u32; 3]>) {
+    let mut simplifier = Simplifier::new(vertices, indices, params);
+    simplifier.run();
+    simplifier.output()
+}
+
+struct Simplifier<'a> {
+    params: &'a MeshSimplificationParameters,
+    vertices: Vec<Point<Real>>,
+    quadrics: Vec<Matrix4<Real>>,
+    stamps: Vec<u32>,
+    locked: Vec<bool>,
+    removed_vertices: Vec<bool>,
+    faces: Vec<[u32; 3]>,
+    removed_faces: Vec<bool>,
+    incident_faces: Vec<Vec<u32>>,
+    num_faces: usize,
+    tolerance: Real,
+    queue: BinaryHeap<Reverse<EdgeCollapse>>,
+}
+
+impl<'a> Simplifier<'a> {
+    fn new(
+        vertices: &[Point<Real>],
+        indices: &[[u32; 3]],
+        params: &'a MeshSimplificationParameters,
+    ) -> Self {
+        let mut quadrics = alloc::vec![Matrix4::zeros(); vertices.len()];
+        let mut locked = alloc::vec![false; vertices.len()];
+        let mut incident_faces = alloc::vec![Vec::new(); vertices.len()];
+        let mut edges: HashMap<[u32; 2], SmallVec<[u32; 2]>> = HashMap::default();
+        let mut faces = Vec::with_capacity(indices.len());
+
+        for idx in indices {
+            // Degenerate faces are discarded right away.
+            if idx[0] == idx[1] || idx[1] == idx[2] || idx[2] == idx[0] {
+                continue;
+            }
+
+            let fid = faces.len() as u32;
+            faces.push(*idx);
+
+            for k in 0..3 {
+                incident_faces[idx[k] as usize].push(fid);
+                edges
+                    .entry(sorted_edge(idx[k], idx[(k + 1) % 3]))
+                    .or_default()
+                    .push(fid);
+            }
+
+            if let Some(n) = face_normal(vertices, idx) {
+                let q = plane_quadric(&n, &vertices[idx[0] as usize], 1.0);
+                for vid in idx {
+                    quadrics[*vid as usize] += q;
+                }
+            }
+        }
+
+        let cos_sharp_angle = ComplexField::cos(params.sharp_angle);
+
+        for (edge, edge_faces) in &edges {
+            let is_feature = match edge_faces.len() {
+                1 => true,
+                2 => match (
+                    face_normal(vertices, &faces[edge_faces[0] as usize]),
+                    face_normal(vertices, &faces[edge_faces[1] as usize]),
+                ) {
+                    (Some(n1), Some(n2)) => n1.dot(&n2) < cos_sharp_angle,
+                    _ => false,
+                },
+                _ => {
+                    locked[edge[0] as usize] = true;
+                    locked[edge[1] as usize] = true;
+                    false
+                }
+            };
+
+            if is_feature {
+                // Penalize the motion of the edge’s vertices away from the planes orthogonal to
+                // its adjacent faces and containing the edge.
+                let a = vertices[edge[0] as usize];
+                let b = vertices[edge[1] as usize];
+
+                for fid in edge_faces {
+                    if let Some(n) = face_normal(vertices, &faces[*fid as usize]) {
+                        if let Some(dir) = (b - a).cross(&n).try_normalize(Real::EPSILON) {
+                            let q = plane_quadric(&dir, &a, params.feature_weight);
+                            quadrics[edge[0] as usize] += q;
+                            quadrics[edge[1] as usize] += q;
+                        }
+                    }
+                }
+            }
+        }
+
+        let tolerance = if vertices.is_empty() {
+            0.0
+        } else {
+            Aabb::from_points(vertices.iter().copied()).extents().norm() * 1.0e-5
+        };
+
+        let mut result = Self {
+            params,
+            vertices: vertices.to_vec(),
+            quadrics,
+            stamps: alloc::vec![0; vertices.len()],
+            locked,
+            removed_vertices: alloc::vec![false; vertices.len()],
+            num_faces: faces.len(),
+            removed_faces: alloc::vec![false; faces.len()],
+            faces,
+            incident_faces,
+            tolerance,
+            queue: BinaryHeap::new(),
+        };
+
+        // NOTE: sort the edges so the result doesn’t depend on the hashmap’s iteration order.
+        let mut sorted_edges: Vec<_> = edges.into_keys().collect();
+        sorted_edges.sort_unstable();
+
+        for edge in sorted_edges {
+            result.push_edge(edge[0], edge[1]);
+        }
+
+        result
+    }
+
+    fn run(&mut self) {
+        let max_cost = if self.params.max_error == Real::MAX {
+            Real::MAX
+        } else {
+            self.params.max_error * self.params.max_error
+        };
+
+        while self.num_faces > self.params.target_triangle_count {
+            let Some(Reverse(collapse)) = self.queue.pop() else {
+                break;
+            };
+
+            if collapse.cost.0 > max_cost {
+                break;
+            }
+
+            let [a, b] = collapse.vertices;
+
+            if self.removed_vertices[a as usize]
+                || self.removed_vertices[b as usize]
+                || self.stamps[a as usize] != collapse.stamps[0]
+                || self.stamps[b as usize] != collapse.stamps[1]
+            {
+                continue;
+            }
+
+            // The neighbors of `a` and `b` might have moved since this collapse was computed,
+            // invalidating its target for the bound constraints.
+            if self.enforce_bound(a, b, collapse.target) != Some(collapse.target) {
+                self.push_edge(a, b);
+                continue;
+            }
+
+            if !self.can_collapse(a, b, &collapse.target) {
+                continue;
+            }
+
+            self.collapse(a, b, collapse.target);
+        }
+    }
+
+    fn output(&self) -> (Vec<Point<Real>>, Vec<[u32; 3]>) {
+        let mut remap = alloc::vec![u32::MAX; self.vertices.len()];
+        let mut vertices = Vec::new();
+        let mut indices = Vec::with_capacity(self.num_faces);
+
+        for (face, removed) in self.faces.iter().zip(&self.removed_faces) {
+            if *removed {
+                continue;
+            }
+
+            indices.push(face.map(|vid| {
+                if remap[vid as usize] == u32::MAX {
+                    remap[vid as usize] = vertices.len() as u32;
+                    vertices.push(self.vertices[vid as usize]);
+                }
+                remap[vid as usize]
+            }));
+        }
+
+        (vertices, indices)
+    }
+
+    fn active_faces(&self, vid: u32) -> impl Iterator<Item = u32> + '_ {
+        self.incident_faces[vid as usize]
+            .iter()
+            .copied()
+            .filter(|fid| !self.removed_faces[*fid as usize])
+    }
+
+    fn neighbors(&self, vid: u32) -> SmallVec<[u32; 16]> {
+        let mut result = SmallVec::new();
+
+        for fid in self.active_faces(vid) {
+            for other in self.faces[fid as usize] {
+                if other != vid && !result.contains(&other) {
+                    result.push(other);
+                }
+            }
+        }
+
+        result
+    }
+
+    fn num_edge_faces(&self, a: u32, b: u32) -> usize {
+        self.active_faces(a)
+            .filter(|fid| self.faces[*fid as usize].contains(&b))
+            .count()
+    }
+
+    fn is_boundary_vertex(&self, vid: u32) -> bool {
+        self.neighbors(vid)
+            .iter()
+            .any(|n| self.num_edge_faces(vid, *n) == 1)
+    }
+
+    fn push_edge(&mut self, a: u32, b: u32) {
+        if self.locked[a as usize] || self.locked[b as usize] {
+            return;
+        }
+
+        let quadric = self.quadrics[a as usize] + self.quadrics[b as usize];
+        let pa = self.vertices[a as usize];
+        let pb = self.vertices[b as usize];
+        let midpoint = na::center(&pa, &pb);
+        let mut best: Option<(Real, Point<Real>)> = None;
+
+        for candidate in [quadric_optimum(&quadric, &midpoint), pa, pb, midpoint] {
+            let Some(candidate) = self.enforce_bound(a, b, candidate) else {
+                continue;
+            };
+            let cost = quadric_error(&quadric, &candidate).max(0.0);
+
+            if best.map(|(best_cost, _)| cost < best_cost).unwrap_or(true) {
+                best = Some((cost, candidate));
+            }
+        }
+
+        if let Some((cost, target)) = best {
+            self.queue.push(Reverse(EdgeCollapse {
+                cost: OrderedFloat(cost),
+                vertices: [a, b],
+                stamps: [self.stamps[a as usize], self.stamps[b as usize]],
+                target,
+            }));
+        }
+    }
+
+    /// Moves `point` to the required side of the faces adjacent to `a` and `b`, if
+    /// `self.params.bound` is set.
+    ///
+    /// This performs successive projections on the most violated face plane. Returns `None` if
+    /// no valid point was found.
+    fn enforce_bound(&self, a: u32, b: u32, mut point: Point<Real>) -> Option<Point<Real>> {
+        const MAX_ITERATIONS: usize = 16;
+
+        let sign = match self.params.bound {
+            MeshSimplificationBound::None => return Some(point),
+            MeshSimplificationBound::Inside => -1.0,
+            MeshSimplificationBound::Outside => 1.0,
+        };
+
+        for _ in 0..MAX_ITERATIONS {
+            let mut worst: Option<(Real, Vector<Real>)> = None;
+
+            for fid in self.active_faces(a).chain(self.active_faces(b)) {
+                let face = &self.faces[fid as usize];
+                if let Some(n) = face_normal(&self.vertices, face) {
+                    let dist = sign * n.dot(&(point - self.vertices[face[0] as usize]));
+                    if worst
+                        .map(|(worst_dist, _)| dist < worst_dist)
+                        .unwrap_or(true)
+                    {
+                        worst = Some((dist, n * sign));
+                    }
+                }
+            }
+
+            match worst {
+                Some((dist, dir)) if dist < -self.tolerance => {
+                    // Project slightly beyond the plane to avoid oscillations.
+                    point -= dir * (dist - self.tolerance * 0.5);
+                }
+                _ => return Some(point),
+            }
+        }
+
+        None
+    }
+
+    fn can_collapse(&self, a: u32, b: u32, target: &Point<Real>) -> bool {
+        const MIN_NORMAL_COS: Real = 0.2;
+
+        let mut opposite: SmallVec<[u32; 2]> = SmallVec::new();
+        for fid in self.active_faces(a) {
+            let face = &self.faces[fid as usize];
+            if face.contains(&b) {
+                opposite.extend(face.iter().copied().filter(|v| *v != a && *v != b));
+            }
+        }
+
+        // The edge no longer exists, or is non-manifold.
+        if opposite.is_empty() || opposite.len() > 2 {
+            return false;
+        }
+
+        // An interior edge between two boundary vertices would pinch the mesh.
+        if opposite.len() == 2 && self.is_boundary_vertex(a) && self.is_boundary_vertex(b) {
+            return false;
+        }
+
+        // Link condition: the only vertices adjacent to both `a` and `b` must be the vertices
+        // opposite to the edge. Otherwise, the collapse would change the topology of the mesh.
+        let neighbors_b = self.neighbors(b);
+        if self
+            .neighbors(a)
+            .iter()
+            .any(|n| neighbors_b.contains(n) && !opposite.contains(n))
+        {
+            return false;
+        }
+
+        let mut new_faces: SmallVec<[[u32; 3]; 16]> = SmallVec::new();
+
+        for fid in self.active_faces(a).chain(self.active_faces(b)) {
+            let face = self.faces[fid as usize];
+            if face.contains(&a) && face.contains(&b) {
+                continue;
+            }
+
+            let Some(old_normal) = face_normal(&self.vertices, &face) else {
+                continue;
+            };
+            let new_pts = face.map(|vid| {
+                if vid == a || vid == b {
+                    *target
+                } else {
+                    self.vertices[vid as usize]
+                }
+            });
+            let new_normal = (new_pts[1] - new_pts[0]).cross(&(new_pts[2] - new_pts[0]));
+
+            // Reject collapses flipping or degenerating a face.
+            match new_normal.try_normalize(Real::EPSILON) {
+                Some(new_normal) if new_normal.dot(&old_normal) >= MIN_NORMAL_COS => {}
+                _ => return false,
+            }
+
+            // Reject collapses creating two faces with the same vertices (e.g. when collapsing
+            // an edge of a tetrahedron).
+            let mut new_face = face.map(|vid| if vid == a { b } else { vid });
+            new_face.sort_unstable();
+            if new_faces.contains(&new_face) {
+                return false;
+            }
+            new_faces.push(new_face);
+        }
+
+        // Reject collapses removing the last faces of a connected component.
+        !new_faces.is_empty()
+    }
+
+    fn collapse(&mut self, a: u32, b: u32, target: Point<Real>) {
+        let faces_a = core::mem::take(&mut self.incident_faces[a as usize]);
+
+        for fid in faces_a {
+            if self.removed_faces[fid as usize] {
+                continue;
+            }
+
+            let face = &mut self.faces[fid as usize];
+            if face.contains(&b) {
+                self.removed_faces[fid as usize] = true;
+                self.num_faces -= 1;
+            } else {
+                face.iter_mut()
+                    .filter(|vid| **vid == a)
+                    .for_each(|vid| *vid = b);
+                self.incident_faces[b as usize].push(fid);
+            }
+        }
+
+        let removed_faces = &self.removed_faces;
+        self.incident_faces[b as usize].retain(|fid| !removed_faces[*fid as usize]);
+        self.removed_vertices[a as usize] = true;
+        self.vertices[b as usize] = target;
+        let quadric_a = self.quadrics[a as usize];
+        self.quadrics[b as usize] += quadric_a;
+        self.stamps[a as usize] += 1;
+        self.stamps[b as usize] += 1;
+
+        for n in self.neighbors(b) {
+            self.push_edge(b, n);
+        }
+    }
+}
+
+fn sorted_edge(a: u32, b: u32) -> [u32; 2] {
+    if a < b {
+        [a, b]
+    } else {
+        [b, a]
+    }
+}
+
+/// The unit normal of a triangle, or `None` if it is degenerate.
+fn face_normal(vertices: &[Point<Real>], face: &[u32; 3]) -> Option<Vector<Real>> {
+    let a = vertices[face[0] as usize];
+    let b = vertices[face[1] as usize];
+    let c = vertices[face[2] as usize];
+    (b - a).cross(&(c - a)).try_normalize(Real::EPSILON)
+}
+
+/// The quadric measuring the squared distance to the plane with normal `n` passing through
+/// `point`, scaled by `weight`.
+fn plane_quadric(n: &Vector<Real>, point: &Point<Real>, weight: Real) -> Matrix4<Real> {
+    let plane = Vector4::new(n.x, n.y, n.z, -n.dot(&point.coords));
+    plane * plane.transpose() * weight
+}
+
+fn quadric_error(quadric: &Matrix4<Real>, point: &Point<Real>) -> Real {
+    let pt = point.to_homogeneous();
+    pt.dot(&(quadric * pt))
+}
+
+/// The point minimizing the error of `quadric`.
+///
+/// If the minimum isn’t unique (e.g. for flat regions), this returns the minimum closest to
+/// `reference`.
+fn quadric_optimum(quadric: &Matrix4<Real>, reference: &Point<Real>) -> Point<Real> {
+    let a = quadric.fixed_view::<3, 3>(0, 0).into_owned();
+    let b = -quadric.fixed_view::<3, 1>(0, 3).into_owned();
+    let svd = a.svd(true, true);
+    let eps = svd.singular_values.max() * 1.0e-3;
+
+    match svd.solve(&(b - a * reference.coords), eps) {
+        Ok(delta) => reference + delta,
+        Err(_) => *reference,
+    }
+}
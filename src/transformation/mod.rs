@@ -7,30 +7,73 @@ pub use self::convex_hull2::{convex_hull2 as convex_hull, convex_hull2_idx as co
 #[cfg(all(feature = "dim3", feature = "std"))]
 pub use self::convex_hull3::check_convex_hull;
 #[cfg(feature = "dim3")]
-pub use self::convex_hull3::{convex_hull, try_convex_hull, ConvexHullError};
+pub use self::convex_hull3::{
+    convex_hull, try_convex_hull, ConvexHullError, ConvexHullUpdate, IncrementalConvexHull,
+};
+pub use self::convex_hull_simplification::{
+    simplify_convex_hull, ConvexHullSimplificationBound, ConvexHullSimplificationParameters,
+};
+#[cfg(feature = "dim3")]
+pub(crate) use self::convex_polyhedra_intersection::convex_polyhedron_from_points;
+#[cfg(feature = "dim3")]
+pub use self::convex_polyhedra_intersection::{
+    convex_polyhedra_intersection, convex_polyhedron_halfspace_intersection, cuboids_intersection,
+};
 #[cfg(all(feature = "dim3", feature = "spade"))]
 pub use self::mesh_intersection::{
-    intersect_meshes, intersect_meshes_with_tolerances, MeshIntersectionError,
-    MeshIntersectionTolerances,
+    intersect_meshes, intersect_meshes_with_tolerances, intersecting_triangle_pairs, mesh_boolean,
+    mesh_boolean_with_tolerances, self_intersecting_triangle_pairs, MeshBoolean, MeshBooleanOp,
+    MeshIntersectionError, MeshIntersectionTolerances, TriangleProvenance,
+};
+#[cfg(feature = "dim3")]
+pub use self::mesh_repair::{repair_trimesh, MeshRepairParameters, MeshRepairReport};
+#[cfg(feature = "dim3")]
+pub use self::mesh_simplification::{
+    simplify_trimesh, MeshSimplificationBound, MeshSimplificationParameters,
+};
+#[cfg(feature = "dim2")]
+pub use self::polygon_boolean::{
+    polygon_boolean, polygon_boolean_with_tolerances, MultiPolygon, PolygonBooleanOp,
 };
 pub use self::polygon_intersection::{
     convex_polygons_intersection, convex_polygons_intersection_points,
     convex_polygons_intersection_points_with_tolerances,
     convex_polygons_intersection_with_tolerances, polygons_intersection,
-    polygons_intersection_points,
+    polygons_intersection_points, PolygonIntersectionTolerances,
 };
+#[cfg(feature = "dim2")]
+pub use self::polyline_offset::{offset_polyline, OffsetJoin};
+#[cfg(feature = "dim3")]
+pub use self::trimesh_offset::{offset_trimesh, solidify_trimesh};
 
+/// Exact convex decomposition by recursive plane cuts of a triangle mesh.
+#[cfg(all(feature = "dim3", feature = "spade"))]
+pub mod coacd;
 mod convex_hull2;
 #[cfg(feature = "dim3")]
 mod convex_hull3;
+mod convex_hull_simplification;
 pub(crate) mod convex_hull_utils;
+#[cfg(feature = "dim3")]
+mod convex_polyhedra_intersection;
 
+#[cfg(feature = "dim2")]
+mod polygon_boolean;
 mod polygon_intersection;
+#[cfg(feature = "dim2")]
+mod polyline_offset;
 /// Approximate convex decomposition using the VHACD algorithm.
 pub mod vhacd;
 /// Voxelization of a 2D polyline or 3D triangle mesh.
 pub mod voxelization;
 
+#[cfg(all(feature = "dim2", feature = "spade"))]
+mod constrained_delaunay;
+#[cfg(all(feature = "dim2", feature = "spade"))]
+pub use constrained_delaunay::{
+    constrained_delaunay_triangulation, ConstrainedTriangulationError,
+    ConstrainedTriangulationParameters,
+};
 #[cfg(feature = "dim2")]
 pub(crate) mod ear_clipping;
 #[cfg(feature = "dim2")]
@@ -40,10 +83,16 @@ pub use hertel_mehlhorn::{hertel_mehlhorn, hertel_mehlhorn_idx};
 #[cfg(all(feature = "dim3", feature = "spade"))]
 mod mesh_intersection;
 #[cfg(feature = "dim3")]
+mod mesh_repair;
+#[cfg(feature = "dim3")]
+mod mesh_simplification;
+#[cfg(feature = "dim3")]
 mod to_outline;
 #[cfg(feature = "dim2")]
 mod to_polyline;
 mod to_trimesh;
+#[cfg(feature = "dim3")]
+mod trimesh_offset;
 pub mod utils;
 
 #[cfg(feature = "wavefront")]
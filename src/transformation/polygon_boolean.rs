@@ -0,0 +1,557 @@
+//! Boolean operations between 2D polygons with holes.
+
+use super::PolygonIntersectionTolerances;
+use crate::bounding_volume::Aabb;
+use crate::math::Real;
+use crate::shape::{Polyline, SegmentPointLocation, Triangle, TriangleOrientation};
+use crate::utils::hashmap::HashMap;
+use crate::utils::{self, SegmentsIntersection};
+use alloc::{vec, vec::Vec};
+use na::{ComplexField, Point2, RealField, Vector2};
+use ordered_float::OrderedFloat;
+
+#[cfg(feature = "spade")]
+use super::{
+    constrained_delaunay_triangulation, ConstrainedTriangulationError,
+    ConstrainedTriangulationParameters,
+};
+#[cfg(feature = "spade")]
+use crate::shape::TriMesh;
+
+/// A boolean operation between two sets of polygons.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum PolygonBooleanOp {
+    /// The area covered by at least one of the polygons.
+    Union,
+    /// The area covered by the first polygon but not by the second.
+    Difference,
+    /// The area covered by both polygons.
+    Intersection,
+    /// The area covered by exactly one of the polygons.
+    Xor,
+}
+
+impl PolygonBooleanOp {
+    fn contains(self, inside1: bool, inside2: bool) -> bool {
+        match self {
+            Self::Union => inside1 || inside2,
+            Self::Difference => inside1 && !inside2,
+            Self::Intersection => inside1 && inside2,
+            Self::Xor => inside1 != inside2,
+        }
+    }
+}
+
+/// A set of polygons with holes, described by closed rings of vertices.
+///
+/// A point is inside of the polygons if it is inside of an odd number of rings, so holes are
+/// rings nested inside of other rings, independently from their orientation. The rings computed
+/// by [`polygon_boolean`] don’t intersect each other, the outer boundaries being oriented
+/// counterclockwise and the boundaries of the holes clockwise.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MultiPolygon {
+    rings: Vec<Vec<Point2<Real>>>,
+}
+
+impl MultiPolygon {
+    /// Creates a set of polygons from its rings.
+    ///
+    /// Each ring is implicitly closed, i.e., its last vertex is connected to its first one.
+    pub fn new(rings: Vec<Vec<Point2<Real>>>) -> Self {
+        Self { rings }
+    }
+
+    /// The rings delimiting the polygons.
+    pub fn rings(&self) -> &[Vec<Point2<Real>>] {
+        &self.rings
+    }
+
+    /// Returns `true` if there is no ring.
+    pub fn is_empty(&self) -> bool {
+        self.rings.is_empty()
+    }
+
+    /// The sum of the signed areas of the rings.
+    ///
+    /// This is the area of the polygons if the rings are oriented like the rings computed by
+    /// [`polygon_boolean`].
+    pub fn area(&self) -> Real {
+        self.rings.iter().map(|ring| signed_area(ring)).sum()
+    }
+
+    /// Tests if the given point is inside of the polygons.
+    pub fn contains_point(&self, pt: &Point2<Real>) -> bool {
+        self.rings
+            .iter()
+            .filter(|ring| utils::point_in_poly2d(pt, ring))
+            .count()
+            % 2
+            == 1
+    }
+
+    /// The outline of the polygons, made of one closed loop per ring.
+    pub fn to_polyline(&self) -> Polyline {
+        let mut vertices = Vec::new();
+        let mut indices = Vec::new();
+
+        for ring in &self.rings {
+            let base = vertices.len() as u32;
+            let n = ring.len() as u32;
+            vertices.extend_from_slice(ring);
+            indices.extend((0..n).map(|i| [base + i, base + (i + 1) % n]));
+        }
+
+        Polyline::new(vertices, Some(indices))
+    }
+
+    /// Triangulates the polygons with [`constrained_delaunay_triangulation`].
+    #[cfg(feature = "spade")]
+    pub fn to_trimesh(
+        &self,
+        params: &ConstrainedTriangulationParameters,
+    ) -> Result<TriMesh, ConstrainedTriangulationError> {
+        let polyline = self.to_polyline();
+        constrained_delaunay_triangulation(polyline.vertices(), polyline.indices(), params)
+    }
+}
+
+/// Computes a boolean operation between two sets of polygons with holes.
+///
+/// This is the same as [`polygon_boolean_with_tolerances`] with the tolerances set to their
+/// default values.
+pub fn polygon_boolean(
+    op: PolygonBooleanOp,
+    poly1: &MultiPolygon,
+    poly2: &MultiPolygon,
+) -> MultiPolygon {
+    polygon_boolean_with_tolerances(op, poly1, poly2, PolygonIntersectionTolerances::default())
+}
+
+/// Computes a boolean operation between two sets of polygons with holes.
+///
+/// The input rings may intersect each other or themselves, and may have overlapping collinear
+/// edges. The boundaries of both inputs are split at their intersections, then the resulting
+/// edges separating the inside of the result from its outside are chained into non-intersecting
+/// rings.
+///
+/// The tolerances are relative to the size of the inputs, so the result doesn’t depend on the
+/// scale of their coordinates. With `d` the diagonal of the bounding box of both inputs, edges
+/// considered collinear by [`Triangle::orientation2d`] with an epsilon of
+/// `tolerances.collinearity_epsilon * d²` are treated as overlapping, vertices closer than
+/// `tolerances.collinearity_epsilon * d` are merged, and the collinear vertices of the resulting
+/// rings are removed.
+pub fn polygon_boolean_with_tolerances(
+    op: PolygonBooleanOp,
+    poly1: &MultiPolygon,
+    poly2: &MultiPolygon,
+    tolerances: PolygonIntersectionTolerances,
+) -> MultiPolygon {
+    let mut aabb = Aabb::new_invalid();
+    poly1
+        .rings
+        .iter()
+        .chain(&poly2.rings)
+        .flatten()
+        .for_each(|pt| aabb.take_point(*pt));
+    let diagonal = if aabb.mins.x <= aabb.maxs.x {
+        aabb.extents().norm()
+    } else {
+        0.0
+    };
+
+    let merge_distance = tolerances.collinearity_epsilon * diagonal;
+    let eps = merge_distance * diagonal;
+    let mut graph = OverlayGraph::new(eps, merge_distance);
+    graph.insert_boundaries([&poly1.rings, &poly2.rings]);
+
+    let edges =
+        graph.result_edges(|windings| op.contains(windings[0] % 2 != 0, windings[1] % 2 != 0));
+    let rings = graph
+        .chain_rings(&edges)
+        .into_iter()
+        .filter_map(|ring| simplify_ring(ring, eps))
+        .collect();
+
+    MultiPolygon { rings }
+}
+
+/// The planar graph formed by the boundaries of two sets of closed rings, split at their
+/// intersections.
+pub(super) struct OverlayGraph {
+    // The epsilon for collinearity tests, on the doubled areas of triangles.
+    eps: Real,
+    // The distance below which vertices are merged.
+    merge_distance: Real,
+    vertices: Vec<Point2<Real>>,
+    // The vertices, bucketed on a grid with cells of size `merge_distance` to merge nearby
+    // vertices.
+    grid: HashMap<[i64; 2], Vec<u32>>,
+    // For each edge, the number of times it is traversed by the rings of each input, counted
+    // positively from its smallest vertex index to its largest.
+    edges: HashMap<[u32; 2], [i32; 2]>,
+}
+
+impl OverlayGraph {
+    pub(super) fn new(eps: Real, merge_distance: Real) -> Self {
+        Self {
+            eps,
+            merge_distance,
+            vertices: Vec::new(),
+            grid: HashMap::default(),
+            edges: HashMap::default(),
+        }
+    }
+
+    fn grid_key(&self, pt: &Point2<Real>) -> [i64; 2] {
+        let cell = self.merge_distance.max(Real::MIN_POSITIVE);
+        [
+            ComplexField::floor(pt.x / cell) as i64,
+            ComplexField::floor(pt.y / cell) as i64,
+        ]
+    }
+
+    /// Returns the index of the vertex at the given point, merging it with any vertex closer
+    /// than `merge_distance`.
+    fn vertex(&mut self, pt: Point2<Real>) -> u32 {
+        let [kx, ky] = self.grid_key(&pt);
+
+        for i in kx.saturating_sub(1)..=kx.saturating_add(1) {
+            for j in ky.saturating_sub(1)..=ky.saturating_add(1) {
+                if let Some(bucket) = self.grid.get(&[i, j]) {
+                    for vid in bucket {
+                        if na::distance(&self.vertices[*vid as usize], &pt) <= self.merge_distance {
+                            return *vid;
+                        }
+                    }
+                }
+            }
+        }
+
+        let vid = self.vertices.len() as u32;
+        self.vertices.push(pt);
+        self.grid.entry([kx, ky]).or_default().push(vid);
+        vid
+    }
+
+    pub(super) fn insert_boundaries(&mut self, polys: [&[Vec<Point2<Real>>]; 2]) {
+        let mut segments = Vec::new();
+
+        for (poly_id, rings) in polys.into_iter().enumerate() {
+            for ring in rings {
+                for (i, a) in ring.iter().enumerate() {
+                    let b = ring[(i + 1) % ring.len()];
+
+                    if *a != b {
+                        segments.push(([*a, b], poly_id));
+                    }
+                }
+            }
+        }
+
+        // Find the points where each segment must be split. The segments are swept along the
+        // x axis, so each segment is only tested against the active segments overlapping its
+        // range of x coordinates.
+        let mut splits = vec![Vec::new(); segments.len()];
+        let x_range = |[a, b]: [Point2<Real>; 2]| (a.x.min(b.x), a.x.max(b.x));
+        let mut order: Vec<_> = (0..segments.len()).collect();
+        order.sort_by_key(|i| OrderedFloat(x_range(segments[*i].0).0));
+        let mut active: Vec<usize> = Vec::new();
+
+        for i in order {
+            let seg = segments[i].0;
+            let (min_x, _) = x_range(seg);
+            let (min_y, max_y) = (seg[0].y.min(seg[1].y), seg[0].y.max(seg[1].y));
+            active.retain(|j| x_range(segments[*j].0).1 + self.merge_distance >= min_x);
+
+            for j in &active {
+                let other = segments[*j].0;
+
+                if other[0].y.max(other[1].y) + self.merge_distance >= min_y
+                    && other[0].y.min(other[1].y) - self.merge_distance <= max_y
+                {
+                    let (split1, split2) = self.segment_splits(seg, other);
+                    splits[i].extend(split1);
+                    splits[*j].extend(split2);
+                }
+            }
+
+            active.push(i);
+        }
+
+        for (([a, b], poly_id), split) in segments.into_iter().zip(splits) {
+            let dir = b - a;
+            let mut vids: Vec<_> = [a, b]
+                .into_iter()
+                .chain(split)
+                .map(|pt| self.vertex(pt))
+                .collect();
+            vids.sort_by_key(|vid| OrderedFloat((self.vertices[*vid as usize] - a).dot(&dir)));
+            vids.dedup();
+
+            for w in vids.windows(2) {
+                let (key, sign) = if w[0] < w[1] {
+                    ([w[0], w[1]], 1)
+                } else {
+                    ([w[1], w[0]], -1)
+                };
+                self.edges.entry(key).or_default()[poly_id] += sign;
+            }
+        }
+
+        self.edges.retain(|_, count| *count != [0; 2]);
+    }
+
+    /// The points where each of the segments `[a, b]` and `[c, d]` must be split so they only
+    /// intersect at their endpoints.
+    fn segment_splits(
+        &self,
+        [a, b]: [Point2<Real>; 2],
+        [c, d]: [Point2<Real>; 2],
+    ) -> (Vec<Point2<Real>>, Vec<Point2<Real>>) {
+        // Split each segment where an endpoint of the other lies on it. This also covers
+        // overlapping collinear segments, even if their endpoints don’t match exactly.
+        let mut split1: Vec<_> = [c, d]
+            .into_iter()
+            .filter(|pt| point_on_segment(&a, &b, pt, self.merge_distance))
+            .collect();
+        let mut split2: Vec<_> = [a, b]
+            .into_iter()
+            .filter(|pt| point_on_segment(&c, &d, pt, self.merge_distance))
+            .collect();
+
+        if let Some(SegmentsIntersection::Point {
+            loc1: SegmentPointLocation::OnEdge(bcoords),
+            loc2: SegmentPointLocation::OnEdge(_),
+        }) = utils::segments_intersection2d(&a, &b, &c, &d, self.eps)
+        {
+            let pt = a * bcoords[0] + b.coords * bcoords[1];
+            split1.push(pt);
+            split2.push(pt);
+        }
+
+        (split1, split2)
+    }
+
+    /// The edges on the boundary of the result, oriented such that the result is on their left.
+    ///
+    /// A point is inside of the result if `inside` returns `true` for the winding numbers of the
+    /// rings of each input around it.
+    pub(super) fn result_edges(&self, inside: impl Fn([i32; 2]) -> bool) -> Vec<[u32; 2]> {
+        let mut edges: Vec<_> = self.edges.iter().map(|(e, c)| (*e, *c)).collect();
+        // Sort the edges for the output to not depend on the hashmap iteration order.
+        edges.sort_by_key(|(e, _)| *e);
+
+        // Cast a ray from the middle of each edge toward its left side, i.e., upward if the
+        // edge is closer to horizontal than to vertical, and toward -x otherwise so the ray is
+        // never nearly parallel to the edge. The boundary edges it crosses give the winding
+        // numbers of each input on the left side of the edge.
+        let is_steep = |[a, b]: [u32; 2]| {
+            let dir = self.vertices[b as usize] - self.vertices[a as usize];
+            dir.x.abs() < dir.y.abs()
+        };
+        let (steep, other): (Vec<_>, Vec<_>) =
+            (0..edges.len()).partition(|i| is_steep(edges[*i].0));
+        let mut left = vec![[0; 2]; edges.len()];
+        self.crossing_windings(&edges, &other, 0, true, &mut left);
+        self.crossing_windings(&edges, &steep, 1, false, &mut left);
+
+        let mut result = Vec::new();
+
+        for (([a, b], count), left) in edges.iter().zip(left) {
+            let (pa, pb) = (self.vertices[*a as usize], self.vertices[*b as usize]);
+            // Orient the edge from left to right, or upward if it is steep. Crossing it from
+            // its left side to its right side removes its traversals along that orientation.
+            let axis = if is_steep([*a, *b]) { 1 } else { 0 };
+            let (dir, sign) = if pa[axis] < pb[axis] {
+                ([*a, *b], 1)
+            } else {
+                ([*b, *a], -1)
+            };
+
+            let right = [left[0] - sign * count[0], left[1] - sign * count[1]];
+            let inside_left = inside(left);
+            let inside_right = inside(right);
+
+            if inside_left && !inside_right {
+                result.push(dir);
+            } else if !inside_left && inside_right {
+                result.push([dir[1], dir[0]]);
+            }
+        }
+
+        result
+    }
+
+    /// Computes, for each of the `queries` edges, the winding numbers of the rings of each input
+    /// around the middle of the edge, from the edges crossed by a ray cast along the axis
+    /// orthogonal to `axis`, toward increasing coordinates if `positive` is `true`, and
+    /// decreasing otherwise.
+    ///
+    /// The edges are swept along `axis`, so each ray is only tested against the edges spanning
+    /// the coordinate of its origin along `axis`.
+    fn crossing_windings(
+        &self,
+        edges: &[([u32; 2], [i32; 2])],
+        queries: &[usize],
+        axis: usize,
+        positive: bool,
+        windings: &mut [[i32; 2]],
+    ) {
+        let other_axis = 1 - axis;
+        let mut ray = Vector2::zeros();
+        ray[other_axis] = if positive { 1.0 } else { -1.0 };
+        let endpoints = |i: usize| {
+            let [a, b] = edges[i].0;
+            let (pa, pb) = (self.vertices[a as usize], self.vertices[b as usize]);
+            if pa[axis] < pb[axis] {
+                (pa, pb)
+            } else {
+                (pb, pa)
+            }
+        };
+        let origin = |i: usize| {
+            let (l, r) = endpoints(i);
+            na::center(&l, &r)
+        };
+
+        let mut queries = queries.to_vec();
+        queries.sort_by_key(|i| OrderedFloat(origin(*i)[axis]));
+        // The edges that aren’t orthogonal to the sweep axis, sorted by their lowest coordinate.
+        let mut candidates: Vec<_> = (0..edges.len())
+            .filter(|i| {
+                let (l, r) = endpoints(*i);
+                l[axis] < r[axis]
+            })
+            .collect();
+        candidates.sort_by_key(|i| OrderedFloat(endpoints(*i).0[axis]));
+        let mut candidates = candidates.into_iter().peekable();
+        let mut active: Vec<usize> = Vec::new();
+
+        for query in queries {
+            let mid = origin(query);
+
+            while let Some(i) = candidates.next_if(|i| endpoints(*i).0[axis] <= mid[axis]) {
+                active.push(i);
+            }
+            active.retain(|i| endpoints(*i).1[axis] > mid[axis]);
+
+            for i in &active {
+                if *i == query {
+                    continue;
+                }
+
+                let (l, r) = endpoints(*i);
+                let t = (mid[axis] - l[axis]) / (r[axis] - l[axis]);
+                let hit = l[other_axis] + (r[other_axis] - l[other_axis]) * t;
+
+                if (positive && hit > mid[other_axis]) || (!positive && hit < mid[other_axis]) {
+                    // The rings crossing the ray counterclockwise around its origin wind
+                    // positively around it.
+                    let [a, b] = edges[*i].0;
+                    let dir = self.vertices[b as usize] - self.vertices[a as usize];
+                    let sign = if ray.perp(&dir) > 0.0 { 1 } else { -1 };
+                    let count = edges[*i].1;
+                    windings[query][0] += sign * count[0];
+                    windings[query][1] += sign * count[1];
+                }
+            }
+        }
+    }
+
+    /// Chains the oriented edges into closed rings.
+    pub(super) fn chain_rings(&self, edges: &[[u32; 2]]) -> Vec<Vec<Point2<Real>>> {
+        let mut outgoing = vec![Vec::new(); self.vertices.len()];
+
+        for (eid, [a, _]) in edges.iter().enumerate() {
+            outgoing[*a as usize].push(eid);
+        }
+
+        let mut visited = vec![false; edges.len()];
+        let mut rings = Vec::new();
+
+        for start in 0..edges.len() {
+            if visited[start] {
+                continue;
+            }
+
+            let mut ring = Vec::new();
+            let mut curr = start;
+
+            loop {
+                visited[curr] = true;
+                let [a, b] = edges[curr];
+                ring.push(self.vertices[a as usize]);
+
+                // Where several boundary edges leave the same vertex, follow the sharpest turn
+                // to the left so the rings touching at that vertex are kept separate.
+                let back = self.vertices[a as usize] - self.vertices[b as usize];
+                let next = outgoing[b as usize]
+                    .iter()
+                    .copied()
+                    .filter(|eid| *eid == start || !visited[*eid])
+                    .min_by_key(|eid| {
+                        let dir =
+                            self.vertices[edges[*eid][1] as usize] - self.vertices[b as usize];
+                        // The clockwise angle from `back` to `dir`, in ]0, 2π].
+                        let angle = RealField::atan2(dir.perp(&back), dir.dot(&back));
+                        OrderedFloat(if angle <= 0.0 {
+                            angle + Real::two_pi()
+                        } else {
+                            angle
+                        })
+                    });
+
+                match next {
+                    Some(next) if next == start => {
+                        rings.push(ring);
+                        break;
+                    }
+                    Some(next) => curr = next,
+                    None => {
+                        log::debug!("Polygon overlay: discarding an open boundary.");
+                        break;
+                    }
+                }
+            }
+        }
+
+        rings
+    }
+}
+
+/// Tests if `pt` is closer than `eps` to the segment `[a, b]`, away from its endpoints.
+fn point_on_segment(a: &Point2<Real>, b: &Point2<Real>, pt: &Point2<Real>, eps: Real) -> bool {
+    let dir = b - a;
+    let t = (pt - a).dot(&dir) / dir.norm_squared();
+    t > 0.0 && t < 1.0 && na::distance(&(a + dir * t), pt) <= eps
+}
+
+fn signed_area(ring: &[Point2<Real>]) -> Real {
+    (0..ring.len())
+        .map(|i| ring[i].coords.perp(&ring[(i + 1) % ring.len()].coords))
+        .sum::<Real>()
+        / 2.0
+}
+
+/// Removes the collinear vertices of a ring, and discards it if it is degenerate.
+pub(super) fn simplify_ring(mut ring: Vec<Point2<Real>>, eps: Real) -> Option<Vec<Point2<Real>>> {
+    let mut i = 0;
+    // The number of vertices checked since the last removal.
+    let mut num_kept = 0;
+
+    while ring.len() >= 3 && num_kept < ring.len() {
+        i %= ring.len();
+        let prev = ring[(i + ring.len() - 1) % ring.len()];
+        let next = ring[(i + 1) % ring.len()];
+
+        if Triangle::orientation2d(&prev, &ring[i], &next, eps) == TriangleOrientation::Degenerate {
+            let _ = ring.remove(i);
+            num_kept = 0;
+        } else {
+            i += 1;
+            num_kept += 1;
+        }
+    }
+
+    (ring.len() >= 3 && signed_area(&ring).abs() > eps).then_some(ring)
+}
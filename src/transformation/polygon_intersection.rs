@@ -8,6 +8,7 @@ use crate::shape::{SegmentPointLocation, Triangle, TriangleOrientation};
 use crate::utils::hashmap::HashMap;
 use crate::utils::{self, SegmentsIntersection};
 
+/// Tolerances for the intersections and boolean operations between 2D polygons.
 #[derive(Copy, Clone, PartialEq, Debug)]
 pub struct PolygonIntersectionTolerances {
     /// The epsilon given to [`Triangle::orientation2d`] for detecting if three points are collinear.
@@ -0,0 +1,181 @@
+//! Offsetting of the polygons delimited by a closed polyline.
+
+use super::polygon_boolean::{simplify_ring, OverlayGraph};
+use crate::math::{Point, Real, Vector};
+use crate::shape::Polyline;
+use alloc::vec::Vec;
+use na::{ComplexField, RealField};
+
+/// The way the offset edges are joined at the convex corners of an offset polygon.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum OffsetJoin {
+    /// The offset edges are extended until they meet.
+    ///
+    /// If the distance between the corner and the point where the edges meet exceeds `limit`
+    /// times the offset distance, the corner is beveled instead.
+    Miter {
+        /// The maximum ratio between the miter length and the offset distance.
+        limit: Real,
+    },
+    /// The offset edges are joined by a circular arc centered on the original corner.
+    Round {
+        /// The number of segments used to approximate a half circle.
+        subdivisions: u32,
+    },
+    /// The offset edges are joined by a straight segment.
+    Bevel,
+}
+
+impl Default for OffsetJoin {
+    fn default() -> Self {
+        OffsetJoin::Miter { limit: 2.0 }
+    }
+}
+
+/// Offsets the polygons delimited by a closed polyline by a signed distance.
+///
+/// The polyline must satisfy the same constraints as for
+/// [`Polyline::extract_connected_components`], with outer boundaries oriented
+/// counterclockwise and holes oriented clockwise. A positive `distance` grows the polygons
+/// while a negative `distance` shrinks them.
+///
+/// Self-intersections of the offset curves are resolved: parts of a polygon thinner than
+/// `2 * -distance` vanish, and polygons closer than `2 * distance` are merged. The result
+/// follows the same orientation convention as the input, and each of its connected components
+/// is a closed loop. Returns `None` if the offset polygons are empty.
+pub fn offset_polyline(polyline: &Polyline, distance: Real, join: OffsetJoin) -> Option<Polyline> {
+    let loops: Vec<Vec<Point<Real>>> = polyline
+        .extract_connected_components()
+        .iter()
+        .map(|component| {
+            let mut pts = component.vertices().to_vec();
+            pts.dedup();
+            while pts.len() > 1 && pts.first() == pts.last() {
+                let _ = pts.pop();
+            }
+            pts
+        })
+        .filter(|pts| pts.len() >= 3)
+        .collect();
+
+    if distance == 0.0 {
+        return loops_to_polyline(loops);
+    }
+
+    let raw_loops: Vec<_> = loops
+        .iter()
+        .map(|pts| raw_offset_loop(pts, distance, join))
+        .collect();
+
+    // Points closer than this are merged when resolving the self-intersections.
+    let scale = loops
+        .iter()
+        .flatten()
+        .map(|pt| pt.coords.amax())
+        .fold(distance.abs(), Real::max);
+    let tol = (distance.abs() * 1.0e-4).max(scale * Real::EPSILON * 16.0);
+    let result = positive_winding_boundary(&raw_loops, tol);
+
+    loops_to_polyline(result)
+}
+
+fn loops_to_polyline(loops: Vec<Vec<Point<Real>>>) -> Option<Polyline> {
+    let mut vertices = Vec::new();
+    let mut indices = Vec::new();
+
+    for pts in loops {
+        let base = vertices.len() as u32;
+        let len = pts.len() as u32;
+        indices.extend((0..len).map(|i| [base + i, base + (i + 1) % len]));
+        vertices.extend(pts);
+    }
+
+    (!indices.is_empty()).then(|| Polyline::new(vertices, Some(indices)))
+}
+
+/// The outward normal of an edge of a counterclockwise polygon.
+fn edge_normal(a: &Point<Real>, b: &Point<Real>) -> Vector<Real> {
+    let dir = b - a;
+    Vector::new(dir.y, -dir.x).normalize()
+}
+
+/// Offsets each edge of a closed loop, and joins consecutive edges, without resolving
+/// self-intersections.
+fn raw_offset_loop(pts: &[Point<Real>], distance: Real, join: OffsetJoin) -> Vec<Point<Real>> {
+    let n = pts.len();
+    let mut result = Vec::new();
+
+    for i in 0..n {
+        let prev = pts[(i + n - 1) % n];
+        let curr = pts[i];
+        let next = pts[(i + 1) % n];
+        let n1 = edge_normal(&prev, &curr);
+        let n2 = edge_normal(&curr, &next);
+        let turn = (curr - prev).perp(&(next - curr));
+
+        if n1.dot(&n2) >= 1.0 - Real::EPSILON {
+            // Collinear edges.
+            result.push(curr + n1 * distance);
+            continue;
+        }
+
+        if turn * distance < 0.0 {
+            // The offset edges overlap at this corner. Connecting them through the corner
+            // creates a small invalid loop which is removed afterward.
+            result.push(curr + n1 * distance);
+            result.push(curr);
+            result.push(curr + n2 * distance);
+            continue;
+        }
+
+        match join {
+            OffsetJoin::Miter { limit } => {
+                let cos = n1.dot(&n2);
+                let ratio = ComplexField::sqrt(2.0 / (1.0 + cos));
+
+                if ratio <= limit {
+                    result.push(curr + (n1 + n2) * (distance / (1.0 + cos)));
+                } else {
+                    result.push(curr + n1 * distance);
+                    result.push(curr + n2 * distance);
+                }
+            }
+            OffsetJoin::Round { subdivisions } => {
+                let step = Real::pi() / subdivisions.max(1) as Real;
+                let angle = RealField::atan2(n1.perp(&n2), n1.dot(&n2));
+                let num_steps = ComplexField::ceil(angle.abs() / step).max(1.0) as u32;
+
+                for k in 0..=num_steps {
+                    let rot = na::Rotation2::new(angle * k as Real / num_steps as Real);
+                    result.push(curr + rot * n1 * distance);
+                }
+            }
+            OffsetJoin::Bevel => {
+                result.push(curr + n1 * distance);
+                result.push(curr + n2 * distance);
+            }
+        }
+    }
+
+    result
+}
+
+/// Computes the boundary of the region where the winding number of a set of closed loops is
+/// positive.
+///
+/// The loops are split at their intersections and overlaps, points closer than `tol` being
+/// merged. Each resulting edge is kept if it separates a region with a positive winding number
+/// from a region with a non-positive winding number, and the kept edges are reconnected into
+/// closed loops.
+fn positive_winding_boundary(loops: &[Vec<Point<Real>>], tol: Real) -> Vec<Vec<Point<Real>>> {
+    let eps = tol * tol;
+    let mut graph = OverlayGraph::new(eps, tol);
+    graph.insert_boundaries([loops, &[]]);
+
+    let edges = graph.result_edges(|windings| windings[0] > 0);
+    graph
+        .chain_rings(&edges)
+        .into_iter()
+        .filter_map(|ring| simplify_ring(ring, eps))
+        .collect()
+}
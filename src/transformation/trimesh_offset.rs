@@ -0,0 +1,287 @@
+//! Offsetting of triangle meshes, and extrusion of open meshes into closed shells.
+
+use crate::bounding_volume::BoundingVolume;
+use crate::math::{Point, Real, Vector};
+use crate::query::PointQuery;
+use crate::shape::{TriMesh, TriMeshFlags, Triangle};
+use crate::utils::hashset::HashSet;
+use alloc::vec::Vec;
+use na::ComplexField;
+
+/// The maximum factor applied to the thickness of a shell at its vertices to compensate for the
+/// angle between the vertex normal and the normals of the adjacent triangles.
+const MAX_THICKNESS_SCALE: Real = 3.0;
+
+/// Computes a triangle mesh approximating the surface offset from a closed mesh by a signed
+/// distance.
+///
+/// A positive `distance` grows the volume enclosed by the mesh, rounding its convex edges and
+/// corners, while a negative `distance` shrinks it. Parts of the volume thinner than
+/// `2 * -distance` vanish, and parts closer than `2 * distance` are merged.
+///
+/// The offset surface is extracted from the signed distance field of the mesh sampled on a
+/// regular grid, with `resolution` cells along the largest dimension of its bounding box. The
+/// inside of the mesh is determined from its generalized winding number, so small holes or
+/// inconsistent orientations in the input are tolerated. The vertices of the result are
+/// projected onto the exact offset surface, and its triangles are oriented outward.
+///
+/// Returns `None` if the offset volume is empty, or if it is too thin to be captured by the
+/// grid.
+pub fn offset_trimesh(mesh: &TriMesh, distance: Real, resolution: u32) -> Option<TriMesh> {
+    let mesh_with_winding_numbers;
+    let mesh = if mesh.winding_numbers.is_some() {
+        mesh
+    } else {
+        let mut with_winding_numbers = mesh.clone();
+        let _ = with_winding_numbers.set_flags(mesh.flags() | TriMeshFlags::WINDING_NUMBERS);
+        mesh_with_winding_numbers = with_winding_numbers;
+        &mesh_with_winding_numbers
+    };
+
+    // The closest point on the mesh, and the signed distance to the offset surface.
+    let signed_distance = |pt: &Point<Real>| {
+        let proj = mesh.project_local_point(pt, false);
+        let dist = na::distance(pt, &proj.point);
+
+        if mesh.contains_local_point(pt) {
+            (proj.point, -dist - distance)
+        } else {
+            (proj.point, dist - distance)
+        }
+    };
+
+    // The grid must enclose the offset surface with a margin of a couple of cells.
+    let aabb = mesh.local_aabb().loosened(distance.max(0.0));
+    let cell_size = aabb.extents().max() / resolution.max(1) as Real;
+    let aabb = aabb.loosened(cell_size * 2.0);
+    let dims = (aabb.extents() / cell_size).map(|e| ComplexField::ceil(e) as usize);
+    let num_points = dims.map(|d| d + 1);
+    let point_id = |i: usize, j: usize, k: usize| i + num_points.x * (j + num_points.y * k);
+    let cell_id = |i: usize, j: usize, k: usize| i + dims.x * (j + dims.y * k);
+    let grid_point = |i: usize, j: usize, k: usize| {
+        aabb.mins + Vector::new(i as Real, j as Real, k as Real) * cell_size
+    };
+
+    let mut values = Vec::with_capacity(num_points.x * num_points.y * num_points.z);
+    for k in 0..num_points.z {
+        for j in 0..num_points.y {
+            for i in 0..num_points.x {
+                values.push(signed_distance(&grid_point(i, j, k)).1);
+            }
+        }
+    }
+
+    // Place one vertex in each cell crossed by the surface, at the average of the points where
+    // the surface crosses its edges (surface nets), then project it on the offset surface.
+    const CELL_EDGES: [([usize; 3], [usize; 3]); 12] = [
+        ([0, 0, 0], [1, 0, 0]),
+        ([0, 1, 0], [1, 1, 0]),
+        ([0, 0, 1], [1, 0, 1]),
+        ([0, 1, 1], [1, 1, 1]),
+        ([0, 0, 0], [0, 1, 0]),
+        ([1, 0, 0], [1, 1, 0]),
+        ([0, 0, 1], [0, 1, 1]),
+        ([1, 0, 1], [1, 1, 1]),
+        ([0, 0, 0], [0, 0, 1]),
+        ([1, 0, 0], [1, 0, 1]),
+        ([0, 1, 0], [0, 1, 1]),
+        ([1, 1, 0], [1, 1, 1]),
+    ];
+
+    let mut vertices = Vec::new();
+    let mut cell_vertex = alloc::vec![u32::MAX; dims.x * dims.y * dims.z];
+
+    for k in 0..dims.z {
+        for j in 0..dims.y {
+            for i in 0..dims.x {
+                let mut sum = Vector::zeros();
+                let mut num_crossings = 0;
+
+                for (a, b) in CELL_EDGES {
+                    let va = values[point_id(i + a[0], j + a[1], k + a[2])];
+                    let vb = values[point_id(i + b[0], j + b[1], k + b[2])];
+
+                    if (va < 0.0) != (vb < 0.0) {
+                        let pa = grid_point(i + a[0], j + a[1], k + a[2]);
+                        let pb = grid_point(i + b[0], j + b[1], k + b[2]);
+                        sum += pa.coords.lerp(&pb.coords, va / (va - vb));
+                        num_crossings += 1;
+                    }
+                }
+
+                if num_crossings > 0 {
+                    let pt = Point::from(sum / num_crossings as Real);
+                    cell_vertex[cell_id(i, j, k)] = vertices.len() as u32;
+                    vertices.push(project_on_offset_surface(&pt, distance, signed_distance));
+                }
+            }
+        }
+    }
+
+    // Emit one quad for each grid edge crossed by the surface, connecting the vertices of the
+    // four cells sharing that edge.
+    let mut indices = Vec::new();
+
+    for k in 1..dims.z {
+        for j in 1..dims.y {
+            for i in 1..dims.x {
+                let curr = [i, j, k];
+                let value = values[point_id(i, j, k)];
+
+                for axis in 0..3 {
+                    let mut next = curr;
+                    next[axis] += 1;
+
+                    if (value < 0.0) == (values[point_id(next[0], next[1], next[2])] < 0.0) {
+                        continue;
+                    }
+
+                    // The cells around the edge, counterclockwise around the axis.
+                    let (b, c) = ((axis + 1) % 3, (axis + 2) % 3);
+                    let quad = [(1, 1), (0, 1), (0, 0), (1, 0)].map(|(db, dc)| {
+                        let mut cell = curr;
+                        cell[b] -= db;
+                        cell[c] -= dc;
+                        cell_vertex[cell_id(cell[0], cell[1], cell[2])]
+                    });
+                    let [q0, q1, q2, q3] = if value < 0.0 {
+                        quad
+                    } else {
+                        [quad[3], quad[2], quad[1], quad[0]]
+                    };
+
+                    // Split the quad along its shortest diagonal.
+                    let diag02 =
+                        na::distance_squared(&vertices[q0 as usize], &vertices[q2 as usize]);
+                    let diag13 =
+                        na::distance_squared(&vertices[q1 as usize], &vertices[q3 as usize]);
+
+                    if diag02 <= diag13 {
+                        indices.push([q0, q1, q2]);
+                        indices.push([q0, q2, q3]);
+                    } else {
+                        indices.push([q0, q1, q3]);
+                        indices.push([q1, q2, q3]);
+                    }
+                }
+            }
+        }
+    }
+
+    TriMesh::new(vertices, indices).ok()
+}
+
+/// Moves a point close to the offset surface onto it, along the gradient of the signed distance
+/// field.
+fn project_on_offset_surface(
+    pt: &Point<Real>,
+    distance: Real,
+    signed_distance: impl Fn(&Point<Real>) -> (Point<Real>, Real),
+) -> Point<Real> {
+    let (closest, value) = signed_distance(pt);
+    let signed_dist = value + distance;
+
+    if signed_dist.abs() <= Real::EPSILON {
+        return *pt;
+    }
+
+    // The direction from the surface to the point, oriented outward.
+    let outward = (pt - closest) / signed_dist;
+    closest + outward * distance
+}
+
+/// Extrudes a triangle mesh into a closed shell of the given thickness.
+///
+/// The shell is bounded by the input mesh and by a copy of it offset along the opposite of its
+/// vertex normals, so that the input mesh forms its outer surface if `thickness` is positive,
+/// and its inner surface if `thickness` is negative. Each boundary loop of the input mesh is
+/// closed by a strip of triangles joining it to the offset copy. The triangles of the input
+/// mesh must be consistently oriented, and the triangles of the result are oriented outward.
+///
+/// The offset of each vertex is scaled up at sharp creases to keep the thickness of the shell
+/// uniform, up to three times the given thickness. Self-intersections of the offset copy, which
+/// can appear where the thickness is larger than the local curvature radius of the mesh, are
+/// not resolved.
+pub fn solidify_trimesh(mesh: &TriMesh, thickness: Real) -> TriMesh {
+    let vertices = mesh.vertices();
+    let indices = mesh.indices();
+    let num_vertices = vertices.len() as u32;
+
+    // Angle-weighted vertex normals.
+    let mut normals = alloc::vec![Vector::zeros(); vertices.len()];
+    let mut face_normals = Vec::with_capacity(indices.len());
+
+    for idx in indices {
+        let tri = Triangle::new(
+            vertices[idx[0] as usize],
+            vertices[idx[1] as usize],
+            vertices[idx[2] as usize],
+        );
+        let normal = tri.normal().map(|n| n.into_inner());
+
+        if let Some(n) = normal {
+            normals[idx[0] as usize] += n * (tri.b - tri.a).angle(&(tri.c - tri.a));
+            normals[idx[1] as usize] += n * (tri.a - tri.b).angle(&(tri.c - tri.b));
+            normals[idx[2] as usize] += n * (tri.b - tri.c).angle(&(tri.a - tri.c));
+        }
+
+        face_normals.push(normal);
+    }
+
+    for n in &mut normals {
+        *n = n.try_normalize(Real::EPSILON).unwrap_or_else(Vector::zeros);
+    }
+
+    // Scale the offsets by the inverse of the smallest cosine between the vertex normal and
+    // the normals of the adjacent triangles.
+    let mut min_cos: Vec<Real> = alloc::vec![1.0; vertices.len()];
+
+    for (idx, normal) in indices.iter().zip(face_normals.iter()) {
+        if let Some(n) = normal {
+            for i in idx {
+                let cos = normals[*i as usize].dot(n);
+                min_cos[*i as usize] = min_cos[*i as usize].min(cos);
+            }
+        }
+    }
+
+    let mut result_vertices = vertices.to_vec();
+    result_vertices.extend(vertices.iter().zip(normals.iter()).zip(min_cos.iter()).map(
+        |((pt, n), cos)| {
+            let scale = 1.0 / cos.max(1.0 / MAX_THICKNESS_SCALE);
+            pt - n * (thickness * scale)
+        },
+    ));
+
+    // The input triangles, and the reversed offset triangles.
+    let mut result_indices = indices.to_vec();
+    result_indices.extend(
+        indices
+            .iter()
+            .map(|idx| [idx[0], idx[2], idx[1]].map(|i| i + num_vertices)),
+    );
+
+    // Close the boundary half-edges with two triangles each.
+    let half_edges: HashSet<[u32; 2]> = indices
+        .iter()
+        .flat_map(|idx| [[idx[0], idx[1]], [idx[1], idx[2]], [idx[2], idx[0]]])
+        .collect();
+
+    for idx in indices {
+        for [u, v] in [[idx[0], idx[1]], [idx[1], idx[2]], [idx[2], idx[0]]] {
+            if !half_edges.contains(&[v, u]) {
+                result_indices.push([v, u, u + num_vertices]);
+                result_indices.push([v, u + num_vertices, v + num_vertices]);
+            }
+        }
+    }
+
+    if thickness < 0.0 {
+        for idx in &mut result_indices {
+            idx.swap(1, 2);
+        }
+    }
+
+    // The input mesh isn’t empty, so neither is the result.
+    TriMesh::new(result_vertices, result_indices).unwrap()
+}
@@ -1,7 +1,9 @@
 pub use self::parameters::VHACDParameters;
+pub use self::progress::{VHACDProgress, VHACDStage};
 pub use self::vhacd::VHACD;
 
 pub(crate) use self::vhacd::CutPlane;
 
 mod parameters;
+mod progress;
 mod vhacd;
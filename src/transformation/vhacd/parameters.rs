@@ -1,5 +1,6 @@
 use crate::math::Real;
 use crate::transformation::voxelization::FillMode;
+use crate::transformation::ConvexHullSimplificationParameters;
 
 /// Parameters controlling the VHACD convex decomposition.
 ///
@@ -33,8 +34,18 @@ pub struct VHACDParameters {
     /// Controls the precision of the convex-hull generation
     /// process during the clipping plane selection stage.
     ///
+    /// This doesn’t bound the number of vertices of the final convex hulls. Use
+    /// [`Self::convex_hull_simplification`] for this.
+    ///
     /// Default: 4
     pub convex_hull_downsampling: u32,
+    /// If set, the final convex hulls computed from the parts of the decomposition are
+    /// simplified with [`simplify_convex_hull`](crate::transformation::simplify_convex_hull) to
+    /// fit in the given budget of vertices and faces. Hulls that can’t be simplified are kept
+    /// unchanged.
+    ///
+    /// Default: `None`
+    pub convex_hull_simplification: Option<ConvexHullSimplificationParameters>,
     /// Controls the way the input mesh or polyline is being
     /// voxelized.
     ///
@@ -65,6 +76,7 @@ impl Default for VHACDParameters {
             concavity: 0.1,
             plane_downsampling: 4,
             convex_hull_downsampling: 4,
+            convex_hull_simplification: None,
             alpha: 0.05,
             beta: 0.05,
             convex_hull_approximation: true,
@@ -0,0 +1,31 @@
+/// A stage of the VHACD convex decomposition.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum VHACDStage {
+    /// The input polyline (in 2D) or triangle mesh (in 3D) is being voxelized.
+    Voxelization,
+    /// The voxelized volume is being recursively split into approximately convex parts.
+    Decomposition,
+}
+
+/// The progress of a VHACD convex decomposition, reported by [`VHACD::decompose_with_progress`].
+///
+/// [`VHACD::decompose_with_progress`]: super::VHACD::decompose_with_progress
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct VHACDProgress {
+    /// The current stage of the decomposition.
+    pub stage: VHACDStage,
+    /// The current splitting iteration, starting at 0.
+    ///
+    /// Each iteration attempts to split every part produced by the previous one.
+    pub iteration: u32,
+    /// The maximum number of splitting iterations, derived from
+    /// [`VHACDParameters::max_convex_hulls`](super::VHACDParameters::max_convex_hulls).
+    pub max_iterations: u32,
+    /// The number of parts processed during the current iteration.
+    pub processed_parts: usize,
+    /// The total number of parts to process during the current iteration.
+    pub iteration_parts: usize,
+    /// The current number of parts of the decomposition, including those that are still
+    /// going to be split.
+    pub num_parts: usize,
+}
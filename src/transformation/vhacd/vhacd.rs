@@ -17,10 +17,15 @@
 // > THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use crate::math::{Point, Real, Vector, DIM};
-use crate::transformation::vhacd::VHACDParameters;
+use crate::transformation::vhacd::{VHACDParameters, VHACDProgress, VHACDStage};
 use crate::transformation::voxelization::{VoxelSet, VoxelizedVolume};
+use crate::transformation::ConvexHullSimplificationParameters;
 use alloc::sync::Arc;
 use alloc::vec::Vec;
+use core::ops::ControlFlow;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 
 #[cfg(feature = "dim2")]
 type ConvexHull = Vec<Point<Real>>;
@@ -41,6 +46,7 @@ pub struct VHACD {
     voxel_parts: Vec<VoxelSet>,
     volume_ch0: Real,
     max_concavity: Real,
+    convex_hull_simplification: Option<ConvexHullSimplificationParameters>,
 }
 
 impl VHACD {
@@ -61,11 +67,50 @@ impl VHACD {
         indices: &[[u32; DIM]],
         keep_voxel_to_primitives_map: bool,
     ) -> Self {
+        Self::decompose_with_progress(
+            params,
+            points,
+            indices,
+            keep_voxel_to_primitives_map,
+            |_| ControlFlow::Continue(()),
+        )
+        .expect("the decomposition can only be cancelled by its progress callback")
+    }
+
+    /// Decompose the given polyline (in 2D) or triangle mesh (in 3D), reporting the progress
+    /// of the decomposition.
+    ///
+    /// This is the same as [`VHACD::decompose`], except that `progress` is called before the
+    /// voxelization, and after each part is processed during the decomposition. If it returns
+    /// [`ControlFlow::Break`], the decomposition is cancelled and `None` is returned.
+    ///
+    /// With the `parallel` feature enabled, the candidate clipping planes of each part are
+    /// evaluated in parallel. The result doesn’t depend on the number of threads.
+    pub fn decompose_with_progress(
+        params: &VHACDParameters,
+        points: &[Point<Real>],
+        indices: &[[u32; DIM]],
+        keep_voxel_to_primitives_map: bool,
+        mut progress: impl FnMut(&VHACDProgress) -> ControlFlow<()>,
+    ) -> Option<Self> {
         // if params.project_hull_vertices || params.fill_mode == FillMode::RAYCAST_FILL {
         //     self.raycast_mesh =
         //         RaycastMesh::create_raycast_mesh(num_points, points, num_triangles, triangles);
         // }
 
+        let voxelization_progress = VHACDProgress {
+            stage: VHACDStage::Voxelization,
+            iteration: 0,
+            max_iterations: decomposition_depth(params),
+            processed_parts: 0,
+            iteration_parts: 0,
+            num_parts: 0,
+        };
+
+        if progress(&voxelization_progress).is_break() {
+            return None;
+        }
+
         let voxelized = VoxelizedVolume::voxelize(
             points,
             indices,
@@ -75,27 +120,40 @@ impl VHACD {
             // &self.raycast_mesh,
         );
 
-        let mut result = Self::from_voxels(params, voxelized.into());
+        let mut result = Self::from_voxels_with_progress(params, voxelized.into(), progress)?;
 
         let primitive_classes = Arc::new(result.classify_primitives(indices.len()));
         for part in &mut result.voxel_parts {
             part.primitive_classes = primitive_classes.clone();
         }
 
-        result
+        Some(result)
     }
 
     /// Perform an approximate convex decomposition of a set of voxels.
     pub fn from_voxels(params: &VHACDParameters, voxels: VoxelSet) -> Self {
+        Self::from_voxels_with_progress(params, voxels, |_| ControlFlow::Continue(()))
+            .expect("the decomposition can only be cancelled by its progress callback")
+    }
+
+    /// Perform an approximate convex decomposition of a set of voxels, reporting its progress.
+    ///
+    /// See [`VHACD::decompose_with_progress`] for details on the `progress` callback.
+    pub fn from_voxels_with_progress(
+        params: &VHACDParameters,
+        voxels: VoxelSet,
+        mut progress: impl FnMut(&VHACDProgress) -> ControlFlow<()>,
+    ) -> Option<Self> {
         let mut result = Self {
             // raycast_mesh: None,
             voxel_parts: Vec::new(),
             volume_ch0: 0.0,
             max_concavity: -Real::MAX,
+            convex_hull_simplification: params.convex_hull_simplification,
         };
 
-        result.do_compute_acd(params, voxels);
-        result
+        result.do_compute_acd(params, voxels, &mut progress)?;
+        Some(result)
     }
 
     /// The almost-convex voxelized parts computed by the VHACD algorithm.
@@ -202,26 +260,15 @@ impl VHACD {
         convex_hull_downsampling: u32,
         params: &VHACDParameters,
     ) -> (CutPlane, Real) {
-        let mut best_plane = planes[0];
-        let mut min_concavity = Real::MAX;
-        let mut i_best = -1;
-        let mut min_total = Real::MAX;
-
-        let mut left_ch;
-        let mut right_ch;
-        let mut left_ch_pts = Vec::new();
-        let mut right_ch_pts = Vec::new();
-        let mut left_voxels = VoxelSet::new();
-        let mut right_voxels = VoxelSet::new();
         let mut on_surface_voxels = VoxelSet::new();
-
         input_voxels.select_on_surface(&mut on_surface_voxels);
 
-        for (x, plane) in planes.iter().enumerate() {
+        // Computes the total cost and the concavity of a plane.
+        let evaluate = |plane: &CutPlane| {
             // Compute convex hulls.
-            if params.convex_hull_approximation {
-                right_ch_pts.clear();
-                left_ch_pts.clear();
+            let (left_ch, right_ch) = if params.convex_hull_approximation {
+                let mut left_ch_pts = Vec::new();
+                let mut right_ch_pts = Vec::new();
 
                 on_surface_voxels.intersect(
                     plane,
@@ -239,13 +286,16 @@ impl VHACD {
                     &mut right_ch_pts,
                     &mut left_ch_pts,
                 );
-                right_ch = convex_hull(&right_ch_pts);
-                left_ch = convex_hull(&left_ch_pts);
+                (convex_hull(&left_ch_pts), convex_hull(&right_ch_pts))
             } else {
+                let mut left_voxels = VoxelSet::new();
+                let mut right_voxels = VoxelSet::new();
                 on_surface_voxels.clip(plane, &mut right_voxels, &mut left_voxels);
-                right_ch = right_voxels.compute_convex_hull(convex_hull_downsampling);
-                left_ch = left_voxels.compute_convex_hull(convex_hull_downsampling);
-            }
+                (
+                    left_voxels.compute_convex_hull(convex_hull_downsampling),
+                    right_voxels.compute_convex_hull(convex_hull_downsampling),
+                )
+            };
 
             let volume_left_ch = compute_volume(&left_ch);
             let volume_right_ch = compute_volume(&right_ch);
@@ -262,11 +312,26 @@ impl VHACD {
             let symmetry = beta * d;
             let total = concavity + balance + symmetry;
 
-            if total < min_total || (total == min_total && (x as i32) < i_best) {
+            (total, concavity)
+        };
+
+        // NOTE: each plane is evaluated independently, and the best one is selected sequentially
+        //       below, so the result doesn’t depend on the number of threads.
+        #[cfg(feature = "parallel")]
+        let costs: Vec<_> = planes.par_iter().map(evaluate).collect();
+        #[cfg(not(feature = "parallel"))]
+        let costs: Vec<_> = planes.iter().map(evaluate).collect();
+
+        let mut best_plane = planes[0];
+        let mut min_concavity = Real::MAX;
+        let mut min_total = Real::MAX;
+
+        // In case of ties, the first plane is selected.
+        for (plane, (total, concavity)) in planes.iter().zip(costs) {
+            if total < min_total {
                 min_concavity = concavity;
                 best_plane = *plane;
                 min_total = total;
-                i_best = x as i32;
             }
         }
 
@@ -359,7 +424,12 @@ impl VHACD {
         }
     }
 
-    fn do_compute_acd(&mut self, params: &VHACDParameters, mut voxels: VoxelSet) {
+    fn do_compute_acd(
+        &mut self,
+        params: &VHACDParameters,
+        mut voxels: VoxelSet,
+        progress: &mut dyn FnMut(&VHACDProgress) -> ControlFlow<()>,
+    ) -> Option<()> {
         let intersections = voxels.intersections.clone();
         let mut input_parts = Vec::new();
         let mut parts = Vec::new();
@@ -369,34 +439,16 @@ impl VHACD {
         let mut first_iteration = true;
         self.volume_ch0 = 1.0;
 
-        // Compute the decomposition depth based on the number of convex hulls being requested.
-        let mut hull_count = 2;
-        let mut depth = 1;
+        let depth = decomposition_depth(params);
 
-        while params.max_convex_hulls > hull_count {
-            depth += 1;
-            hull_count *= 2;
-        }
-
-        // We must always increment the decomposition depth one higher than the maximum number of hulls requested.
-        // The reason for this is as follows.
-        // Say, for example, the user requests 32 convex hulls exactly.  This would be a decomposition depth of 5.
-        // However, when we do that, we do *not* necessarily get 32 hulls as a result.  This is because, during
-        // the recursive descent of the binary tree, one or more of the leaf nodes may have no concavity and
-        // will not be split.  So, in this way, even with a decomposition depth of 5, you can produce fewer than
-        // 32 hulls.  So, in this case, we would set the decomposition depth to 6 (producing up to as high as 64 convex
-        // hulls). Then, the merge step which combines over-described hulls down to the user requested amount, we will end
-        // up getting exactly 32 convex hulls as a result. We could just allow the artist to directly control the
-        // decomposition depth directly, but this would be a bit too complex and the preference is simply to let them
-        // specify how many hulls they want and derive the solution from that.
-        depth += 1;
-
-        for _ in 0..depth {
+        for iteration in 0..depth {
             if input_parts.is_empty() {
                 break;
             }
 
-            for input_part in input_parts.drain(..) {
+            let iteration_parts = input_parts.len();
+
+            for (i, input_part) in input_parts.drain(..).enumerate() {
                 self.process_primitive_set(
                     params,
                     first_iteration,
@@ -405,6 +457,19 @@ impl VHACD {
                     input_part,
                 );
                 first_iteration = false;
+
+                let decomposition_progress = VHACDProgress {
+                    stage: VHACDStage::Decomposition,
+                    iteration,
+                    max_iterations: depth,
+                    processed_parts: i + 1,
+                    iteration_parts,
+                    num_parts: parts.len() + temp.len() + iteration_parts - i - 1,
+                };
+
+                if progress(&decomposition_progress).is_break() {
+                    return None;
+                }
             }
 
             core::mem::swap(&mut input_parts, &mut temp);
@@ -421,6 +486,8 @@ impl VHACD {
         for part in &mut self.voxel_parts {
             part.intersections = intersections.clone();
         }
+
+        Some(())
     }
 
     // Returns a vector such that `result[i]` gives the index of the voxelized convex part that
@@ -467,10 +534,7 @@ impl VHACD {
         points: &[Point<Real>],
         indices: &[[u32; DIM]],
     ) -> Vec<Vec<Point<Real>>> {
-        self.voxel_parts
-            .iter()
-            .map(|part| part.compute_primitive_intersections(points, indices))
-            .collect()
+        self.map_voxel_parts(|part| part.compute_primitive_intersections(points, indices))
     }
 
     /// Compute the convex-hulls of the parts computed by this approximate convex-decomposition,
@@ -484,10 +548,10 @@ impl VHACD {
         points: &[Point<Real>],
         indices: &[[u32; DIM]],
     ) -> Vec<Vec<Point<Real>>> {
-        self.voxel_parts
-            .iter()
-            .map(|part| part.compute_exact_convex_hull(points, indices))
-            .collect()
+        self.map_voxel_parts(|part| {
+            let hull = part.compute_exact_convex_hull(points, indices);
+            simplify_convex_hull(hull, self.convex_hull_simplification.as_ref())
+        })
     }
 
     /// Compute the convex-hulls of the parts computed by this approximate convex-decomposition,
@@ -501,10 +565,10 @@ impl VHACD {
         points: &[Point<Real>],
         indices: &[[u32; DIM]],
     ) -> Vec<(Vec<Point<Real>>, Vec<[u32; DIM]>)> {
-        self.voxel_parts
-            .iter()
-            .map(|part| part.compute_exact_convex_hull(points, indices))
-            .collect()
+        self.map_voxel_parts(|part| {
+            let hull = part.compute_exact_convex_hull(points, indices);
+            simplify_convex_hull(hull, self.convex_hull_simplification.as_ref())
+        })
     }
 
     /// Compute the convex hulls of the voxelized approximately-convex parts
@@ -515,10 +579,10 @@ impl VHACD {
     #[cfg(feature = "dim2")]
     pub fn compute_convex_hulls(&self, downsampling: u32) -> Vec<Vec<Point<Real>>> {
         let downsampling = downsampling.max(1);
-        self.voxel_parts
-            .iter()
-            .map(|part| part.compute_convex_hull(downsampling))
-            .collect()
+        self.map_voxel_parts(|part| {
+            let hull = part.compute_convex_hull(downsampling);
+            simplify_convex_hull(hull, self.convex_hull_simplification.as_ref())
+        })
     }
 
     /// Compute the convex hulls of the voxelized approximately-convex parts
@@ -532,13 +596,49 @@ impl VHACD {
         downsampling: u32,
     ) -> Vec<(Vec<Point<Real>>, Vec<[u32; DIM]>)> {
         let downsampling = downsampling.max(1);
-        self.voxel_parts
-            .iter()
-            .map(|part| part.compute_convex_hull(downsampling))
-            .collect()
+        self.map_voxel_parts(|part| {
+            let hull = part.compute_convex_hull(downsampling);
+            simplify_convex_hull(hull, self.convex_hull_simplification.as_ref())
+        })
+    }
+
+    /// Maps each voxelized part, in parallel if the `parallel` feature is enabled.
+    fn map_voxel_parts<T: Send>(&self, f: impl Fn(&VoxelSet) -> T + Sync + Send) -> Vec<T> {
+        #[cfg(feature = "parallel")]
+        let parts = self.voxel_parts.par_iter();
+        #[cfg(not(feature = "parallel"))]
+        let parts = self.voxel_parts.iter();
+
+        parts.map(f).collect()
     }
 }
 
+/// The maximum number of splitting iterations of the decomposition.
+fn decomposition_depth(params: &VHACDParameters) -> u32 {
+    let mut hull_count = 2;
+    let mut depth = 1;
+
+    while params.max_convex_hulls > hull_count {
+        depth += 1;
+        hull_count *= 2;
+    }
+
+    // We must always increment the decomposition depth one higher than the maximum number of hulls requested.
+    // The reason for this is as follows.
+    // Say, for example, the user requests 32 convex hulls exactly.  This would be a decomposition depth of 5.
+    // However, when we do that, we do *not* necessarily get 32 hulls as a result.  This is because, during
+    // the recursive descent of the binary tree, one or more of the leaf nodes may have no concavity and
+    // will not be split.  So, in this way, even with a decomposition depth of 5, you can produce fewer than
+    // 32 hulls.  So, in this case, we would set the decomposition depth to 6 (producing up to as high as 64 convex
+    // hulls). Then, the merge step which combines over-described hulls down to the user requested amount, we will end
+    // up getting exactly 32 convex hulls as a result. We could just allow the artist to directly control the
+    // decomposition depth directly, but this would be a bit too complex and the preference is simply to let them
+    // specify how many hulls they want and derive the solution from that.
+    depth += 1;
+
+    depth
+}
+
 fn compute_concavity(volume: Real, volume_ch: Real, volume0: Real) -> Real {
     (volume_ch - volume).abs() / volume0
 }
@@ -581,6 +681,28 @@ fn convex_hull(vertices: &[Point<Real>]) -> (Vec<Point<Real>>, Vec<[u32; DIM]>)
     }
 }
 
+#[cfg(feature = "dim2")]
+fn simplify_convex_hull(
+    hull: ConvexHull,
+    params: Option<&ConvexHullSimplificationParameters>,
+) -> ConvexHull {
+    params
+        .and_then(|params| crate::transformation::simplify_convex_hull(&hull, params))
+        .map(|polygon| polygon.points().to_vec())
+        .unwrap_or(hull)
+}
+
+#[cfg(feature = "dim3")]
+fn simplify_convex_hull(
+    hull: ConvexHull,
+    params: Option<&ConvexHullSimplificationParameters>,
+) -> ConvexHull {
+    params
+        .and_then(|params| crate::transformation::simplify_convex_hull(&hull.0, params))
+        .map(|poly| poly.to_trimesh())
+        .unwrap_or(hull)
+}
+
 #[cfg(feature = "dim2")]
 fn compute_volume(polygon: &[Point<Real>]) -> Real {
     if !polygon.is_empty() {
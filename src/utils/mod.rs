@@ -5,7 +5,6 @@ pub use self::center::center;
 #[cfg(feature = "std")]
 pub use self::deterministic_state::DeterministicState;
 
-#[cfg(feature = "dim3")]
 #[cfg(feature = "alloc")]
 pub use self::cleanup::remove_unused_points;
 pub(crate) use self::inv::inv;
@@ -32,14 +31,13 @@ pub use self::segments_intersection::{segments_intersection2d, SegmentsIntersect
 pub use self::sort::sort2;
 pub use self::sort::sort3;
 pub use self::sorted_pair::SortedPair;
-#[cfg(all(feature = "dim3", feature = "spade"))]
+#[cfg(feature = "spade")]
 pub(crate) use self::spade::sanitize_spade_point;
 pub(crate) use self::wops::{simd_swap, WBasis, WCross, WSign};
 
 mod as_bytes;
 mod ccw_face_normal;
 mod center;
-#[cfg(feature = "dim3")]
 #[cfg(feature = "alloc")]
 mod cleanup;
 mod consts;
@@ -68,7 +66,7 @@ mod sdp_matrix;
 mod segments_intersection;
 mod sort;
 mod sorted_pair;
-#[cfg(all(feature = "dim3", feature = "spade"))]
+#[cfg(feature = "spade")]
 mod spade;
 mod wops;
 